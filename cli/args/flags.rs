@@ -4,6 +4,7 @@ use clap::builder::FalseyValueParser;
 use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
@@ -68,6 +69,9 @@ pub struct CompileFlags {
   pub target: Option<String>,
   pub no_terminal: bool,
   pub include: Vec<String>,
+  pub include_dir: Vec<String>,
+  pub compress: bool,
+  pub obfuscate: bool,
 }
 
 impl CompileFlags {
@@ -91,6 +95,37 @@ pub enum CoverageType {
   Detailed,
   Lcov,
   Html,
+  Cobertura,
+  Json,
+}
+
+/// Minimum coverage percentages, which make `deno coverage` fail when the
+/// coverage is below them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub struct CoverageThresholds {
+  pub lines: Option<u8>,
+  pub branches: Option<u8>,
+  pub functions: Option<u8>,
+  /// Minimum line coverage of each file.
+  pub per_file: Option<u8>,
+}
+
+impl CoverageThresholds {
+  pub fn is_empty(&self) -> bool {
+    self.lines.is_none()
+      && self.branches.is_none()
+      && self.functions.is_none()
+      && self.per_file.is_none()
+  }
+}
+
+/// The changes to report the diff coverage of.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CoverageDiffBase {
+  /// Changes since a git ref, including uncommitted changes.
+  GitRef(String),
+  /// A file containing a unified diff.
+  File(PathBuf),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
@@ -100,6 +135,10 @@ pub struct CoverageFlags {
   pub include: Vec<String>,
   pub exclude: Vec<String>,
   pub r#type: CoverageType,
+  pub thresholds: CoverageThresholds,
+  pub diff_base: Option<CoverageDiffBase>,
+  /// Minimum coverage percentage of the changed lines.
+  pub diff_threshold: Option<u8>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -139,6 +178,7 @@ pub struct EvalFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FmtFlags {
   pub check: bool,
+  pub changed_since: Option<String>,
   pub files: FileFlags,
   pub use_tabs: Option<bool>,
   pub line_width: Option<NonZeroU32>,
@@ -146,6 +186,9 @@ pub struct FmtFlags {
   pub single_quote: Option<bool>,
   pub prose_wrap: Option<String>,
   pub no_semicolons: Option<bool>,
+  pub unstable_css: bool,
+  pub unstable_html: bool,
+  pub unstable_yaml: bool,
   pub watch: Option<WatchFlags>,
 }
 
@@ -165,6 +208,7 @@ pub struct InitFlags {
 pub struct InfoFlags {
   pub json: bool,
   pub file: Option<String>,
+  pub extract: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -176,6 +220,19 @@ pub struct InstallFlags {
   pub force: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InstalledScriptsAction {
+  List,
+  /// Upgrades the scripts with the provided names or all of them when empty.
+  Upgrade(Vec<String>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstalledScriptsFlags {
+  pub action: InstalledScriptsAction,
+  pub root: Option<String>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct JupyterFlags {
   pub install: bool,
@@ -198,6 +255,10 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub sarif: bool,
+  pub github: bool,
+  pub fix: bool,
+  pub fix_dry_run: bool,
   pub watch: Option<WatchFlags>,
 }
 
@@ -219,6 +280,7 @@ pub struct ReplFlags {
 pub struct RunFlags {
   pub script: String,
   pub watch: Option<WatchFlagsWithPaths>,
+  pub coverage_dir: Option<String>,
 }
 
 impl RunFlags {
@@ -227,6 +289,7 @@ impl RunFlags {
     Self {
       script,
       watch: None,
+      coverage_dir: None,
     }
   }
 
@@ -252,6 +315,12 @@ pub struct WatchFlagsWithPaths {
 pub struct TaskFlags {
   pub cwd: Option<String>,
   pub task: Option<String>,
+  /// Run the task and the other tasks provided as arguments at the same time.
+  pub parallel: bool,
+  /// Run the task in every workspace member which defines it.
+  pub recursive: bool,
+  /// Only run the task in the workspace members whose name matches.
+  pub filter: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -317,6 +386,7 @@ pub enum DenoSubcommand {
   Init(InitFlags),
   Info(InfoFlags),
   Install(InstallFlags),
+  InstalledScripts(InstalledScriptsFlags),
   Jupyter(JupyterFlags),
   Uninstall(UninstallFlags),
   Lsp,
@@ -758,8 +828,8 @@ impl Flags {
         std::env::current_dir().ok()
       }
       Bundle(_) | Completions(_) | Doc(_) | Fmt(_) | Init(_) | Install(_)
-      | Uninstall(_) | Jupyter(_) | Lsp | Lint(_) | Types | Upgrade(_)
-      | Vendor(_) | Publish(_) => None,
+      | InstalledScripts(_) | Uninstall(_) | Jupyter(_) | Lsp | Lint(_)
+      | Types | Upgrade(_) | Vendor(_) | Publish(_) => None,
     }
   }
 
@@ -1242,6 +1312,11 @@ is something generic like 'main', 'mod', 'index' or 'cli', and the path has no
 parent, take the file name of the parent path. Otherwise settle with the
 generic name. If the resulting name has an '@...' suffix, strip it.
 
+Static assets, such as HTML files and templates, can be embedded in the
+executable with the `--include-dir` flag:
+
+  deno compile --include-dir ./public main.ts
+
 Cross-compiling to different target architectures is supported using the
 `--target` flag. On the first invocation with deno will download proper
 binary and cache it in $DENO_DIR. The aarch64-apple-darwin target is not
@@ -1264,6 +1339,44 @@ supported in canary.
           .action(ArgAction::Append)
           .value_hint(ValueHint::FilePath),
       )
+      .arg(
+        Arg::new("include-dir")
+          .long("include-dir")
+          .help("Directory of assets to embed in the executable")
+          .long_help(
+            "Embeds the files of a directory in the compiled executable, where
+    they can be read at their original paths with the file system APIs and
+    fetch, such as 'fetch(import.meta.resolve(\"./public/index.html\"))'.
+    The files keep the absolute paths they had when compiling, so resolve
+    them relative to 'import.meta.url' instead of the current directory.
+    This flag can be passed multiple times, to include multiple directories.",
+          )
+          .action(ArgAction::Append)
+          .value_hint(ValueHint::DirPath),
+      )
+      .arg(
+        Arg::new("compress")
+          .long("compress")
+          .help("Compress the code and files embedded in the executable")
+          .long_help(
+            "Compresses the modules, npm packages and included directories
+    embedded in the executable with zstd. Files are decompressed when the
+    executable first reads them.",
+          )
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("obfuscate")
+          .long("obfuscate")
+          .help("Obfuscate the code and files embedded in the executable")
+          .long_help(
+            "Obfuscates the modules, npm packages and included directories
+    embedded in the executable, so that sources aren't stored as plaintext.
+    This is not a security measure: the key is stored in the executable in
+    order for it to run, so the sources can still be extracted from it.",
+          )
+          .action(ArgAction::SetTrue),
+      )
       .arg(
         Arg::new("output")
           .long("output")
@@ -1324,6 +1437,10 @@ Collect a coverage profile with deno test:
 
   deno test --coverage=cov_profile
 
+Or with deno run:
+
+  deno run --coverage=cov_profile main.ts
+
 Print a report to stdout:
 
   deno coverage cov_profile
@@ -1349,6 +1466,22 @@ Write a report using the lcov format:
 Generate html reports from lcov:
 
   genhtml -o html_cov cov.lcov
+
+Write a report using the Cobertura XML format:
+
+  deno coverage --cobertura --output=cobertura.xml cov_profile/
+
+Write a JSON summary of the line, branch and function coverage of each file:
+
+  deno coverage --json --output=coverage.json cov_profile/
+
+Fail when the line or branch coverage is below a percentage:
+
+  deno coverage --threshold-lines=80 --threshold-branches=70 cov_profile
+
+Report the coverage of the lines changed since a git ref:
+
+  deno coverage --diff-base=main --diff-threshold=90 cov_profile
 ",
     )
     .defer(|cmd| {
@@ -1388,15 +1521,32 @@ Generate html reports from lcov:
             .help("Output coverage report in lcov format")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("cobertura")
+            .long("cobertura")
+            .help("Output coverage report in Cobertura XML format")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .help("Output a JSON summary of the coverage of each file")
+            .action(ArgAction::SetTrue),
+        )
+        .group(
+          ArgGroup::new("file-reporter")
+            .args(["lcov", "cobertura", "json"])
+            .multiple(false),
+        )
         .arg(
           Arg::new("output")
-            .requires("lcov")
+            .requires("file-reporter")
             .long("output")
             // todo(dsherret): remove value_parser!(PathBuf) and instead parse as string
             .value_parser(value_parser!(PathBuf))
-            .help("Output file (defaults to stdout) for lcov")
+            .help("Output file (defaults to stdout) for lcov, cobertura or json")
             .long_help(
-              "Exports the coverage report in lcov format to the given file.
+              "Exports the coverage report in lcov, Cobertura or JSON format to the given file.
     Filename should be passed along with '=' For example '--output=foo.lcov'
     If no --output arg is specified then the report is written to stdout.",
             )
@@ -1417,6 +1567,54 @@ Generate html reports from lcov:
             .help("Output coverage report in detailed format in the terminal.")
             .action(ArgAction::SetTrue),
         )
+        .arg(coverage_threshold_arg("threshold-lines", "line"))
+        .arg(coverage_threshold_arg("threshold-branches", "branch"))
+        .arg(coverage_threshold_arg("threshold-functions", "function"))
+        .arg(
+          Arg::new("threshold-per-file")
+            .long("threshold-per-file")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help(
+              "Fail if the line coverage of any file is below the percentage",
+            ),
+        )
+        .arg(
+          Arg::new("diff-base")
+            .long("diff-base")
+            .value_name("GIT_REF")
+            .require_equals(true)
+            .help("Report the coverage of the lines changed since the git ref"),
+        )
+        .arg(
+          Arg::new("diff-file")
+            .long("diff-file")
+            .value_name("FILE")
+            .require_equals(true)
+            .value_parser(value_parser!(PathBuf))
+            .help("Report the coverage of the lines changed by a unified diff")
+            .long_help(
+              "Report the coverage of the lines changed by a unified diff, \
+such as the output of `git diff`. The paths in the diff are resolved relative \
+to the current working directory.",
+            )
+            .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+          Arg::new("diff-threshold")
+            .long("diff-threshold")
+            .requires("diff")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help("Fail if the coverage of the changed lines is below the percentage"),
+        )
+        .group(
+          ArgGroup::new("diff")
+            .args(["diff-base", "diff-file"])
+            .multiple(false),
+        )
         .arg(
           Arg::new("files")
             .num_args(0..)
@@ -1426,6 +1624,17 @@ Generate html reports from lcov:
     })
 }
 
+fn coverage_threshold_arg(name: &'static str, kind: &str) -> Arg {
+  Arg::new(name)
+    .long(name)
+    .value_name("PERCENT")
+    .require_equals(true)
+    .value_parser(value_parser!(u8).range(0..=100))
+    .help(format!(
+      "Fail if the total {kind} coverage is below the percentage"
+    ))
+}
+
 fn doc_subcommand() -> Command {
   Command::new("doc")
     .about("Show documentation for a module")
@@ -1595,6 +1804,14 @@ fn fmt_subcommand() -> Command {
   deno fmt myfile1.ts myfile2.ts
   deno fmt --check
 
+Only format the lines changed since a git ref:
+
+  deno fmt --changed-since=main
+
+Format CSS, HTML, and YAML files, which is unstable:
+
+  deno fmt --unstable-css --unstable-html --unstable-yaml
+
 Format stdin and write to stdout:
 
   cat file.ts | deno fmt -
@@ -1617,6 +1834,17 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             .help("Check if the source files are formatted")
             .num_args(0),
         )
+        .arg(
+          Arg::new("changed-since")
+            .long("changed-since")
+            .value_name("GIT_REF")
+            .require_equals(true)
+            .help("Only format the lines changed since the git ref")
+            .long_help(
+              "Only format the lines which were changed since the git ref, \
+including uncommitted changes. Untracked files are formatted entirely.",
+            ),
+        )
         .arg(
           Arg::new("ext")
             .long("ext")
@@ -1624,9 +1852,28 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             // prefer using ts for formatting instead of js because ts works in more scenarios
             .default_value("ts")
             .value_parser([
-              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "ipynb",
+              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "ipynb", "css",
+              "scss", "sass", "less", "html", "htm", "yml", "yaml",
             ]),
         )
+        .arg(
+          Arg::new("unstable-css")
+            .long("unstable-css")
+            .help("Enable formatting CSS, SCSS, Sass and Less files")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("unstable-html")
+            .long("unstable-html")
+            .help("Enable formatting HTML files")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("unstable-yaml")
+            .long("unstable-yaml")
+            .help("Enable formatting YAML files")
+            .action(ArgAction::SetTrue),
+        )
         .arg(
           Arg::new("ignore")
             .long("ignore")
@@ -1732,6 +1979,15 @@ type: JavaScript, TypeScript, or JSON.
 emit: Local path of compiled source code. (TypeScript only.)
 dependencies: Dependency tree of the source file.
 
+Get information about an executable created with 'deno compile', such as
+its entrypoint, permissions, modules and npm packages:
+
+  deno info ./my_app
+
+Its embedded modules can be extracted with --extract:
+
+  deno info --extract ./my_app_sources ./my_app
+
 Without any additional arguments, 'deno info' shows:
 
 DENO_DIR: Directory containing Deno-managed files.
@@ -1762,6 +2018,15 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
           .long("json")
           .help("UNSTABLE: Outputs the information in JSON format")
           .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("extract")
+          .long("extract")
+          .value_name("DIR")
+          .requires("file")
+          .help("Extract the modules embedded in a compiled executable to a directory")
+          .value_parser(value_parser!(PathBuf))
+          .value_hint(ValueHint::DirPath),
       ))
 }
 
@@ -1795,9 +2060,38 @@ The installation root is determined, in order of precedence:
   - DENO_INSTALL_ROOT environment variable
   - $HOME/.deno
 
-These must be added to the path manually if required.")
-    .defer(|cmd| runtime_args(cmd, true, true).arg(Arg::new("cmd").required(true).num_args(1..).value_hint(ValueHint::FilePath))
-      .arg(check_arg(true))
+These must be added to the path manually if required.
+
+To list the installed scripts with their URLs and flags, use --list:
+
+  deno install --list
+
+To upgrade installed scripts to the latest versions of their npm packages or
+versioned URLs, reinstalling them with the same flags, use --upgrade with the
+names of the scripts or without names to upgrade all of them:
+
+  deno install --upgrade file_server")
+    .defer(|cmd| {
+      let cmd = runtime_args(cmd, true, true).arg(check_arg(true));
+      // scripts are upgraded with the flags they were installed with
+      let runtime_arg_ids = cmd
+        .get_arguments()
+        .map(|arg| arg.get_id().clone())
+        .collect::<Vec<_>>();
+      cmd.arg(Arg::new("cmd").required_unless_present_any(["list", "upgrade"]).num_args(1..).value_hint(ValueHint::FilePath))
+      .arg(
+        Arg::new("list")
+          .long("list")
+          .help("List the installed scripts")
+          .conflicts_with_all(["cmd", "upgrade", "name", "force"])
+          .action(ArgAction::SetTrue))
+      .arg(
+        Arg::new("upgrade")
+          .long("upgrade")
+          .help("Upgrade the installed scripts with the provided names, or all of them")
+          .conflicts_with_all(["name", "force", "env"])
+          .conflicts_with_all(runtime_arg_ids)
+          .action(ArgAction::SetTrue))
       .arg(
         Arg::new("name")
           .long("name")
@@ -1815,7 +2109,7 @@ These must be added to the path manually if required.")
           .short('f')
           .help("Forcefully overwrite existing installation")
           .action(ArgAction::SetTrue))
-      )
+    })
       .arg(env_file_arg())
 }
 
@@ -1903,6 +2197,11 @@ Print result as JSON:
 
   deno lint --json
 
+Print result in the SARIF format, or as GitHub Actions workflow annotations:
+
+  deno lint --sarif
+  deno lint --github
+
 Read from stdin:
 
   cat file.ts | deno lint -
@@ -1912,6 +2211,14 @@ List available rules:
 
   deno lint --rules
 
+Apply the safe fixes of the reported problems to the files:
+
+  deno lint --fix
+
+Print the changes the fixes would make without writing them:
+
+  deno lint --fix-dry-run
+
 Ignore diagnostics on the next line by preceding it with an ignore comment and
 rule name:
 
@@ -1984,6 +2291,33 @@ Ignore linting a file by adding an ignore comment at the top of the file:
             .action(ArgAction::SetTrue)
             .conflicts_with("json"),
         )
+        .arg(
+          Arg::new("sarif")
+            .long("sarif")
+            .help("Output lint result in SARIF format")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["json", "compact"]),
+        )
+        .arg(
+          Arg::new("github")
+            .long("github")
+            .help("Output lint result as GitHub Actions workflow annotations")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["json", "compact", "sarif"]),
+        )
+        .arg(
+          Arg::new("fix")
+            .long("fix")
+            .help("Fix the problems that have a safe automatic fix")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("fix-dry-run")
+            .long("fix-dry-run")
+            .help("Print the changes fixes would make without writing them")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("fix"),
+        )
         .arg(
           Arg::new("files")
             .num_args(1..)
@@ -2032,6 +2366,7 @@ fn run_subcommand() -> Command {
         .trailing_var_arg(true),
     )
     .arg(env_file_arg())
+    .arg(coverage_arg())
     .about("Run a JavaScript or TypeScript program")
     .long_about(
       "Run a JavaScript or TypeScript program
@@ -2055,7 +2390,12 @@ Grant permission to read allow-listed files from disk:
 
 Specifying the filename '-' to read the file from stdin.
 
-  curl https://examples.deno.land/hello-world.ts | deno run -",
+  curl https://examples.deno.land/hello-world.ts | deno run -
+
+Collect coverage of the program, its web workers and any Deno subprocesses
+it spawns, to be reported with 'deno coverage':
+
+  deno run --coverage=cov_profile main.ts",
     )
 }
 
@@ -2065,7 +2405,46 @@ fn task_subcommand() -> Command {
     .long_about(
       "Run a task defined in the configuration file
 
-  deno task build",
+  deno task build
+
+Tasks can depend on other tasks, which are run first. Tasks that don't depend
+on each other are run at the same time:
+
+  {
+    \"tasks\": {
+      \"codegen\": \"deno run -A codegen.ts\",
+      \"build\": {
+        \"command\": \"deno run -A build.ts\",
+        \"dependsOn\": [\"codegen\"]
+      }
+    }
+  }
+
+Run several tasks at the same time:
+
+  deno task --parallel lint test
+
+A task with \"inputs\" is skipped when its inputs, command and environment
+didn't change since its last successful run and its \"outputs\" exist:
+
+  {
+    \"tasks\": {
+      \"codegen\": {
+        \"command\": \"deno run -A codegen.ts\",
+        \"inputs\": [\"schema/**/*.json\"],
+        \"outputs\": [\"generated/\"]
+      }
+    }
+  }
+
+Run a task in every workspace member which defines it, following the
+dependencies between the members:
+
+  deno task --recursive build
+
+Only run it in some of the members:
+
+  deno task --recursive --filter \"@scope/*\" build",
     )
     .defer(|cmd| {
       cmd
@@ -2079,6 +2458,26 @@ fn task_subcommand() -> Command {
             .help("Specify the directory to run the task in")
             .value_hint(ValueHint::DirPath),
         )
+        .arg(
+          Arg::new("parallel")
+            .long("parallel")
+            .help("Run the task and the other tasks provided as arguments at the same time")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("recursive")
+            .long("recursive")
+            .conflicts_with("cwd")
+            .help("Run the task in every workspace member which defines it")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("filter")
+            .long("filter")
+            .value_name("PATTERN")
+            .requires("recursive")
+            .help("Only run the task in the workspace members whose name matches the pattern, which can contain '*' wildcards"),
+        )
     })
 }
 
@@ -2158,18 +2557,7 @@ Directory arguments are expanded to all contained files matching the glob
         .require_equals(true)
         .value_parser(value_parser!(u64)),
     )
-    .arg(
-      Arg::new("coverage")
-        .long("coverage")
-        .value_name("DIR")
-        .num_args(0..=1)
-        .require_equals(true)
-        .default_missing_value("coverage")
-        .conflicts_with("inspect")
-        .conflicts_with("inspect-wait")
-        .conflicts_with("inspect-brk")
-        .help("Collect coverage profile data into DIR. If DIR is not specified, it uses 'coverage/'."),
-    )
+    .arg(coverage_arg())
     .arg(
       Arg::new("parallel")
         .long("parallel")
@@ -2292,11 +2680,16 @@ fn vendor_subcommand() -> Command {
 
 Analyzes the provided modules along with their dependencies, downloads
 remote modules to the output directory, and produces an import map that
-maps remote specifiers to the downloaded files.
+maps remote specifiers to the downloaded files. npm packages are copied
+to the npm directory of the output directory, which is used instead of
+the global npm cache when the vendored import map is in use.
 
   deno vendor main.ts
   deno run --import-map vendor/import_map.json main.ts
 
+Running it again updates the output directory, removing the files which
+are no longer used.
+
 Remote modules and multiple modules may also be specified:
 
   deno vendor main.ts test.deps.ts https://deno.land/std/path/mod.ts",
@@ -2822,6 +3215,19 @@ fn import_map_arg() -> Arg {
     .value_hint(ValueHint::FilePath)
 }
 
+fn coverage_arg() -> Arg {
+  Arg::new("coverage")
+    .long("coverage")
+    .value_name("DIR")
+    .num_args(0..=1)
+    .require_equals(true)
+    .default_missing_value("coverage")
+    .conflicts_with("inspect")
+    .conflicts_with("inspect-wait")
+    .conflicts_with("inspect-brk")
+    .help("Collect coverage profile data into DIR. If DIR is not specified, it uses 'coverage/'.")
+}
+
 fn env_file_arg() -> Arg {
   Arg::new("env")
     .long("env")
@@ -3263,6 +3669,12 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Some(f) => f.collect(),
     None => vec![],
   };
+  let include_dir = match matches.remove_many::<String>("include-dir") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let compress = matches.get_flag("compress");
+  let obfuscate = matches.get_flag("obfuscate");
   ext_arg_parse(flags, matches);
 
   flags.subcommand = DenoSubcommand::Compile(CompileFlags {
@@ -3272,6 +3684,9 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     target,
     no_terminal,
     include,
+    include_dir,
+    compress,
+    obfuscate,
   });
 }
 
@@ -3323,6 +3738,10 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
   let r#type = if matches.get_flag("lcov") {
     CoverageType::Lcov
+  } else if matches.get_flag("cobertura") {
+    CoverageType::Cobertura
+  } else if matches.get_flag("json") {
+    CoverageType::Json
   } else if matches.get_flag("html") {
     CoverageType::Html
   } else if matches.get_flag("detailed") {
@@ -3331,6 +3750,21 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     CoverageType::Summary
   };
   let output = matches.remove_one::<PathBuf>("output");
+  let thresholds = CoverageThresholds {
+    lines: matches.remove_one::<u8>("threshold-lines"),
+    branches: matches.remove_one::<u8>("threshold-branches"),
+    functions: matches.remove_one::<u8>("threshold-functions"),
+    per_file: matches.remove_one::<u8>("threshold-per-file"),
+  };
+  let diff_base =
+    if let Some(git_ref) = matches.remove_one::<String>("diff-base") {
+      Some(CoverageDiffBase::GitRef(git_ref))
+    } else {
+      matches
+        .remove_one::<PathBuf>("diff-file")
+        .map(CoverageDiffBase::File)
+    };
+  let diff_threshold = matches.remove_one::<u8>("diff-threshold");
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
@@ -3340,6 +3774,9 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     include,
     exclude,
     r#type,
+    thresholds,
+    diff_base,
+    diff_threshold,
   });
 }
 
@@ -3450,6 +3887,7 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
     check: matches.get_flag("check"),
+    changed_since: matches.remove_one::<String>("changed-since"),
     files: FileFlags { include, ignore },
     use_tabs,
     line_width,
@@ -3457,6 +3895,9 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     single_quote,
     prose_wrap,
     no_semicolons,
+    unstable_css: matches.get_flag("unstable-css"),
+    unstable_html: matches.get_flag("unstable-html"),
+    unstable_yaml: matches.get_flag("unstable-yaml"),
     watch: watch_arg_parse(matches),
   });
 }
@@ -3482,6 +3923,7 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
     json,
+    extract: matches.remove_one::<PathBuf>("extract"),
   });
 }
 
@@ -3490,6 +3932,22 @@ fn install_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   let root = matches.remove_one::<String>("root");
 
+  if matches.get_flag("list") || matches.get_flag("upgrade") {
+    let action = if matches.get_flag("list") {
+      InstalledScriptsAction::List
+    } else {
+      InstalledScriptsAction::Upgrade(
+        matches
+          .remove_many::<String>("cmd")
+          .map(|names| names.collect())
+          .unwrap_or_default(),
+      )
+    };
+    flags.subcommand =
+      DenoSubcommand::InstalledScripts(InstalledScriptsFlags { action, root });
+    return;
+  }
+
   let force = matches.get_flag("force");
   let name = matches.remove_one::<String>("name");
   let mut cmd_values = matches.remove_many::<String>("cmd").unwrap();
@@ -3554,6 +4012,10 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   let json = matches.get_flag("json");
   let compact = matches.get_flag("compact");
+  let sarif = matches.get_flag("sarif");
+  let github = matches.get_flag("github");
+  let fix = matches.get_flag("fix");
+  let fix_dry_run = matches.get_flag("fix-dry-run");
   flags.subcommand = DenoSubcommand::Lint(LintFlags {
     files: FileFlags {
       include: files,
@@ -3565,6 +4027,10 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     maybe_rules_exclude,
     json,
     compact,
+    sarif,
+    github,
+    fix,
+    fix_dry_run,
     watch: watch_arg_parse(matches),
   });
 }
@@ -3612,6 +4078,7 @@ fn run_parse(
   flags.subcommand = DenoSubcommand::Run(RunFlags {
     script,
     watch: watch_arg_parse_with_paths(matches),
+    coverage_dir: matches.remove_one::<String>("coverage"),
   });
 
   Ok(())
@@ -3626,6 +4093,9 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
     task: None,
+    parallel: matches.get_flag("parallel"),
+    recursive: matches.get_flag("recursive"),
+    filter: matches.remove_one::<String>("filter"),
   };
 
   if let Some((task, mut matches)) = matches.remove_subcommand() {
@@ -4283,6 +4753,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: false,
           }),
+          coverage_dir: None,
         }),
         ..Flags::default()
       }
@@ -4306,6 +4777,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: true,
           }),
+          coverage_dir: None,
         }),
         ..Flags::default()
       }
@@ -4329,6 +4801,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: true,
           }),
+          coverage_dir: None,
         }),
         ..Flags::default()
       }
@@ -4352,6 +4825,7 @@ mod tests {
             paths: vec![PathBuf::from("foo.txt")],
             no_clear_screen: true,
           }),
+          coverage_dir: None,
         }),
         ..Flags::default()
       }
@@ -4377,6 +4851,7 @@ mod tests {
             paths: vec![PathBuf::from("file1"), PathBuf::from("file2")],
             no_clear_screen: false,
           }),
+          coverage_dir: None,
         }),
         ..Flags::default()
       }
@@ -4404,10 +4879,54 @@ mod tests {
             paths: vec![],
             no_clear_screen: true,
           }),
+          coverage_dir: None,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_coverage() {
+    let r = flags_from_vec(svec!["deno", "run", "--coverage", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          coverage_dir: Some("coverage".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--coverage=cov_profile",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: None,
+          coverage_dir: Some("cov_profile".to_string()),
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--coverage",
+      "--inspect",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -4628,6 +5147,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
             ignore: vec![],
@@ -4638,6 +5158,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -4651,6 +5174,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: true,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4661,6 +5185,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -4674,6 +5201,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4684,6 +5212,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -4697,6 +5228,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4707,6 +5239,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Some(Default::default()),
         }),
         ext: Some("ts".to_string()),
@@ -4721,6 +5256,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4731,6 +5267,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -4754,6 +5293,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: true,
+          changed_since: None,
           files: FileFlags {
             include: vec!["foo.ts".to_string()],
             ignore: vec!["bar.js".to_string()],
@@ -4764,6 +5304,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Some(Default::default()),
         }),
         ext: Some("ts".to_string()),
@@ -4777,6 +5320,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4787,6 +5331,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -4808,6 +5355,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec!["foo.ts".to_string()],
             ignore: vec![],
@@ -4818,6 +5366,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Some(Default::default()),
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -4844,6 +5395,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4854,6 +5406,9 @@ mod tests {
           single_quote: Some(true),
           prose_wrap: Some("never".to_string()),
           no_semicolons: Some(true),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -4874,6 +5429,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          changed_since: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -4884,6 +5440,9 @@ mod tests {
           single_quote: Some(false),
           prose_wrap: None,
           no_semicolons: Some(false),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -4893,41 +5452,111 @@ mod tests {
   }
 
   #[test]
-  fn lint() {
-    let r = flags_from_vec(svec!["deno", "lint", "script_1.ts", "script_2.ts"]);
+  fn fmt_changed_since() {
+    let r = flags_from_vec(svec!["deno", "fmt", "--changed-since=main"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          changed_since: Some("main".to_string()),
           files: FileFlags {
-            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string(),],
+            include: vec![],
             ignore: vec![],
           },
-          rules: false,
-          maybe_rules_tags: None,
-          maybe_rules_include: None,
-          maybe_rules_exclude: None,
-          json: false,
-          compact: false,
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
           watch: Default::default(),
         }),
+        ext: Some("ts".to_string()),
         ..Flags::default()
       }
     );
+  }
 
+  #[test]
+  fn fmt_unstable_languages() {
     let r = flags_from_vec(svec![
       "deno",
-      "lint",
-      "--watch",
-      "script_1.ts",
-      "script_2.ts"
+      "fmt",
+      "--unstable-css",
+      "--unstable-html",
+      "--unstable-yaml"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Lint(LintFlags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          changed_since: None,
           files: FileFlags {
-            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          unstable_css: true,
+          unstable_html: true,
+          unstable_yaml: true,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn lint() {
+    let r = flags_from_vec(svec!["deno", "lint", "script_1.ts", "script_2.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string(),],
+            ignore: vec![],
+          },
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--watch",
+      "script_1.ts",
+      "script_2.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
             ignore: vec![],
           },
           rules: false,
@@ -4936,6 +5565,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Some(Default::default()),
         }),
         ..Flags::default()
@@ -4964,6 +5597,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -4989,6 +5626,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5010,6 +5651,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5036,6 +5681,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5063,6 +5712,10 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5084,6 +5737,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5112,6 +5769,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -5141,6 +5802,10 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -5149,6 +5814,99 @@ mod tests {
     );
   }
 
+  #[test]
+  fn lint_fix() {
+    let r = flags_from_vec(svec!["deno", "lint", "--fix", "script_1.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string()],
+            ignore: vec![],
+          },
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: false,
+          github: false,
+          fix: true,
+          fix_dry_run: false,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--fix-dry-run"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: false,
+          github: false,
+          fix: false,
+          fix_dry_run: true,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--fix", "--fix-dry-run"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn lint_sarif_and_github() {
+    for (flag, sarif, github) in
+      [("--sarif", true, false), ("--github", false, true)]
+    {
+      let r = flags_from_vec(svec!["deno", "lint", flag]);
+      assert_eq!(
+        r.unwrap(),
+        Flags {
+          subcommand: DenoSubcommand::Lint(LintFlags {
+            files: FileFlags {
+              include: vec![],
+              ignore: vec![],
+            },
+            rules: false,
+            maybe_rules_tags: None,
+            maybe_rules_include: None,
+            maybe_rules_exclude: None,
+            json: false,
+            compact: false,
+            sarif,
+            github,
+            fix: false,
+            fix_dry_run: false,
+            watch: Default::default(),
+          }),
+          ..Flags::default()
+        }
+      );
+    }
+
+    let r = flags_from_vec(svec!["deno", "lint", "--sarif", "--json"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "lint", "--github", "--sarif"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn types() {
     let r = flags_from_vec(svec!["deno", "types"]);
@@ -5225,6 +5983,7 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          extract: None,
         }),
         ..Flags::default()
       }
@@ -5237,6 +5996,7 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          extract: None,
         }),
         reload: true,
         ..Flags::default()
@@ -5250,18 +6010,37 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
           file: Some("script.ts".to_string()),
+          extract: None,
         }),
         ..Flags::default()
       }
     );
 
+    let r =
+      flags_from_vec(svec!["deno", "info", "--extract", "sources", "my_app"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("my_app".to_string()),
+          extract: Some(PathBuf::from("sources")),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--extract", "sources"]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec!["deno", "info"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          extract: None,
         }),
         ..Flags::default()
       }
@@ -5273,7 +6052,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
-          file: None
+          file: None,
+          extract: None,
         }),
         ..Flags::default()
       }
@@ -5292,7 +6072,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          extract: None,
         }),
         config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
         no_npm: true,
@@ -5542,6 +6323,23 @@ mod tests {
     );
   }
 
+  #[test]
+  fn repl_with_check() {
+    let r = flags_from_vec(svec!["deno", "repl", "--check"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: None,
+          is_default_command: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn repl_with_eval_flag() {
     #[rustfmt::skip]
@@ -6230,6 +7028,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           file: Some("script.ts".to_string()),
+          extract: None,
           json: false,
         }),
         import_map_path: Some("import_map.json".to_owned()),
@@ -6425,6 +7224,83 @@ mod tests {
     );
   }
 
+  #[test]
+  fn install_list() {
+    let r =
+      flags_from_vec(svec!["deno", "install", "--list", "--root", "/foo"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::InstalledScripts(InstalledScriptsFlags {
+          action: InstalledScriptsAction::List,
+          root: Some("/foo".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "install", "--list", "file_server"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn install_upgrade() {
+    let r = flags_from_vec(svec!["deno", "install", "--upgrade"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::InstalledScripts(InstalledScriptsFlags {
+          action: InstalledScriptsAction::Upgrade(vec![]),
+          root: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "--upgrade",
+      "file_server",
+      "cowsay"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::InstalledScripts(InstalledScriptsFlags {
+          action: InstalledScriptsAction::Upgrade(svec![
+            "file_server",
+            "cowsay"
+          ]),
+          root: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "install", "--upgrade", "-f"]);
+    assert!(r.is_err());
+
+    // the flags the scripts were installed with are used instead
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "--upgrade",
+      "--allow-net",
+      "file_server"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "--upgrade",
+      "--check",
+      "cowsay"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn uninstall() {
     let r = flags_from_vec(svec!["deno", "uninstall", "file_server"]);
@@ -7534,6 +8410,7 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("https://example.com".to_string()),
+          extract: None,
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
@@ -7847,7 +8724,69 @@ mod tests {
           args: vec![],
           target: None,
           no_terminal: false,
-          include: vec![]
+          include: vec![],
+          include_dir: vec![],
+          compress: false,
+          obfuscate: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_with_include_dir() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--include-dir",
+      "public",
+      "--include-dir=templates",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          include_dir: svec!["public", "templates"],
+          compress: false,
+          obfuscate: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_with_compress_and_obfuscate() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--compress",
+      "--obfuscate",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          include_dir: vec![],
+          compress: true,
+          obfuscate: true,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -7869,7 +8808,10 @@ mod tests {
           args: svec!["foo", "bar", "-p", "8080"],
           target: None,
           no_terminal: true,
-          include: vec![]
+          include: vec![],
+          include_dir: vec![],
+          compress: false,
+          obfuscate: false,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -7933,10 +8875,165 @@ mod tests {
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           r#type: CoverageType::Lcov,
           output: Some(PathBuf::from("foo.lcov")),
+          thresholds: Default::default(),
+          diff_base: None,
+          diff_threshold: None,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn coverage_with_cobertura_and_json() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--cobertura",
+      "--output=cobertura.xml",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Cobertura,
+          output: Some(PathBuf::from("cobertura.xml")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--json", "foo.json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Json,
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "coverage", "--lcov", "--json", "foo.json"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "coverage", "--output=foo", "foo.json"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn coverage_with_thresholds() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--threshold-lines=80",
+      "--threshold-branches=70",
+      "--threshold-functions=60",
+      "--threshold-per-file=50",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          thresholds: CoverageThresholds {
+            lines: Some(80),
+            branches: Some(70),
+            functions: Some(60),
+            per_file: Some(50),
+          },
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--threshold-lines=101"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn coverage_with_diff() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--diff-base=main",
+      "--diff-threshold=90",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          diff_base: Some(CoverageDiffBase::GitRef("main".to_string())),
+          diff_threshold: Some(90),
+          ..CoverageFlags::default()
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--diff-file=changes.diff",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          diff_base: Some(CoverageDiffBase::File(PathBuf::from(
+            "changes.diff"
+          ))),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--diff-base=main",
+      "--diff-file=changes.diff",
+      "foo.json"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -8069,6 +9166,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["hello", "world"],
         ..Flags::default()
@@ -8082,6 +9182,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -8094,6 +9197,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -8118,6 +9224,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--", "hello", "world"],
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
@@ -8134,6 +9243,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--", "hello", "world"],
         ..Flags::default()
@@ -8151,6 +9263,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--"],
         ..Flags::default()
@@ -8167,6 +9282,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["-1", "--test"],
         ..Flags::default()
@@ -8183,6 +9301,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--test"],
         ..Flags::default()
@@ -8201,6 +9322,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -8212,6 +9336,53 @@ mod tests {
     );
   }
 
+  #[test]
+  fn task_subcommand_parallel() {
+    let r = flags_from_vec(svec!["deno", "task", "--parallel", "lint", "test"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("lint".to_string()),
+          parallel: true,
+          recursive: false,
+          filter: None,
+        }),
+        argv: svec!["test"],
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn task_subcommand_recursive() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "task",
+      "--recursive",
+      "--filter",
+      "@scope/*",
+      "build"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          parallel: false,
+          recursive: true,
+          filter: Some("@scope/*".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "task", "--filter", "a", "build"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn task_subcommand_empty() {
     let r = flags_from_vec(svec!["deno", "task"]);
@@ -8221,6 +9392,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -8236,6 +9410,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -8252,6 +9429,9 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          parallel: false,
+          recursive: false,
+          filter: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
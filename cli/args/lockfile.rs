@@ -19,7 +19,9 @@ pub fn discover(
   if flags.no_lock
     || matches!(
       flags.subcommand,
-      DenoSubcommand::Install(_) | DenoSubcommand::Uninstall(_)
+      DenoSubcommand::Install(_)
+        | DenoSubcommand::InstalledScripts(_)
+        | DenoSubcommand::Uninstall(_)
     )
   {
     return Ok(None);
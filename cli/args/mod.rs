@@ -243,10 +243,44 @@ impl BenchOptions {
   }
 }
 
+/// The languages whose formatting is unstable, which are only formatted when
+/// enabled with an `--unstable-*` flag or in the `unstable` field of the
+/// config file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UnstableFmtOptions {
+  pub css: bool,
+  pub html: bool,
+  pub yaml: bool,
+}
+
+impl UnstableFmtOptions {
+  pub fn resolve(
+    maybe_config_file: Option<&ConfigFile>,
+    maybe_fmt_flags: Option<&FmtFlags>,
+  ) -> Self {
+    let has_unstable = |name: &str| {
+      maybe_config_file
+        .map(|c| c.has_unstable(name))
+        .unwrap_or(false)
+    };
+    Self {
+      css: maybe_fmt_flags.map(|f| f.unstable_css).unwrap_or(false)
+        || has_unstable("fmt-css"),
+      html: maybe_fmt_flags.map(|f| f.unstable_html).unwrap_or(false)
+        || has_unstable("fmt-html"),
+      yaml: maybe_fmt_flags.map(|f| f.unstable_yaml).unwrap_or(false)
+        || has_unstable("fmt-yaml"),
+    }
+  }
+}
+
 #[derive(Clone, Debug)]
 pub struct FmtOptions {
   pub check: bool,
+  /// Only format the lines which changed since this git ref.
+  pub changed_since: Option<String>,
   pub options: FmtOptionsConfig,
+  pub unstable: UnstableFmtOptions,
   pub files: FilePatterns,
 }
 
@@ -254,13 +288,16 @@ impl FmtOptions {
   pub fn new_with_base(base: PathBuf) -> Self {
     Self {
       check: false,
+      changed_since: None,
       options: FmtOptionsConfig::default(),
+      unstable: UnstableFmtOptions::default(),
       files: FilePatterns::new_with_base(base),
     }
   }
 
   pub fn resolve(
     maybe_fmt_config: Option<FmtConfig>,
+    unstable: UnstableFmtOptions,
     maybe_fmt_flags: Option<FmtFlags>,
     initial_cwd: &Path,
   ) -> Result<Self, AnyError> {
@@ -269,10 +306,14 @@ impl FmtOptions {
 
     Ok(Self {
       check: maybe_fmt_flags.as_ref().map(|f| f.check).unwrap_or(false),
+      changed_since: maybe_fmt_flags
+        .as_ref()
+        .and_then(|f| f.changed_since.clone()),
       options: resolve_fmt_options(
         maybe_fmt_flags.as_ref(),
         maybe_config_options,
       ),
+      unstable,
       files: resolve_files(
         maybe_config_files,
         maybe_fmt_flags.map(|f| f.files),
@@ -374,6 +415,18 @@ pub enum LintReporterKind {
   Pretty,
   Json,
   Compact,
+  Sarif,
+  Github,
+}
+
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+pub enum LintFixMode {
+  #[default]
+  Disabled,
+  /// Write the fixed files back to disk.
+  Write,
+  /// Only print a diff of the changes the fixes would make.
+  DryRun,
 }
 
 #[derive(Clone, Debug)]
@@ -381,6 +434,9 @@ pub struct LintOptions {
   pub rules: LintRulesConfig,
   pub files: FilePatterns,
   pub reporter_kind: LintReporterKind,
+  pub fix_mode: LintFixMode,
+  /// Modules providing additional lint rules.
+  pub plugins: Vec<ModuleSpecifier>,
 }
 
 impl LintOptions {
@@ -389,6 +445,8 @@ impl LintOptions {
       rules: Default::default(),
       files: FilePatterns::new_with_base(base),
       reporter_kind: Default::default(),
+      fix_mode: Default::default(),
+      plugins: Vec::new(),
     }
   }
 
@@ -403,6 +461,10 @@ impl LintOptions {
           Some(LintReporterKind::Json)
         } else if lint_flags.compact {
           Some(LintReporterKind::Compact)
+        } else if lint_flags.sarif {
+          Some(LintReporterKind::Sarif)
+        } else if lint_flags.github {
+          Some(LintReporterKind::Github)
         } else {
           None
         }
@@ -415,6 +477,8 @@ impl LintOptions {
           Some("json") => Some(LintReporterKind::Json),
           Some("compact") => Some(LintReporterKind::Compact),
           Some("pretty") => Some(LintReporterKind::Pretty),
+          Some("sarif") => Some(LintReporterKind::Sarif),
+          Some("github") => Some(LintReporterKind::Github),
           Some(_) => {
             bail!("Invalid lint report type in config file")
           }
@@ -423,6 +487,12 @@ impl LintOptions {
       }
     }
 
+    let fix_mode = match &maybe_lint_flags {
+      Some(lint_flags) if lint_flags.fix => LintFixMode::Write,
+      Some(lint_flags) if lint_flags.fix_dry_run => LintFixMode::DryRun,
+      _ => LintFixMode::Disabled,
+    };
+
     let (
       maybe_file_flags,
       maybe_rules_tags,
@@ -454,10 +524,180 @@ impl LintOptions {
         maybe_rules_include,
         maybe_rules_exclude,
       ),
+      fix_mode,
+      plugins: Vec::new(),
     })
   }
 }
 
+/// Parses the `lint.plugins` entry of the config file into the resolved
+/// plugin modules. Plugins are handled by the CLI rather than by the config
+/// file's lint configuration, so the entry is taken out when the config file
+/// is loaded.
+pub fn take_lint_plugins(
+  config_file: &mut ConfigFile,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
+  let maybe_plugins = match config_file.json.lint.as_mut() {
+    Some(serde_json::Value::Object(lint)) => lint.remove("plugins"),
+    _ => None,
+  };
+  let plugins = match maybe_plugins {
+    Some(plugins) => {
+      let plugins: Vec<String> = serde_json::from_value(plugins)
+        .context("Failed to parse \"lint.plugins\" configuration")?;
+      plugins
+        .iter()
+        .map(|plugin| {
+          deno_core::resolve_import(plugin, config_file.specifier.as_str())
+            .with_context(|| format!("Invalid lint plugin \"{plugin}\""))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+    }
+    None => Vec::new(),
+  };
+  Ok(plugins)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+struct SerializedCoverageConfig {
+  thresholds: SerializedCoverageThresholds,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+struct SerializedCoverageThresholds {
+  lines: Option<u8>,
+  branches: Option<u8>,
+  functions: Option<u8>,
+  per_file: Option<u8>,
+}
+
+/// Parses the `test.coverage` entry of the config file into the coverage
+/// thresholds. Like `lint.plugins`, the entry is handled by the CLI rather
+/// than by the config file's test configuration, so it's taken out when the
+/// config file is loaded.
+pub fn take_coverage_thresholds(
+  config_file: &mut ConfigFile,
+) -> Result<CoverageThresholds, AnyError> {
+  let maybe_coverage = match config_file.json.test.as_mut() {
+    Some(serde_json::Value::Object(test)) => test.remove("coverage"),
+    _ => None,
+  };
+  let Some(value) = maybe_coverage else {
+    return Ok(Default::default());
+  };
+  let config: SerializedCoverageConfig = serde_json::from_value(value)
+    .context("Failed to parse \"test.coverage\" configuration")?;
+  let thresholds = config.thresholds;
+  for percent in [
+    thresholds.lines,
+    thresholds.branches,
+    thresholds.functions,
+    thresholds.per_file,
+  ]
+  .into_iter()
+  .flatten()
+  {
+    if percent > 100 {
+      bail!("Coverage thresholds must be between 0 and 100, found {percent}");
+    }
+  }
+  Ok(CoverageThresholds {
+    lines: thresholds.lines,
+    branches: thresholds.branches,
+    functions: thresholds.functions,
+    per_file: thresholds.per_file,
+  })
+}
+
+/// A task of the `tasks` configuration of the config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskDefinition {
+  /// The command, which is empty for a task that only groups the tasks it
+  /// depends on.
+  pub command: String,
+  pub depends_on: Vec<String>,
+  /// Globs of the files the task reads, relative to the config file. When
+  /// provided, the task is skipped if they didn't change since its last
+  /// successful run.
+  pub inputs: Vec<String>,
+  /// Globs of the files the task writes, relative to the config file, which
+  /// need to exist for the task to be skipped.
+  pub outputs: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SerializedTaskDefinition {
+  Command(String),
+  Object(SerializedTaskDefinitionObject),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct SerializedTaskDefinitionObject {
+  #[serde(default)]
+  command: String,
+  #[serde(default)]
+  depends_on: Vec<String>,
+  #[serde(default)]
+  inputs: Vec<String>,
+  #[serde(default)]
+  outputs: Vec<String>,
+}
+
+impl From<SerializedTaskDefinition> for TaskDefinition {
+  fn from(value: SerializedTaskDefinition) -> Self {
+    match value {
+      SerializedTaskDefinition::Command(command) => TaskDefinition {
+        command,
+        ..Default::default()
+      },
+      SerializedTaskDefinition::Object(object) => TaskDefinition {
+        command: object.command,
+        depends_on: object.depends_on,
+        inputs: object.inputs,
+        outputs: object.outputs,
+      },
+    }
+  }
+}
+
+/// Resolves the `tasks` configuration of the config file. `deno_config` only
+/// supports tasks that are a command string, so tasks written as an object
+/// are parsed here and only their commands are given to `deno_config` to
+/// validate the task names.
+pub fn resolve_tasks_config(
+  config_file: &ConfigFile,
+) -> Result<IndexMap<String, TaskDefinition>, AnyError> {
+  let mut config_file = config_file.clone();
+  let Some(tasks) = config_file.json.tasks.take() else {
+    return Ok(Default::default());
+  };
+  let definitions: IndexMap<String, SerializedTaskDefinition> =
+    serde_json::from_value(tasks)
+      .context("Failed to parse \"tasks\" configuration")?;
+  let definitions = definitions
+    .into_iter()
+    .map(|(name, definition)| (name, TaskDefinition::from(definition)))
+    .collect::<IndexMap<_, _>>();
+  let commands = definitions
+    .iter()
+    .map(|(name, definition)| (name.clone(), definition.command.clone()))
+    .collect::<IndexMap<_, _>>();
+  config_file.json.tasks = Some(serde_json::to_value(commands)?);
+  config_file.resolve_tasks_config()?;
+  for (name, definition) in &definitions {
+    for dependency in &definition.depends_on {
+      if !definitions.contains_key(dependency) {
+        bail!("Task '{name}' depends on unknown task '{dependency}'");
+      }
+    }
+  }
+  Ok(definitions)
+}
+
 fn resolve_lint_rules_options(
   maybe_lint_rules_config: Option<LintRulesConfig>,
   mut maybe_rules_tags: Option<Vec<String>>,
@@ -682,6 +922,8 @@ pub struct CliOptions {
   maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
   overrides: CliOptionOverrides,
   maybe_workspace_config: Option<WorkspaceConfig>,
+  lint_plugins: Vec<ModuleSpecifier>,
+  coverage_thresholds: CoverageThresholds,
   pub disable_deprecated_api_warning: bool,
 }
 
@@ -707,6 +949,14 @@ impl CliOptions {
       eprintln!("{}", colors::yellow(msg));
     }
 
+    let mut maybe_config_file = maybe_config_file;
+    let (lint_plugins, coverage_thresholds) = match maybe_config_file.as_mut() {
+      Some(config_file) => (
+        take_lint_plugins(config_file)?,
+        take_coverage_thresholds(config_file)?,
+      ),
+      None => (Vec::new(), CoverageThresholds::default()),
+    };
     let maybe_node_modules_folder = resolve_node_modules_folder(
       &initial_cwd,
       &flags,
@@ -747,6 +997,8 @@ impl CliOptions {
       maybe_vendor_folder,
       overrides: Default::default(),
       maybe_workspace_config,
+      lint_plugins,
+      coverage_thresholds,
       disable_deprecated_api_warning,
     })
   }
@@ -1070,6 +1322,25 @@ impl CliOptions {
     self.maybe_vendor_folder.as_ref()
   }
 
+  /// The directory of the npm packages vendored by `deno vendor`, which
+  /// is used as a read-only npm cache when its import map is in use.
+  pub fn vendored_npm_cache_dir(&self) -> Option<PathBuf> {
+    let import_map_path = self
+      .resolve_import_map_specifier()
+      .ok()
+      .flatten()
+      .and_then(|specifier| specifier.to_file_path().ok())?;
+    if import_map_path.file_name()? != "import_map.json" {
+      return None;
+    }
+    let vendor_dir = import_map_path.parent()?;
+    let npm_dir = vendor_dir.join("npm");
+    // written by `deno vendor`
+    let manifest_path =
+      vendor_dir.join(crate::tools::vendor::MANIFEST_FILE_NAME);
+    (manifest_path.exists() && npm_dir.is_dir()).then_some(npm_dir)
+  }
+
   pub fn resolve_root_cert_store_provider(
     &self,
   ) -> Arc<dyn RootCertStoreProvider> {
@@ -1106,9 +1377,9 @@ impl CliOptions {
 
   pub fn resolve_tasks_config(
     &self,
-  ) -> Result<IndexMap<String, String>, AnyError> {
+  ) -> Result<IndexMap<String, TaskDefinition>, AnyError> {
     if let Some(config_file) = &self.maybe_config_file {
-      config_file.resolve_tasks_config()
+      resolve_tasks_config(config_file)
     } else if self.maybe_package_json.is_some() {
       Ok(Default::default())
     } else {
@@ -1182,7 +1453,16 @@ impl CliOptions {
     } else {
       None
     };
-    FmtOptions::resolve(maybe_fmt_config, Some(fmt_flags), &self.initial_cwd)
+    let unstable = UnstableFmtOptions::resolve(
+      self.maybe_config_file.as_ref(),
+      Some(&fmt_flags),
+    );
+    FmtOptions::resolve(
+      maybe_fmt_config,
+      unstable,
+      Some(fmt_flags),
+      &self.initial_cwd,
+    )
   }
 
   pub fn resolve_lint_options(
@@ -1194,7 +1474,28 @@ impl CliOptions {
     } else {
       None
     };
-    LintOptions::resolve(maybe_lint_config, Some(lint_flags), &self.initial_cwd)
+    let mut lint_options = LintOptions::resolve(
+      maybe_lint_config,
+      Some(lint_flags),
+      &self.initial_cwd,
+    )?;
+    lint_options.plugins = self.lint_plugins.clone();
+    Ok(lint_options)
+  }
+
+  /// Resolves the coverage thresholds, where the thresholds provided by
+  /// flags take precedence over the config file.
+  pub fn resolve_coverage_thresholds(
+    &self,
+    flag_thresholds: CoverageThresholds,
+  ) -> Result<CoverageThresholds, AnyError> {
+    let config_thresholds = &self.coverage_thresholds;
+    Ok(CoverageThresholds {
+      lines: flag_thresholds.lines.or(config_thresholds.lines),
+      branches: flag_thresholds.branches.or(config_thresholds.branches),
+      functions: flag_thresholds.functions.or(config_thresholds.functions),
+      per_file: flag_thresholds.per_file.or(config_thresholds.per_file),
+    })
   }
 
   pub fn resolve_config_excludes(&self) -> Result<PathOrPatternSet, AnyError> {
@@ -1264,6 +1565,13 @@ impl CliOptions {
         .as_ref()
         .map(ToOwned::to_owned)
         .or_else(|| env::var("DENO_UNSTABLE_COVERAGE_DIR").ok()),
+      // subprocesses spawned by a program run with coverage inherit the
+      // coverage directory through the environment
+      DenoSubcommand::Run(run) => run
+        .coverage_dir
+        .as_ref()
+        .map(ToOwned::to_owned)
+        .or_else(|| env::var("DENO_UNSTABLE_COVERAGE_DIR").ok()),
       _ => None,
     }
   }
@@ -1949,6 +2257,79 @@ mod test {
     );
   }
 
+  #[test]
+  fn resolve_tasks_config_with_dependencies() {
+    let config_text = r#"{
+      "tasks": {
+        "codegen": "deno run -A codegen.ts",
+        "build": {
+          "command": "deno run -A build.ts",
+          "dependsOn": ["codegen"]
+        },
+        "all": { "dependsOn": ["build"] },
+        "assets": {
+          "command": "deno run -A assets.ts",
+          "inputs": ["assets/**/*.svg"],
+          "outputs": ["dist/assets"]
+        }
+      }
+    }"#;
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+    let tasks = resolve_tasks_config(&config_file).unwrap();
+    assert_eq!(
+      tasks.into_iter().collect::<Vec<_>>(),
+      vec![
+        (
+          "codegen".to_string(),
+          TaskDefinition {
+            command: "deno run -A codegen.ts".to_string(),
+            ..Default::default()
+          }
+        ),
+        (
+          "build".to_string(),
+          TaskDefinition {
+            command: "deno run -A build.ts".to_string(),
+            depends_on: vec!["codegen".to_string()],
+            ..Default::default()
+          }
+        ),
+        (
+          "all".to_string(),
+          TaskDefinition {
+            command: "".to_string(),
+            depends_on: vec!["build".to_string()],
+            ..Default::default()
+          }
+        ),
+        (
+          "assets".to_string(),
+          TaskDefinition {
+            command: "deno run -A assets.ts".to_string(),
+            depends_on: vec![],
+            inputs: vec!["assets/**/*.svg".to_string()],
+            outputs: vec!["dist/assets".to_string()],
+          }
+        ),
+      ]
+    );
+
+    let config_text = r#"{
+      "tasks": {
+        "build": { "command": "deno run build.ts", "dependsOn": ["gen"] }
+      }
+    }"#;
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+    assert_eq!(
+      resolve_tasks_config(&config_file).unwrap_err().to_string(),
+      "Task 'build' depends on unknown task 'gen'"
+    );
+  }
+
   #[test]
   fn deno_registry_urls() {
     let reg_url = deno_registry_url();
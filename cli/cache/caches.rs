@@ -12,6 +12,7 @@ use super::deno_dir::DenoDirProvider;
 use super::incremental::INCREMENTAL_CACHE_DB;
 use super::module_info::MODULE_INFO_CACHE_DB;
 use super::node::NODE_ANALYSIS_CACHE_DB;
+use super::task::TASK_CACHE_DB;
 
 pub struct Caches {
   dir_provider: Arc<DenoDirProvider>,
@@ -20,6 +21,7 @@ pub struct Caches {
   dep_analysis_db: OnceCell<CacheDB>,
   node_analysis_db: OnceCell<CacheDB>,
   type_checking_cache_db: OnceCell<CacheDB>,
+  task_cache_db: OnceCell<CacheDB>,
 }
 
 impl Caches {
@@ -31,6 +33,7 @@ impl Caches {
       dep_analysis_db: Default::default(),
       node_analysis_db: Default::default(),
       type_checking_cache_db: Default::default(),
+      task_cache_db: Default::default(),
     }
   }
 
@@ -109,4 +112,16 @@ impl Caches {
         .map(|dir| dir.type_checking_cache_db_file_path()),
     )
   }
+
+  pub fn task_cache_db(&self) -> CacheDB {
+    Self::make_db(
+      &self.task_cache_db,
+      &TASK_CACHE_DB,
+      self
+        .dir_provider
+        .get_or_create()
+        .ok()
+        .map(|dir| dir.task_cache_db_file_path()),
+    )
+  }
 }
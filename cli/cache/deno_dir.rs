@@ -110,6 +110,12 @@ impl DenoDir {
     self.root.join("check_cache_v1")
   }
 
+  /// Path for the cache used to skip running tasks which are up to date.
+  pub fn task_cache_db_file_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("task_cache_v1")
+  }
+
   /// Path to the registries cache, used for the lps.
   pub fn registries_folder_path(&self) -> PathBuf {
     self.root.join("registries")
@@ -150,6 +156,11 @@ impl DenoDir {
     }
   }
 
+  /// Path used for the history of the code executed by the Jupyter kernel.
+  pub fn jupyter_history_file_path(&self) -> PathBuf {
+    self.root.join("jupyter_history.jsonl")
+  }
+
   /// Folder path used for downloading new versions of deno.
   pub fn dl_folder_path(&self) -> PathBuf {
     self.root.join("dl")
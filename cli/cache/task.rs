@@ -0,0 +1,110 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::cache_db::CacheDB;
+use super::cache_db::CacheDBConfiguration;
+use super::cache_db::CacheFailure;
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage::rusqlite::params;
+
+pub static TASK_CACHE_DB: CacheDBConfiguration = CacheDBConfiguration {
+  table_initializer: "CREATE TABLE IF NOT EXISTS taskcache (
+      task_key TEXT PRIMARY KEY,
+      inputs_hash TEXT NOT NULL
+    );",
+  on_version_change: "DELETE FROM taskcache;",
+  preheat_queries: &[],
+  // If the cache fails, just ignore all caching attempts
+  on_failure: CacheFailure::Blackhole,
+};
+
+/// The cache used to skip running a task again when its inputs didn't change.
+///
+/// This stores the hash of the inputs of the last successful run of each
+/// task, keyed by the config file and the task name.
+pub struct TaskCache(CacheDB);
+
+impl TaskCache {
+  pub fn new(db: CacheDB) -> Self {
+    Self(db)
+  }
+
+  pub fn get_inputs_hash(&self, task_key: &str) -> Option<u64> {
+    match self.get_inputs_hash_result(task_key) {
+      Ok(val) => val,
+      Err(err) => {
+        if cfg!(debug_assertions) {
+          panic!("Error retrieving task hash: {err}");
+        } else {
+          log::debug!("Error retrieving task hash: {}", err);
+          // fail silently when not debugging
+          None
+        }
+      }
+    }
+  }
+
+  fn get_inputs_hash_result(
+    &self,
+    task_key: &str,
+  ) -> Result<Option<u64>, AnyError> {
+    let maybe_hash = self.0.query_row(
+      "SELECT inputs_hash FROM taskcache WHERE task_key=?1 LIMIT 1",
+      params![task_key],
+      |row| Ok(row.get::<_, String>(0)?),
+    )?;
+    Ok(maybe_hash.and_then(|hash| hash.parse().ok()))
+  }
+
+  pub fn set_inputs_hash(&self, task_key: &str, inputs_hash: u64) {
+    if let Err(err) = self.set_inputs_hash_result(task_key, inputs_hash) {
+      // should never error here, but if it ever does don't fail
+      if cfg!(debug_assertions) {
+        panic!("Error saving task hash: {err}");
+      } else {
+        log::debug!("Error saving task hash: {}", err);
+      }
+    }
+  }
+
+  fn set_inputs_hash_result(
+    &self,
+    task_key: &str,
+    inputs_hash: u64,
+  ) -> Result<(), AnyError> {
+    self.0.execute(
+      "INSERT OR REPLACE INTO taskcache (task_key, inputs_hash) VALUES (?1, ?2)",
+      params![task_key, inputs_hash.to_string()],
+    )?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  pub fn task_cache_general_use() {
+    let conn = CacheDB::in_memory(&TASK_CACHE_DB, "1.0.0");
+    let cache = TaskCache::new(conn);
+
+    assert_eq!(cache.get_inputs_hash("file:///deno.json#build"), None);
+    cache.set_inputs_hash("file:///deno.json#build", 1);
+    assert_eq!(cache.get_inputs_hash("file:///deno.json#build"), Some(1));
+    assert_eq!(cache.get_inputs_hash("file:///deno.json#lint"), None);
+
+    // replacing an existing hash
+    cache.set_inputs_hash("file:///deno.json#build", 2);
+    assert_eq!(cache.get_inputs_hash("file:///deno.json#build"), Some(2));
+
+    // recreating with the same cli version should keep the data
+    let conn = cache.0.recreate_with_version("1.0.0");
+    let cache = TaskCache::new(conn);
+    assert_eq!(cache.get_inputs_hash("file:///deno.json#build"), Some(2));
+
+    // changing the cli version should clear it
+    let conn = cache.0.recreate_with_version("2.0.0");
+    let cache = TaskCache::new(conn);
+    assert_eq!(cache.get_inputs_hash("file:///deno.json#build"), None);
+  }
+}
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::CacheSetting;
 use crate::args::CliOptions;
 use crate::args::DenoSubcommand;
 use crate::args::Flags;
@@ -303,6 +304,7 @@ impl CliFactory {
       .npm_resolver
       .get_or_try_init_async(async {
         let fs = self.fs();
+        let vendored_npm_cache_dir = self.options.vendored_npm_cache_dir();
         create_cli_npm_resolver(if self.options.unstable_byonm() {
           CliNpmResolverCreateOptions::Byonm(CliNpmResolverByonmCreateOptions {
             fs: fs.clone(),
@@ -332,8 +334,16 @@ impl CliFactory {
             maybe_lockfile: self.maybe_lockfile().as_ref().cloned(),
             fs: fs.clone(),
             http_client: self.http_client().clone(),
-            npm_global_cache_dir: self.deno_dir()?.npm_folder_path(),
-            cache_setting: self.options.cache_setting(),
+            // the vendored packages are checked in, so never download
+            // into them and only use what was vendored
+            npm_global_cache_dir: match &vendored_npm_cache_dir {
+              Some(npm_cache_dir) => npm_cache_dir.clone(),
+              None => self.deno_dir()?.npm_folder_path(),
+            },
+            cache_setting: match vendored_npm_cache_dir {
+              Some(_) => CacheSetting::Only,
+              None => self.options.cache_setting(),
+            },
             text_only_progress_bar: self.text_only_progress_bar().clone(),
             maybe_node_modules_path: self.options.node_modules_dir_path(),
             package_json_installer:
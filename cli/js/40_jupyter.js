@@ -334,7 +334,164 @@ async function formatInner(obj, raw) {
   }
 }
 
-internals.jupyter = { formatInner };
+const kDispatchMessage = Symbol("kDispatchMessage");
+const kDispatchClose = Symbol("kDispatchClose");
+
+/**
+ * A channel for custom messages between the kernel and a Jupyter frontend,
+ * as described by the Jupyter messaging protocol. Interactive widgets are
+ * built on top of comms.
+ */
+class Comm {
+  #broadcast;
+  #onClosed;
+  #closed = false;
+  #messageListeners = [];
+  #closeListeners = [];
+
+  constructor(broadcast, id, targetName, onClosed) {
+    this.#broadcast = broadcast;
+    this.#onClosed = onClosed;
+    this.id = id;
+    this.targetName = targetName;
+  }
+
+  get closed() {
+    return this.#closed;
+  }
+
+  async send(data = {}, { metadata = {}, buffers = [] } = {}) {
+    if (this.#closed) {
+      throw new Error(`Comm '${this.id}' is closed.`);
+    }
+    await this.#broadcast("comm_msg", { comm_id: this.id, data }, {
+      metadata,
+      buffers,
+    });
+  }
+
+  async close(data = {}, { metadata = {}, buffers = [] } = {}) {
+    if (this.#closed) {
+      return;
+    }
+    this.#setClosed();
+    await this.#broadcast("comm_close", { comm_id: this.id, data }, {
+      metadata,
+      buffers,
+    });
+  }
+
+  onMessage(listener) {
+    this.#messageListeners.push(listener);
+  }
+
+  onClose(listener) {
+    this.#closeListeners.push(listener);
+  }
+
+  #setClosed() {
+    this.#closed = true;
+    this.#onClosed(this.id);
+  }
+
+  async [kDispatchMessage](message) {
+    for (const listener of this.#messageListeners) {
+      await listener(message);
+    }
+  }
+
+  async [kDispatchClose](message) {
+    if (this.#closed) {
+      return;
+    }
+    this.#setClosed();
+    for (const listener of this.#closeListeners) {
+      await listener(message);
+    }
+  }
+}
+
+/**
+ * Keeps track of the open comms and the targets which the frontend can open
+ * comms to. The kernel calls the `handle*` methods with the comm messages
+ * it receives from the frontend.
+ */
+class CommManager {
+  #broadcast;
+  #comms = new Map();
+  #targets = new Map();
+
+  constructor(broadcast) {
+    this.#broadcast = broadcast;
+  }
+
+  async open(targetName, data = {}, { metadata = {}, buffers = [] } = {}) {
+    const comm = this.#createComm(crypto.randomUUID(), targetName);
+    await this.#broadcast("comm_open", {
+      comm_id: comm.id,
+      target_name: targetName,
+      data,
+    }, { metadata, buffers });
+    return comm;
+  }
+
+  registerTarget(targetName, callback) {
+    this.#targets.set(targetName, callback);
+  }
+
+  /** Returns whether there's a target for the comm opened by the frontend. */
+  async handleOpen(commId, targetName, data, buffers) {
+    const callback = this.#targets.get(targetName);
+    if (callback === undefined) {
+      return false;
+    }
+    const comm = this.#createComm(commId, targetName);
+    await callback(comm, { data, buffers: toUint8Arrays(buffers) });
+    return true;
+  }
+
+  async handleMessage(commId, data, buffers) {
+    await this.#comms.get(commId)?.[kDispatchMessage]({
+      data,
+      buffers: toUint8Arrays(buffers),
+    });
+  }
+
+  async handleClose(commId, data, buffers) {
+    await this.#comms.get(commId)?.[kDispatchClose]({
+      data,
+      buffers: toUint8Arrays(buffers),
+    });
+  }
+
+  /** Gets the open comms, optionally only those of a target. */
+  info(targetName) {
+    const comms = {};
+    for (const comm of this.#comms.values()) {
+      if (targetName == null || comm.targetName === targetName) {
+        comms[comm.id] = { target_name: comm.targetName };
+      }
+    }
+    return comms;
+  }
+
+  #createComm(commId, targetName) {
+    const comm = new Comm(
+      this.#broadcast,
+      commId,
+      targetName,
+      (id) => this.#comms.delete(id),
+    );
+    this.#comms.set(commId, comm);
+    return comm;
+  }
+}
+
+function toUint8Arrays(buffers = []) {
+  return buffers.map((buffer) => new Uint8Array(buffer));
+}
+
+internals.jupyter = { formatInner, CommManager };
 
 function enableJupyter() {
   const {
@@ -388,6 +545,9 @@ function enableJupyter() {
 
   internals.jupyter.broadcastResult = broadcastResult;
 
+  const comms = new CommManager(broadcast);
+  internals.jupyter.comms = comms;
+
   /**
    * Display function for Jupyter Deno Kernel.
    * Mimics the behavior of IPython's `display(obj, raw=True)` function to allow
@@ -414,6 +574,14 @@ function enableJupyter() {
     return;
   }
 
+  function openComm(targetName, data, options) {
+    return comms.open(targetName, data, options);
+  }
+
+  function registerCommTarget(targetName, callback) {
+    comms.registerTarget(targetName, callback);
+  }
+
   globalThis.Deno.jupyter = {
     broadcast,
     display,
@@ -422,6 +590,8 @@ function enableJupyter() {
     html,
     svg,
     $display,
+    openComm,
+    registerCommTarget,
   };
 }
 
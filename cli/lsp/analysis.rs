@@ -8,6 +8,9 @@ use super::tsc;
 
 use crate::npm::CliNpmResolver;
 use crate::tools::lint::create_linter;
+use crate::tools::lint::get_lint_fixes;
+use crate::tools::lint::LintFix;
+use crate::tools::lint::LintPluginRunner;
 use crate::util::path::specifier_to_file_path;
 
 use deno_ast::SourceRange;
@@ -17,6 +20,7 @@ use deno_core::anyhow::anyhow;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
 use deno_core::serde::Deserialize;
+use deno_core::serde::Serialize;
 use deno_core::serde_json;
 use deno_core::serde_json::json;
 use deno_core::ModuleSpecifier;
@@ -79,9 +83,31 @@ pub enum Category {
     message: String,
     code: String,
     hint: Option<String>,
+    quick_fixes: Vec<DataQuickFix>,
   },
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataQuickFixChange {
+  pub range: Range,
+  pub new_text: String,
+}
+
+/// A quick fix that's stored in the diagnostic's data field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataQuickFix {
+  pub description: String,
+  pub changes: Vec<DataQuickFixChange>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticDataLintQuickFixes {
+  quick_fixes: Vec<DataQuickFix>,
+}
+
 /// A structure to hold a reference to a diagnostic message.
 #[derive(Debug, PartialEq, Eq)]
 pub struct Reference {
@@ -96,6 +122,7 @@ impl Reference {
         message,
         code,
         hint,
+        quick_fixes,
       } => lsp::Diagnostic {
         range: self.range,
         severity: Some(lsp::DiagnosticSeverity::WARNING),
@@ -112,7 +139,11 @@ impl Reference {
         },
         related_information: None,
         tags: None, // we should tag unused code
-        data: None,
+        data: if quick_fixes.is_empty() {
+          None
+        } else {
+          Some(json!({ "quickFixes": quick_fixes }))
+        },
       },
     }
   }
@@ -131,21 +162,62 @@ fn as_lsp_range(range: &deno_lint::diagnostic::Range) -> Range {
   }
 }
 
+fn as_data_quick_fix(fix: LintFix, text_info: &SourceTextInfo) -> DataQuickFix {
+  let as_lsp_position = |byte_index: usize| {
+    let loc =
+      text_info.line_and_column_index(text_info.range().start + byte_index);
+    Position {
+      line: loc.line_index as u32,
+      character: loc.column_index as u32,
+    }
+  };
+  DataQuickFix {
+    description: fix.description,
+    changes: fix
+      .edits
+      .into_iter()
+      .map(|edit| DataQuickFixChange {
+        range: Range {
+          start: as_lsp_position(edit.start),
+          end: as_lsp_position(edit.end),
+        },
+        new_text: edit.new_text,
+      })
+      .collect(),
+  }
+}
+
 pub fn get_lint_references(
   parsed_source: &deno_ast::ParsedSource,
   lint_rules: Vec<&'static dyn LintRule>,
+  maybe_plugin_runner: Option<&LintPluginRunner>,
 ) -> Result<Vec<Reference>, AnyError> {
   let linter = create_linter(lint_rules);
-  let lint_diagnostics = linter.lint_with_ast(parsed_source);
+  let mut lint_diagnostics = linter.lint_with_ast(parsed_source);
+  if let Some(plugin_runner) = maybe_plugin_runner {
+    if let Err(err) = plugin_runner.lint(parsed_source, &mut lint_diagnostics) {
+      lsp_warn!(
+        "Unable to run lint plugins for \"{}\": {:#}",
+        parsed_source.specifier(),
+        err
+      );
+    }
+  }
+  let lint_fixes = get_lint_fixes(parsed_source, &lint_diagnostics);
 
   Ok(
     lint_diagnostics
       .into_iter()
-      .map(|d| Reference {
+      .zip(lint_fixes)
+      .map(|(d, maybe_fix)| Reference {
         category: Category::Lint {
           message: d.message,
           code: d.code,
           hint: d.hint,
+          quick_fixes: maybe_fix
+            .map(|fix| as_data_quick_fix(fix, parsed_source.text_info()))
+            .into_iter()
+            .collect(),
         },
         range: as_lsp_range(&d.range),
       })
@@ -603,6 +675,48 @@ impl CodeActionCollection {
     Ok(())
   }
 
+  /// Add the quick fixes stored in the data of a lint diagnostic.
+  pub fn add_deno_lint_fix_actions(
+    &mut self,
+    specifier: &ModuleSpecifier,
+    diagnostic: &lsp::Diagnostic,
+  ) -> Result<(), AnyError> {
+    let Some(data) = diagnostic.data.clone() else {
+      return Ok(());
+    };
+    let data: DiagnosticDataLintQuickFixes = serde_json::from_value(data)?;
+    for quick_fix in data.quick_fixes {
+      let mut changes = HashMap::new();
+      changes.insert(
+        specifier.clone(),
+        quick_fix
+          .changes
+          .into_iter()
+          .map(|change| lsp::TextEdit {
+            new_text: change.new_text,
+            range: change.range,
+          })
+          .collect(),
+      );
+      let fix_action = lsp::CodeAction {
+        title: quick_fix.description,
+        kind: Some(lsp::CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        command: None,
+        is_preferred: Some(true),
+        disabled: None,
+        data: None,
+        edit: Some(lsp::WorkspaceEdit {
+          changes: Some(changes),
+          change_annotations: None,
+          document_changes: None,
+        }),
+      };
+      self.actions.push(CodeActionKind::DenoLint(fix_action));
+    }
+    Ok(())
+  }
+
   pub fn add_deno_lint_ignore_action(
     &mut self,
     specifier: &ModuleSpecifier,
@@ -1022,6 +1136,7 @@ mod tests {
             message: "message1".to_string(),
             code: "code1".to_string(),
             hint: None,
+            quick_fixes: vec![],
           },
           range,
         },
@@ -1040,6 +1155,7 @@ mod tests {
             message: "message2".to_string(),
             code: "code2".to_string(),
             hint: Some("hint2".to_string()),
+            quick_fixes: vec![],
           },
           range,
         },
@@ -37,9 +37,25 @@ fn code_action_capabilities(
     .unwrap_or(CodeActionProviderCapability::Simple(true))
 }
 
+/// Diagnostics are only provided with `textDocument/diagnostic` requests to
+/// clients which pull them, otherwise they are published.
+fn diagnostic_capabilities(
+  pull_diagnostics: bool,
+) -> Option<DiagnosticServerCapabilities> {
+  pull_diagnostics.then(|| {
+    DiagnosticServerCapabilities::Options(DiagnosticOptions {
+      identifier: Some("deno".to_string()),
+      inter_file_dependencies: true,
+      workspace_diagnostics: true,
+      work_done_progress_options: Default::default(),
+    })
+  })
+}
+
 pub fn server_capabilities(
   client_capabilities: &ClientCapabilities,
   enable_builtin_commands: bool,
+  pull_diagnostics: bool,
 ) -> ServerCapabilities {
   let code_action_provider = code_action_capabilities(client_capabilities);
   ServerCapabilities {
@@ -110,14 +126,22 @@ pub fn server_capabilities(
       resolve_provider: Some(true),
     }),
     document_formatting_provider: Some(OneOf::Left(true)),
-    document_range_formatting_provider: None,
-    document_on_type_formatting_provider: None,
+    document_range_formatting_provider: Some(OneOf::Left(true)),
+    document_on_type_formatting_provider: Some(
+      DocumentOnTypeFormattingOptions {
+        first_trigger_character: ";".to_string(),
+        more_trigger_character: Some(vec!["}".to_string()]),
+      },
+    ),
     selection_range_provider: Some(SelectionRangeProviderCapability::Simple(
       true,
     )),
     folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
     rename_provider: Some(OneOf::Left(true)),
-    document_link_provider: None,
+    document_link_provider: Some(DocumentLinkOptions {
+      resolve_provider: Some(false),
+      work_done_progress_options: Default::default(),
+    }),
     color_provider: None,
     execute_command_provider: Some(ExecuteCommandOptions {
       commands: if enable_builtin_commands {
@@ -156,8 +180,7 @@ pub fn server_capabilities(
     })),
     inlay_hint_provider: Some(OneOf::Left(true)),
     position_encoding: None,
-    // TODO(nayeemrmn): Support pull-based diagnostics.
-    diagnostic_provider: None,
+    diagnostic_provider: diagnostic_capabilities(pull_diagnostics),
     inline_value_provider: None,
   }
 }
@@ -153,6 +153,11 @@ impl OutsideLockClient {
       .publish_diagnostics(uri.into_url(), diags, version)
       .await;
   }
+
+  /// Asks the client to pull diagnostics again.
+  pub async fn workspace_diagnostic_refresh(&self) -> Result<(), AnyError> {
+    self.0.workspace_diagnostic_refresh().await
+  }
 }
 
 #[async_trait]
@@ -189,6 +194,7 @@ trait ClientTrait: Send + Sync {
     &self,
     registrations: Vec<lsp::Registration>,
   ) -> Result<(), AnyError>;
+  async fn workspace_diagnostic_refresh(&self) -> Result<(), AnyError>;
 }
 
 #[derive(Clone)]
@@ -340,6 +346,14 @@ impl ClientTrait for TowerClient {
       .await
       .map_err(|err| anyhow!("{}", err))
   }
+
+  async fn workspace_diagnostic_refresh(&self) -> Result<(), AnyError> {
+    self
+      .0
+      .send_request::<lsp::request::WorkspaceDiagnosticRefresh>(())
+      .await
+      .map_err(|err| anyhow!("{}", err))
+  }
 }
 
 #[derive(Clone)]
@@ -401,4 +415,8 @@ impl ClientTrait for ReplClient {
   ) -> Result<(), AnyError> {
     Ok(())
   }
+
+  async fn workspace_diagnostic_refresh(&self) -> Result<(), AnyError> {
+    Ok(())
+  }
 }
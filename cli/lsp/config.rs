@@ -30,6 +30,10 @@ pub const SETTINGS_SECTION: &str = "deno";
 pub struct ClientCapabilities {
   pub code_action_disabled_support: bool,
   pub line_folding_only: bool,
+  /// The client requests diagnostics with `textDocument/diagnostic` instead
+  /// of them being published to it. Only used when the client also supports
+  /// `workspace/diagnostic/refresh`, so it can be told when they change.
+  pub pull_diagnostics: bool,
   pub snippet_support: bool,
   pub status_notification: bool,
   /// The client provides the `experimental.testingApi` capability, which is
@@ -1151,6 +1155,14 @@ impl Config {
         .as_ref()
         .and_then(|it| it.line_folding_only)
         .unwrap_or(false);
+      self.client_capabilities.pull_diagnostics =
+        text_document.diagnostic.is_some()
+          && capabilities
+            .workspace
+            .as_ref()
+            .and_then(|it| it.diagnostic.as_ref())
+            .and_then(|it| it.refresh_support)
+            == Some(true);
       self.client_capabilities.code_action_disabled_support = text_document
         .code_action
         .as_ref()
@@ -22,6 +22,7 @@ use crate::lsp::lsp_custom::DiagnosticBatchNotificationParams;
 use crate::resolver::SloppyImportsResolution;
 use crate::resolver::SloppyImportsResolver;
 use crate::tools::lint::get_configured_rules;
+use crate::tools::lint::LintPluginRunner;
 
 use deno_ast::MediaType;
 use deno_core::anyhow::anyhow;
@@ -47,7 +48,10 @@ use deno_semver::package::PackageReq;
 use log::error;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
 use tokio::sync::mpsc;
@@ -61,6 +65,7 @@ pub struct DiagnosticServerUpdateMessage {
   pub snapshot: Arc<StateSnapshot>,
   pub config: Arc<ConfigSnapshot>,
   pub lint_options: LintOptions,
+  pub maybe_lint_plugin_runner: Option<Arc<LintPluginRunner>>,
   pub url_map: LspUrlMap,
 }
 
@@ -124,6 +129,8 @@ impl DiagnosticsPublisher {
       self.diagnostics_by_specifier.lock().await;
     let mut seen_specifiers = HashSet::with_capacity(diagnostics.len());
     let mut messages_sent = 0;
+    let pull_mode = self.state.is_pull_mode();
+    let mut has_changes = false;
 
     for record in diagnostics {
       if token.is_cancelled() {
@@ -151,6 +158,10 @@ impl DiagnosticsPublisher {
       self
         .state
         .update(&record.specifier, version, &all_specifier_diagnostics);
+      has_changes = true;
+      if pull_mode {
+        continue;
+      }
       self
         .client
         .when_outside_lsp_lock()
@@ -182,6 +193,10 @@ impl DiagnosticsPublisher {
         if let Some(removed_value) = maybe_removed_value {
           // clear out any diagnostics for this specifier
           self.state.update(specifier, removed_value.version, &[]);
+          has_changes = true;
+          if pull_mode {
+            continue;
+          }
           self
             .client
             .when_outside_lsp_lock()
@@ -203,6 +218,16 @@ impl DiagnosticsPublisher {
       diagnostics_by_specifier.remove(&specifier);
     }
 
+    if has_changes && pull_mode {
+      // don't hold up publishing the next diagnostics waiting on the client
+      let client = self.client.when_outside_lsp_lock();
+      spawn(async move {
+        if let Err(err) = client.workspace_diagnostic_refresh().await {
+          error!("Failed to refresh diagnostics: {:#}", err);
+        }
+      });
+    }
+
     messages_sent
   }
 
@@ -304,11 +329,30 @@ struct ChannelUpdateMessage {
 struct SpecifierState {
   version: Option<i32>,
   no_cache_diagnostics: Vec<lsp::Diagnostic>,
+  /// All the diagnostics for the specifier, which are only kept when the
+  /// client pulls diagnostics.
+  diagnostics: Vec<lsp::Diagnostic>,
+  result_id: u64,
+}
+
+/// The latest diagnostics for a specifier, as provided to clients which pull
+/// diagnostics.
+#[derive(Debug)]
+pub struct PulledDiagnostics {
+  pub specifier: ModuleSpecifier,
+  pub version: Option<i32>,
+  pub result_id: String,
+  pub diagnostics: Vec<lsp::Diagnostic>,
 }
 
 #[derive(Debug, Default)]
 pub struct DiagnosticsState {
   specifiers: RwLock<HashMap<ModuleSpecifier, SpecifierState>>,
+  /// The client requests diagnostics with `textDocument/diagnostic` and
+  /// `workspace/diagnostic`, so they shouldn't be published to it. It's then
+  /// sent `workspace/diagnostic/refresh` requests when they change.
+  pull_mode: AtomicBool,
+  next_result_id: AtomicU64,
 }
 
 impl DiagnosticsState {
@@ -319,8 +363,8 @@ impl DiagnosticsState {
     diagnostics: &[lsp::Diagnostic],
   ) {
     let mut specifiers = self.specifiers.write();
-    let current_version = specifiers.get(specifier).and_then(|s| s.version);
-    match (version, current_version) {
+    let current = specifiers.get(specifier);
+    match (version, current.and_then(|s| s.version)) {
       (Some(arg), Some(existing)) if arg < existing => return,
       _ => {}
     }
@@ -334,15 +378,60 @@ impl DiagnosticsState {
         no_cache_diagnostics.push(diagnostic.clone());
       }
     }
+    let diagnostics = if self.is_pull_mode() {
+      diagnostics.to_vec()
+    } else {
+      Vec::new()
+    };
+    // each diagnostic source updates the specifier separately, so only
+    // provide a new result id to pulling clients when something changed
+    let result_id = match current {
+      Some(current)
+        if current.version == version && current.diagnostics == diagnostics =>
+      {
+        current.result_id
+      }
+      _ => self.next_result_id.fetch_add(1, Ordering::SeqCst),
+    };
     specifiers.insert(
       specifier.clone(),
       SpecifierState {
         version,
         no_cache_diagnostics,
+        diagnostics,
+        result_id,
       },
     );
   }
 
+  pub fn set_pull_mode(&self, pull_mode: bool) {
+    self.pull_mode.store(pull_mode, Ordering::SeqCst);
+  }
+
+  pub fn is_pull_mode(&self) -> bool {
+    self.pull_mode.load(Ordering::SeqCst)
+  }
+
+  pub fn pulled_diagnostics(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<PulledDiagnostics> {
+    self
+      .specifiers
+      .read()
+      .get(specifier)
+      .map(|state| to_pulled_diagnostics(specifier, state))
+  }
+
+  pub fn all_pulled_diagnostics(&self) -> Vec<PulledDiagnostics> {
+    self
+      .specifiers
+      .read()
+      .iter()
+      .map(|(specifier, state)| to_pulled_diagnostics(specifier, state))
+      .collect()
+  }
+
   pub fn clear(&self, specifier: &ModuleSpecifier) {
     self.specifiers.write().remove(specifier);
   }
@@ -369,6 +458,18 @@ impl DiagnosticsState {
   }
 }
 
+fn to_pulled_diagnostics(
+  specifier: &ModuleSpecifier,
+  state: &SpecifierState,
+) -> PulledDiagnostics {
+  PulledDiagnostics {
+    specifier: specifier.clone(),
+    version: state.version,
+    result_id: state.result_id.to_string(),
+    diagnostics: state.diagnostics.clone(),
+  }
+}
+
 #[derive(Debug)]
 pub struct DiagnosticsServer {
   channel: Option<mpsc::UnboundedSender<ChannelMessage>>,
@@ -458,6 +559,7 @@ impl DiagnosticsServer {
                     snapshot,
                     config,
                     lint_options,
+                    maybe_lint_plugin_runner,
                     url_map,
                   },
                 batch_index,
@@ -613,6 +715,7 @@ impl DiagnosticsServer {
                         &snapshot,
                         &config,
                         &lint_options,
+                        maybe_lint_plugin_runner.as_deref(),
                         token,
                       )
                     }
@@ -788,6 +891,7 @@ fn generate_lint_diagnostics(
   snapshot: &language_server::StateSnapshot,
   config: &ConfigSnapshot,
   lint_options: &LintOptions,
+  maybe_lint_plugin_runner: Option<&LintPluginRunner>,
   token: CancellationToken,
 ) -> DiagnosticVec {
   let documents = snapshot
@@ -820,6 +924,7 @@ fn generate_lint_diagnostics(
           config,
           lint_options,
           lint_rules.clone(),
+          maybe_lint_plugin_runner,
           &document,
         ),
       },
@@ -832,6 +937,7 @@ fn generate_document_lint_diagnostics(
   config: &ConfigSnapshot,
   lint_options: &LintOptions,
   lint_rules: Vec<&'static dyn LintRule>,
+  maybe_lint_plugin_runner: Option<&LintPluginRunner>,
   document: &Document,
 ) -> Vec<lsp::Diagnostic> {
   if !config.specifier_enabled(document.specifier()) {
@@ -842,9 +948,11 @@ fn generate_document_lint_diagnostics(
   }
   match document.maybe_parsed_source() {
     Some(Ok(parsed_source)) => {
-      if let Ok(references) =
-        analysis::get_lint_references(&parsed_source, lint_rules)
-      {
+      if let Ok(references) = analysis::get_lint_references(
+        &parsed_source,
+        lint_rules,
+        maybe_lint_plugin_runner,
+      ) {
         references
           .into_iter()
           .map(|r| r.to_diagnostic())
@@ -1645,6 +1753,8 @@ let c: number = "a";
       rules: Default::default(),
       files: FilePatterns::new_with_base(temp_dir.path().to_path_buf()),
       reporter_kind: Default::default(),
+      fix_mode: Default::default(),
+      plugins: Vec::new(),
     };
 
     // test enabled
@@ -1654,6 +1764,7 @@ let c: number = "a";
         &snapshot,
         &enabled_config,
         &lint_options,
+        None,
         Default::default(),
       );
       assert_eq!(get_diagnostics_for_single(diagnostics).len(), 6);
@@ -1686,6 +1797,7 @@ let c: number = "a";
         &snapshot,
         &disabled_config,
         &lint_options,
+        None,
         Default::default(),
       );
       assert_eq!(get_diagnostics_for_single(diagnostics).len(), 0);
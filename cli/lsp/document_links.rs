@@ -0,0 +1,248 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Provides document links for the import specifiers of modules, and for the
+//! import map entries and paths of configuration files.
+
+use super::text::LineIndex;
+
+use deno_core::error::AnyError;
+use deno_core::url::Url;
+use deno_core::ModuleSpecifier;
+use deno_graph::Resolution;
+use deno_semver::npm::NpmPackageReqReference;
+use indexmap::IndexMap;
+use jsonc_parser::ast::Object;
+use jsonc_parser::ast::ObjectPropName;
+use jsonc_parser::ast::Str;
+use jsonc_parser::ast::Value;
+use text_size::TextSize;
+use tower_lsp::lsp_types as lsp;
+
+/// Properties of a configuration file which contain a path.
+const CONFIG_FILE_PATH_PROPERTIES: [&str; 2] = ["importMap", "lock"];
+
+/// Gets the links for the remote and registry import specifiers of a module.
+/// The `get_remote_target` function provides the target for a resolved remote
+/// module, which is the cached module when available.
+pub fn get_module_links(
+  dependencies: &IndexMap<String, deno_graph::Dependency>,
+  get_remote_target: impl Fn(&ModuleSpecifier) -> Url,
+) -> Vec<lsp::DocumentLink> {
+  let mut links = Vec::new();
+  for (specifier_text, dependency) in dependencies {
+    let Resolution::Ok(resolved) = &dependency.maybe_code else {
+      continue;
+    };
+    let (target, tooltip) =
+      if let Some(registry_url) = get_registry_url(specifier_text) {
+        (registry_url, "Open the registry page")
+      } else if matches!(resolved.specifier.scheme(), "http" | "https") {
+        (get_remote_target(&resolved.specifier), "Open the module")
+      } else {
+        continue;
+      };
+    links.push(lsp::DocumentLink {
+      range: super::documents::to_lsp_range(&resolved.range),
+      target: Some(target),
+      tooltip: Some(tooltip.to_string()),
+      data: None,
+    });
+  }
+  links
+}
+
+/// Gets the links for the `imports` and `scopes` of a configuration or import
+/// map file, along with the paths in a configuration file.
+pub fn get_json_links(
+  text: &str,
+  line_index: &LineIndex,
+  specifier: &ModuleSpecifier,
+  is_config_file: bool,
+) -> Result<Vec<lsp::DocumentLink>, AnyError> {
+  let ast =
+    jsonc_parser::parse_to_ast(text, &Default::default(), &Default::default())?;
+  let Some(Value::Object(root)) = ast.value else {
+    return Ok(Vec::new());
+  };
+  let mut links = Vec::new();
+  for prop in &root.properties {
+    match (prop_name(&prop.name), &prop.value) {
+      ("imports", Value::Object(imports)) => {
+        add_import_links(imports, text, line_index, specifier, &mut links);
+      }
+      ("scopes", Value::Object(scopes)) => {
+        for scope in &scopes.properties {
+          if let Value::Object(imports) = &scope.value {
+            add_import_links(imports, text, line_index, specifier, &mut links);
+          }
+        }
+      }
+      (name, Value::StringLit(lit))
+        if is_config_file && CONFIG_FILE_PATH_PROPERTIES.contains(&name) =>
+      {
+        if let Ok(target) = specifier.join(&lit.value) {
+          links.push(to_string_link(lit, text, line_index, target));
+        }
+      }
+      _ => {}
+    }
+  }
+  Ok(links)
+}
+
+fn add_import_links(
+  imports: &Object,
+  text: &str,
+  line_index: &LineIndex,
+  base: &ModuleSpecifier,
+  links: &mut Vec<lsp::DocumentLink>,
+) {
+  for prop in &imports.properties {
+    if let Value::StringLit(lit) = &prop.value {
+      if let Some(target) = get_import_target(&lit.value, base) {
+        links.push(to_string_link(lit, text, line_index, target));
+      }
+    }
+  }
+}
+
+/// Creates a link for the contents of a string literal.
+fn to_string_link(
+  lit: &Str,
+  text: &str,
+  line_index: &LineIndex,
+  target: Url,
+) -> lsp::DocumentLink {
+  // exclude the quotes
+  let start = lit.range.start + 1;
+  let end = lit.range.end.saturating_sub(1).max(start);
+  let utf16_offset = |offset: usize| {
+    TextSize::from(text[..offset].encode_utf16().count() as u32)
+  };
+  lsp::DocumentLink {
+    range: lsp::Range {
+      start: line_index.position_utf16(utf16_offset(start)),
+      end: line_index.position_utf16(utf16_offset(end)),
+    },
+    target: Some(target),
+    tooltip: None,
+    data: None,
+  }
+}
+
+fn prop_name<'a>(name: &'a ObjectPropName) -> &'a str {
+  match name {
+    ObjectPropName::String(lit) => &lit.value,
+    ObjectPropName::Word(word) => word.value,
+  }
+}
+
+/// Gets the target of an import map value, which may be relative to the
+/// import map.
+fn get_import_target(value: &str, base: &ModuleSpecifier) -> Option<Url> {
+  if let Some(registry_url) = get_registry_url(value) {
+    return Some(registry_url);
+  }
+  let url = base.join(value).ok()?;
+  match url.scheme() {
+    "file" | "http" | "https" => Some(url),
+    _ => None,
+  }
+}
+
+/// Gets the url of the registry page for a `jsr:` or `npm:` specifier.
+fn get_registry_url(specifier: &str) -> Option<Url> {
+  if let Some(jsr_specifier) = specifier.strip_prefix("jsr:") {
+    let jsr_specifier =
+      jsr_specifier.strip_prefix('/').unwrap_or(jsr_specifier);
+    let (scope, rest) = jsr_specifier.strip_prefix('@')?.split_once('/')?;
+    let name = rest
+      .split(|c| c == '@' || c == '/')
+      .next()
+      .filter(|name| !name.is_empty())?;
+    return Url::parse(&format!("https://jsr.io/@{scope}/{name}")).ok();
+  }
+  if specifier.starts_with("npm:") {
+    let req_ref = NpmPackageReqReference::from_str(specifier).ok()?;
+    return Url::parse(&format!(
+      "https://www.npmjs.com/package/{}",
+      req_ref.req().name
+    ))
+    .ok();
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get_registry_url() {
+    let cases = [
+      ("jsr:@std/path", Some("https://jsr.io/@std/path")),
+      ("jsr:@std/path@^0.210.0", Some("https://jsr.io/@std/path")),
+      ("jsr:/@std/path@1/posix", Some("https://jsr.io/@std/path")),
+      ("jsr:std/path", None),
+      ("npm:chalk@5", Some("https://www.npmjs.com/package/chalk")),
+      (
+        "npm:@types/node/fs",
+        Some("https://www.npmjs.com/package/@types/node"),
+      ),
+      ("https://deno.land/std/path/mod.ts", None),
+    ];
+    for (specifier, expected) in cases {
+      assert_eq!(
+        get_registry_url(specifier).as_ref().map(|u| u.as_str()),
+        expected,
+        "{specifier}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_get_json_links() {
+    let text = r#"{
+  // comment
+  "importMap": "./import_map.json",
+  "imports": {
+    "@std/path": "jsr:@std/path@^0.210.0",
+    "chalk": "npm:chalk@5",
+    "std/": "https://deno.land/std@0.210.0/",
+    "~/": "./src/"
+  },
+  "scopes": {
+    "https://deno.land/": { "a": "./a.ts" }
+  }
+}"#;
+    let specifier = ModuleSpecifier::parse("file:///a/deno.json").unwrap();
+    let line_index = LineIndex::new(text);
+    let links = get_json_links(text, &line_index, &specifier, true).unwrap();
+    let actual = links
+      .iter()
+      .map(|link| {
+        (
+          link.range.start.line,
+          link.range.start.character,
+          link.range.end.character,
+          link.target.as_ref().unwrap().to_string(),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      actual,
+      vec![
+        (2, 16, 33, "file:///a/import_map.json".to_string()),
+        (4, 18, 40, "https://jsr.io/@std/path".to_string()),
+        (5, 14, 25, "https://www.npmjs.com/package/chalk".to_string()),
+        (6, 13, 43, "https://deno.land/std@0.210.0/".to_string()),
+        (7, 11, 17, "file:///a/src/".to_string()),
+        (10, 34, 40, "file:///a/a.ts".to_string()),
+      ]
+    );
+
+    // paths are only linked in configuration files
+    let links = get_json_links(text, &line_index, &specifier, false).unwrap();
+    assert_eq!(links.len(), 5);
+  }
+}
@@ -61,6 +61,7 @@ use super::diagnostics::DiagnosticDataSpecifier;
 use super::diagnostics::DiagnosticServerUpdateMessage;
 use super::diagnostics::DiagnosticsServer;
 use super::diagnostics::DiagnosticsState;
+use super::document_links;
 use super::documents::to_hover_text;
 use super::documents::to_lsp_range;
 use super::documents::AssetOrDocument;
@@ -90,6 +91,8 @@ use super::urls;
 use crate::args::get_root_cert_store;
 use crate::args::package_json;
 use crate::args::resolve_import_map_from_specifier;
+use crate::args::take_coverage_thresholds;
+use crate::args::take_lint_plugins;
 use crate::args::CaData;
 use crate::args::CacheSetting;
 use crate::args::CliOptions;
@@ -98,6 +101,7 @@ use crate::args::Flags;
 use crate::args::FmtOptions;
 use crate::args::LintOptions;
 use crate::args::TsConfig;
+use crate::args::UnstableFmtOptions;
 use crate::cache::DenoDir;
 use crate::cache::FastInsecureHasher;
 use crate::cache::GlobalHttpCache;
@@ -119,6 +123,8 @@ use crate::npm::CliNpmResolverManagedPackageJsonInstallerOption;
 use crate::npm::CliNpmResolverManagedSnapshotOption;
 use crate::tools::fmt::format_file;
 use crate::tools::fmt::format_parsed_source;
+use crate::tools::fmt::restrict_to_line_ranges;
+use crate::tools::lint::LintPluginRunner;
 use crate::tools::upgrade::check_for_upgrades_for_lsp;
 use crate::tools::upgrade::upgrade_check_enabled;
 use crate::util::fs::remove_dir_all_if_exists;
@@ -258,6 +264,8 @@ pub struct Inner {
   fmt_options: FmtOptions,
   /// An optional configuration for linter which has been taken from specified config file.
   lint_options: LintOptions,
+  /// Runs the rules of the lint plugins specified in the config file.
+  maybe_lint_plugin_runner: Option<Arc<LintPluginRunner>>,
   /// A lazily create "server" for handling test run requests.
   maybe_testing_server: Option<testing::TestServer>,
   /// Services used for dealing with npm related functionality.
@@ -406,6 +414,24 @@ impl LanguageServer {
     self.0.read().await.task_definitions()
   }
 
+  /// Gets the TypeScript diagnostics of a document, which the REPL uses to
+  /// type check its input.
+  pub async fn ts_diagnostics(
+    &self,
+    specifier: ModuleSpecifier,
+  ) -> Result<Vec<crate::tsc::Diagnostic>, AnyError> {
+    let inner = self.0.read().await;
+    let mut diagnostics = inner
+      .ts_server
+      .get_diagnostics(
+        inner.snapshot(),
+        vec![specifier.clone()],
+        CancellationToken::new(),
+      )
+      .await?;
+    Ok(diagnostics.remove(specifier.as_str()).unwrap_or_default())
+  }
+
   pub async fn test_run_request(
     &self,
     params: Option<Value>,
@@ -551,6 +577,7 @@ impl Inner {
       fmt_options: FmtOptions::new_with_base(initial_cwd.clone()),
       task_queue: Default::default(),
       lint_options: LintOptions::new_with_base(initial_cwd),
+      maybe_lint_plugin_runner: None,
       maybe_testing_server: None,
       module_registries,
       module_registries_location,
@@ -1048,15 +1075,41 @@ impl Inner {
     Ok(())
   }
 
+  async fn create_lint_plugin_runner(
+    &self,
+  ) -> Result<LintPluginRunner, AnyError> {
+    let cli_options = CliOptions::new(
+      Flags {
+        cache_path: self.maybe_global_cache_path.clone(),
+        ..Default::default()
+      },
+      self.initial_cwd.clone(),
+      self.config.maybe_config_file().cloned(),
+      self.config.maybe_lockfile().cloned(),
+      self.maybe_package_json.clone(),
+    )?;
+    let factory = CliFactory::from_cli_options(Arc::new(cli_options));
+    LintPluginRunner::create(&factory, self.lint_options.plugins.clone()).await
+  }
+
   async fn update_config_file(&mut self) -> Result<(), AnyError> {
     self.config.clear_config_file();
     self.fmt_options = FmtOptions::new_with_base(self.initial_cwd.clone());
     self.lint_options = LintOptions::new_with_base(self.initial_cwd.clone());
-    if let Some(config_file) = self.get_config_file()? {
-      let lint_options = config_file
-        .to_lint_config()
-        .and_then(|maybe_lint_config| {
-          LintOptions::resolve(maybe_lint_config, None, &self.initial_cwd)
+    self.maybe_lint_plugin_runner = None;
+    if let Some(mut config_file) = self.get_config_file()? {
+      // the coverage configuration isn't used here, but it's taken out of
+      // the test configuration like when the CLI loads the config file
+      if let Err(err) = take_coverage_thresholds(&mut config_file) {
+        lsp_warn!("Unable to read coverage configuration: {:#}", err);
+      }
+      let lint_options = take_lint_plugins(&mut config_file)
+        .and_then(|plugins| {
+          let maybe_lint_config = config_file.to_lint_config()?;
+          let mut lint_options =
+            LintOptions::resolve(maybe_lint_config, None, &self.initial_cwd)?;
+          lint_options.plugins = plugins;
+          Ok(lint_options)
         })
         .map_err(|err| {
           anyhow!("Unable to update lint configuration: {:?}", err)
@@ -1064,7 +1117,13 @@ impl Inner {
       let fmt_options = config_file
         .to_fmt_config()
         .and_then(|maybe_fmt_config| {
-          FmtOptions::resolve(maybe_fmt_config, None, &self.initial_cwd)
+          let unstable = UnstableFmtOptions::resolve(Some(&config_file), None);
+          FmtOptions::resolve(
+            maybe_fmt_config,
+            unstable,
+            None,
+            &self.initial_cwd,
+          )
         })
         .map_err(|err| {
           anyhow!("Unable to update formatter configuration: {:?}", err)
@@ -1073,6 +1132,12 @@ impl Inner {
       self.config.set_config_file(config_file);
       self.lint_options = lint_options;
       self.fmt_options = fmt_options;
+      if !self.lint_options.plugins.is_empty() {
+        match self.create_lint_plugin_runner().await {
+          Ok(runner) => self.maybe_lint_plugin_runner = Some(Arc::new(runner)),
+          Err(err) => lsp_warn!("Unable to load lint plugins: {:#}", err),
+        }
+      }
       self.recreate_http_client_and_dependents().await?;
       if let Some(config_file) = self.config.maybe_config_file() {
         if let Ok((compiler_options, _)) = config_file.to_compiler_options() {
@@ -1230,11 +1295,6 @@ impl Inner {
       }
     }
 
-    let capabilities = capabilities::server_capabilities(
-      &params.capabilities,
-      enable_builtin_commands,
-    );
-
     let version = format!(
       "{} ({}, {})",
       crate::version::deno(),
@@ -1301,8 +1361,17 @@ impl Inner {
         }
       }
       self.config.update_capabilities(&params.capabilities);
+      self
+        .diagnostics_state
+        .set_pull_mode(self.config.client_capabilities.pull_diagnostics);
     }
 
+    let capabilities = capabilities::server_capabilities(
+      &params.capabilities,
+      enable_builtin_commands,
+      self.config.client_capabilities.pull_diagnostics,
+    );
+
     self
       .ts_server
       .start(self.config.internal_inspect().to_address());
@@ -1782,13 +1851,216 @@ impl Inner {
     Ok(response)
   }
 
+  async fn document_link(
+    &self,
+    params: DocumentLinkParams,
+  ) -> LspResult<Option<Vec<DocumentLink>>> {
+    let specifier = self
+      .url_map
+      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let Some(document) = self.documents.get(&specifier) else {
+      return Ok(None);
+    };
+    let mark = self
+      .performance
+      .mark_with_args("lsp.document_link", &params);
+    let is_config_file = self
+      .config
+      .maybe_config_file()
+      .map(|config_file| config_file.specifier == specifier)
+      .unwrap_or(false);
+    let is_import_map = self.maybe_import_map_uri.as_ref() == Some(&specifier);
+    let links = if is_config_file || is_import_map {
+      document_links::get_json_links(
+        &document.content(),
+        &document.line_index(),
+        &specifier,
+        is_config_file,
+      )
+      .unwrap_or_else(|err| {
+        lsp_warn!("Unable to get document links for \"{specifier}\": {err:#}");
+        Vec::new()
+      })
+    } else if self.config.specifier_enabled(&specifier) {
+      document_links::get_module_links(document.dependencies(), |remote| {
+        // prefer opening the cached module
+        if self.documents.exists(remote) {
+          if let Ok(url) = self.url_map.normalize_specifier(remote) {
+            return url.into_url();
+          }
+        }
+        remote.clone()
+      })
+    } else {
+      Vec::new()
+    };
+    self.performance.measure(mark);
+    if links.is_empty() {
+      Ok(None)
+    } else {
+      Ok(Some(links))
+    }
+  }
+
+  async fn diagnostic(
+    &self,
+    params: DocumentDiagnosticParams,
+  ) -> LspResult<DocumentDiagnosticReportResult> {
+    let specifier = self
+      .url_map
+      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let mark = self.performance.mark_with_args("lsp.diagnostic", &params);
+    let report = match self.diagnostics_state.pulled_diagnostics(&specifier) {
+      Some(pulled)
+        if params.previous_result_id == Some(pulled.result_id.clone()) =>
+      {
+        DocumentDiagnosticReport::Unchanged(
+          RelatedUnchangedDocumentDiagnosticReport {
+            related_documents: None,
+            unchanged_document_diagnostic_report:
+              UnchangedDocumentDiagnosticReport {
+                result_id: pulled.result_id,
+              },
+          },
+        )
+      }
+      pulled => {
+        DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+          related_documents: None,
+          full_document_diagnostic_report: FullDocumentDiagnosticReport {
+            result_id: pulled.as_ref().map(|p| p.result_id.clone()),
+            items: pulled.map(|p| p.diagnostics).unwrap_or_default(),
+          },
+        })
+      }
+    };
+    self.performance.measure(mark);
+    Ok(DocumentDiagnosticReportResult::Report(report))
+  }
+
+  async fn workspace_diagnostic(
+    &self,
+    params: WorkspaceDiagnosticParams,
+  ) -> LspResult<WorkspaceDiagnosticReportResult> {
+    let mark = self
+      .performance
+      .mark_with_args("lsp.workspace_diagnostic", &params);
+    let previous_result_ids = params
+      .previous_result_ids
+      .into_iter()
+      .map(|previous| (previous.uri, previous.value))
+      .collect::<HashMap<_, _>>();
+    let mut items = Vec::new();
+    for pulled in self.diagnostics_state.all_pulled_diagnostics() {
+      let uri = self
+        .url_map
+        .normalize_specifier(&pulled.specifier)
+        .map(|url| url.into_url())
+        .unwrap_or(pulled.specifier);
+      let version = pulled.version.map(|v| v as i64);
+      if previous_result_ids.get(&uri) == Some(&pulled.result_id) {
+        items.push(WorkspaceDocumentDiagnosticReport::Unchanged(
+          WorkspaceUnchangedDocumentDiagnosticReport {
+            uri,
+            version,
+            unchanged_document_diagnostic_report:
+              UnchangedDocumentDiagnosticReport {
+                result_id: pulled.result_id,
+              },
+          },
+        ));
+      } else {
+        items.push(WorkspaceDocumentDiagnosticReport::Full(
+          WorkspaceFullDocumentDiagnosticReport {
+            uri,
+            version,
+            full_document_diagnostic_report: FullDocumentDiagnosticReport {
+              result_id: Some(pulled.result_id),
+              items: pulled.diagnostics,
+            },
+          },
+        ));
+      }
+    }
+    self.performance.measure(mark);
+    Ok(WorkspaceDiagnosticReportResult::Report(
+      WorkspaceDiagnosticReport { items },
+    ))
+  }
+
   async fn formatting(
     &self,
     params: DocumentFormattingParams,
   ) -> LspResult<Option<Vec<TextEdit>>> {
-    let mut specifier = self
-      .url_map
-      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let mark = self.performance.mark_with_args("lsp.formatting", &params);
+    let result = self
+      .format_document(&params.text_document.uri, None, true)
+      .await;
+    self.performance.measure(mark);
+    result
+  }
+
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let mark = self
+      .performance
+      .mark_with_args("lsp.range_formatting", &params);
+    let result = self
+      .format_document(&params.text_document.uri, Some(params.range), true)
+      .await;
+    self.performance.measure(mark);
+    result
+  }
+
+  async fn on_type_formatting(
+    &self,
+    params: DocumentOnTypeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let uri = &params.text_document_position.text_document.uri;
+    let position = params.text_document_position.position;
+    let specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
+    let Some(document) = self.documents.get(&specifier) else {
+      return Ok(None);
+    };
+    let mark = self
+      .performance
+      .mark_with_args("lsp.on_type_formatting", &params);
+    // format the statement ending in `;` or the block ending in `}`
+    let start_line = if params.ch == "}" {
+      let content = document.content();
+      document
+        .line_index()
+        .offset(position)
+        .ok()
+        .map(|offset| u32::from(offset) as usize)
+        .filter(|offset| *offset > 0 && *offset <= content.len())
+        .and_then(|offset| text::find_open_brace_line(&content, offset - 1))
+        .unwrap_or(position.line)
+    } else {
+      position.line
+    };
+    let range = Range {
+      start: Position::new(start_line, 0),
+      end: position,
+    };
+    // the document is likely incomplete while typing, so don't notify
+    // the user about syntax errors
+    let result = self.format_document(uri, Some(range), false).await;
+    self.performance.measure(mark);
+    result
+  }
+
+  /// Formats the document, keeping only the changes to the lines of the
+  /// range when one is provided.
+  async fn format_document(
+    &self,
+    uri: &Url,
+    maybe_range: Option<Range>,
+    show_error_message: bool,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let mut specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
     // skip formatting any files ignored by the config file
     if !self.fmt_options.files.matches_specifier(&specifier) {
       return Ok(None);
@@ -1800,40 +2072,65 @@ impl Inner {
     // Detect vendored paths. Vendor file URLs will normalize to their remote
     // counterparts, but for formatting we want to favour the file URL.
     // TODO(nayeemrmn): Implement `Document::file_resource_path()` or similar.
-    if specifier.scheme() != "file"
-      && params.text_document.uri.scheme() == "file"
-    {
-      specifier = params.text_document.uri.clone();
+    if specifier.scheme() != "file" && uri.scheme() == "file" {
+      specifier = uri.clone();
     }
     let file_path = specifier_to_file_path(&specifier).map_err(|err| {
       error!("{}", err);
       LspError::invalid_request()
     })?;
-    let mark = self.performance.mark_with_args("lsp.formatting", &params);
 
     // spawn a blocking task to allow doing other work while this is occurring
     let text_edits = deno_core::unsync::spawn_blocking({
       let fmt_options = self.fmt_options.options.clone();
+      let unstable_options = self.fmt_options.unstable.clone();
       let document = document.clone();
       move || {
+        // the file path is only used to determine what formatter should
+        // be used to format the file, so give the filepath an extension
+        // that matches what the user selected as the language
+        let file_path = document
+          .maybe_language_id()
+          .and_then(|id| id.as_extension())
+          .map(|ext| file_path.with_extension(ext))
+          .unwrap_or(file_path);
         let format_result = match document.maybe_parsed_source() {
           Some(Ok(parsed_source)) => {
             format_parsed_source(&parsed_source, &fmt_options)
           }
           Some(Err(err)) => Err(anyhow!("{}", err)),
           None => {
-            // the file path is only used to determine what formatter should
-            // be used to format the file, so give the filepath an extension
-            // that matches what the user selected as the language
-            let file_path = document
-              .maybe_language_id()
-              .and_then(|id| id.as_extension())
-              .map(|ext| file_path.with_extension(ext))
-              .unwrap_or(file_path);
             // it's not a js/ts file, so attempt to format its contents
-            format_file(&file_path, &document.content(), &fmt_options)
+            format_file(
+              &file_path,
+              &document.content(),
+              &fmt_options,
+              &unstable_options,
+            )
           }
         };
+        let format_result = match maybe_range {
+          Some(range) => format_result.map(|maybe_new_text| {
+            let new_text = maybe_new_text?;
+            // a selection ending at the start of a line doesn't include it
+            let end_line = if range.end.character == 0
+              && range.end.line > range.start.line
+            {
+              range.end.line
+            } else {
+              range.end.line + 1
+            };
+            restrict_to_line_ranges(
+              &file_path,
+              &document.content(),
+              &new_text,
+              &[range.start.line as usize..end_line as usize],
+              &fmt_options,
+              &unstable_options,
+            )
+          }),
+          None => format_result,
+        };
         match format_result {
           Ok(Some(new_text)) => Some(text::get_edits(
             &document.content(),
@@ -1851,7 +2148,6 @@ impl Inner {
     .await
     .unwrap();
 
-    self.performance.measure(mark);
     if let Some(text_edits) = text_edits {
       if text_edits.is_empty() {
         Ok(None)
@@ -1859,7 +2155,9 @@ impl Inner {
         Ok(Some(text_edits))
       }
     } else {
-      self.client.show_message(MessageType::WARNING, format!("Unable to format \"{specifier}\". Likely due to unrecoverable syntax errors in the file."));
+      if show_error_message {
+        self.client.show_message(MessageType::WARNING, format!("Unable to format \"{specifier}\". Likely due to unrecoverable syntax errors in the file."));
+      }
       Ok(None)
     }
   }
@@ -2046,17 +2344,25 @@ impl Inner {
                 LspError::internal_error()
               })?
           }
-          Some("deno-lint") => code_actions
-            .add_deno_lint_ignore_action(
-              &specifier,
-              diagnostic,
-              asset_or_doc.document().map(|d| d.text_info()),
-              asset_or_doc.maybe_parsed_source().and_then(|r| r.ok()),
-            )
-            .map_err(|err| {
-              error!("Unable to fix lint error: {}", err);
-              LspError::internal_error()
-            })?,
+          Some("deno-lint") => {
+            code_actions
+              .add_deno_lint_fix_actions(&specifier, diagnostic)
+              .map_err(|err| {
+                error!("Unable to fix lint error: {}", err);
+                LspError::internal_error()
+              })?;
+            code_actions
+              .add_deno_lint_ignore_action(
+                &specifier,
+                diagnostic,
+                asset_or_doc.document().map(|d| d.text_info()),
+                asset_or_doc.maybe_parsed_source().and_then(|r| r.ok()),
+              )
+              .map_err(|err| {
+                error!("Unable to fix lint error: {}", err);
+                LspError::internal_error()
+              })?
+          }
           _ => (),
         }
       }
@@ -3204,6 +3510,7 @@ impl Inner {
       snapshot: self.snapshot(),
       config: self.config.snapshot(),
       lint_options: self.lint_options.clone(),
+      maybe_lint_plugin_runner: self.maybe_lint_plugin_runner.clone(),
       url_map: self.url_map.clone(),
     };
     if let Err(err) = self.diagnostics_server.update(snapshot) {
@@ -3516,6 +3823,27 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.0.read().await.document_symbol(params).await
   }
 
+  async fn document_link(
+    &self,
+    params: DocumentLinkParams,
+  ) -> LspResult<Option<Vec<DocumentLink>>> {
+    self.0.read().await.document_link(params).await
+  }
+
+  async fn diagnostic(
+    &self,
+    params: DocumentDiagnosticParams,
+  ) -> LspResult<DocumentDiagnosticReportResult> {
+    self.0.read().await.diagnostic(params).await
+  }
+
+  async fn workspace_diagnostic(
+    &self,
+    params: WorkspaceDiagnosticParams,
+  ) -> LspResult<WorkspaceDiagnosticReportResult> {
+    self.0.read().await.workspace_diagnostic(params).await
+  }
+
   async fn formatting(
     &self,
     params: DocumentFormattingParams,
@@ -3523,6 +3851,20 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.0.read().await.formatting(params).await
   }
 
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    self.0.read().await.range_formatting(params).await
+  }
+
+  async fn on_type_formatting(
+    &self,
+    params: DocumentOnTypeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    self.0.read().await.on_type_formatting(params).await
+  }
+
   async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
     self.0.read().await.hover(params).await
   }
@@ -3768,7 +4110,10 @@ impl Inner {
     if let Some(config_file) = self.config.maybe_config_file() {
       if let Some(tasks) = json!(&config_file.json.tasks).as_object() {
         for (name, value) in tasks {
-          let Some(command) = value.as_str() else {
+          let Some(command) = value
+            .as_str()
+            .or_else(|| value.get("command").and_then(|c| c.as_str()))
+          else {
             continue;
           };
           result.push(TaskDefinition {
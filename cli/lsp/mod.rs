@@ -20,6 +20,7 @@ mod code_lens;
 mod completions;
 mod config;
 mod diagnostics;
+mod document_links;
 mod documents;
 pub mod language_server;
 mod logging;
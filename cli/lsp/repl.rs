@@ -18,8 +18,11 @@ use tower_lsp::lsp_types::CompletionTriggerKind;
 use tower_lsp::lsp_types::DidChangeTextDocumentParams;
 use tower_lsp::lsp_types::DidCloseTextDocumentParams;
 use tower_lsp::lsp_types::DidOpenTextDocumentParams;
+use tower_lsp::lsp_types::HoverContents;
+use tower_lsp::lsp_types::HoverParams;
 use tower_lsp::lsp_types::InitializeParams;
 use tower_lsp::lsp_types::InitializedParams;
+use tower_lsp::lsp_types::MarkedString;
 use tower_lsp::lsp_types::PartialResultParams;
 use tower_lsp::lsp_types::Position;
 use tower_lsp::lsp_types::Range;
@@ -40,6 +43,23 @@ use super::config::LanguageWorkspaceSettings;
 use super::config::ObjectLiteralMethodSnippets;
 use super::config::TestingSettings;
 use super::config::WorkspaceSettings;
+use crate::tsc::Diagnostic;
+use crate::tsc::DiagnosticCategory;
+
+/// Diagnostics which are expected for the REPL's code, since it's evaluated
+/// line by line and allows redeclarations and top level await.
+#[rustfmt::skip]
+const IGNORED_DIAGNOSTIC_CODES: &[u64] = &[
+  // TS1375: 'await' expressions are only allowed at the top level of a file
+  // when that file is a module, but this file has no imports or exports.
+  1375,
+  // TS2300: Duplicate identifier '...'.
+  2300,
+  // TS2393: Duplicate function implementation.
+  2393,
+  // TS2451: Cannot redeclare block-scoped variable '...'.
+  2451,
+];
 
 #[derive(Debug)]
 pub struct ReplCompletionItem {
@@ -192,6 +212,140 @@ impl ReplLanguageServer {
       .collect()
   }
 
+  /// Gets the hover information of the symbol at the position in the line,
+  /// such as its type and JSDoc, as markdown.
+  pub async fn hover(
+    &mut self,
+    line_text: &str,
+    position: usize,
+  ) -> Option<String> {
+    let text = self
+      .hover_parts(line_text, position)
+      .await?
+      .into_iter()
+      .map(|part| match part {
+        MarkedString::String(text) => text,
+        MarkedString::LanguageString(code) => {
+          format!("```{}\n{}\n```", code.language, code.value)
+        }
+      })
+      .collect::<Vec<_>>()
+      .join("\n\n");
+    let text = text.trim();
+    if text.is_empty() {
+      None
+    } else {
+      Some(text.to_string())
+    }
+  }
+
+  /// Gets the inferred type of an expression, which may use the declarations
+  /// of the committed text.
+  pub async fn expression_type(&mut self, expr: &str) -> Option<String> {
+    const DECLARATION: &str = "const $deno$type";
+    let line_text = format!("{DECLARATION} = ({expr});");
+    let parts = self.hover_parts(&line_text, DECLARATION.len()).await?;
+    parts.into_iter().find_map(|part| match part {
+      MarkedString::LanguageString(code) => code
+        .value
+        .strip_prefix(&format!("{DECLARATION}: "))
+        .map(|ts_type| ts_type.to_string()),
+      MarkedString::String(_) => None,
+    })
+  }
+
+  /// Type checks the line along with the committed text, returning the
+  /// errors which are in the line. The positions of the errors are relative
+  /// to the line.
+  pub async fn type_check(&mut self, line_text: &str) -> Vec<Diagnostic> {
+    self.did_change(line_text).await;
+    let line_count =
+      self.document_text.chars().filter(|c| *c == '\n').count() as u64;
+    let diagnostics = match self
+      .language_server
+      .ts_diagnostics(self.get_document_specifier())
+      .await
+    {
+      Ok(diagnostics) => diagnostics,
+      Err(err) => {
+        log::debug!("Failed to type check the REPL input: {:#}", err);
+        return Vec::new();
+      }
+    };
+    diagnostics
+      .into_iter()
+      .filter(|diagnostic| {
+        diagnostic.category == DiagnosticCategory::Error
+          && !IGNORED_DIAGNOSTIC_CODES.contains(&diagnostic.code)
+          && diagnostic
+            .start
+            .as_ref()
+            .is_some_and(|start| start.line >= line_count)
+      })
+      .map(|mut diagnostic| {
+        for position in [&mut diagnostic.start, &mut diagnostic.end]
+          .into_iter()
+          .flatten()
+        {
+          position.line -= line_count;
+        }
+        diagnostic.source_line = diagnostic.start.as_ref().and_then(|start| {
+          self
+            .pending_text
+            .lines()
+            .nth(start.line as usize)
+            .map(|line| line.to_string())
+        });
+        // the file name and related information would point to the REPL's
+        // internal document
+        diagnostic.file_name = None;
+        diagnostic.related_information = None;
+        diagnostic
+      })
+      .collect()
+  }
+
+  async fn hover_parts(
+    &mut self,
+    line_text: &str,
+    position: usize,
+  ) -> Option<Vec<MarkedString>> {
+    self.did_change(line_text).await;
+    let text_info = deno_ast::SourceTextInfo::from_string(format!(
+      "{}{}",
+      self.document_text, self.pending_text
+    ));
+    let position =
+      text_info.range().start + self.document_text.len() + position;
+    let line_and_column = text_info.line_and_column_index(position);
+    let hover = self
+      .language_server
+      .hover(HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+          text_document: TextDocumentIdentifier {
+            uri: self.get_document_specifier(),
+          },
+          position: Position {
+            line: line_and_column.line_index as u32,
+            character: line_and_column.column_index as u32,
+          },
+        },
+        work_done_progress_params: WorkDoneProgressParams {
+          work_done_token: None,
+        },
+      })
+      .await
+      .ok()??;
+
+    Some(match hover.contents {
+      HoverContents::Scalar(part) => vec![part],
+      HoverContents::Array(parts) => parts,
+      HoverContents::Markup(content) => {
+        vec![MarkedString::String(content.value)]
+      }
+    })
+  }
+
   async fn did_change(&mut self, new_text: &str) {
     self.check_cwd_change().await;
     let new_text = if new_text.ends_with('\n') {
@@ -269,6 +269,31 @@ pub fn get_edits(a: &str, b: &str, line_index: &LineIndex) -> Vec<TextEdit> {
   text_edits
 }
 
+/// Gets the zero based line of the `{` which matches the `}` at the provided
+/// byte offset. Braces in strings and comments aren't taken into account, so
+/// this is only suitable for choosing which lines to format.
+///
+/// Returns `None` when there is no `}` at the provided offset.
+pub fn find_open_brace_line(text: &str, close_offset: usize) -> Option<u32> {
+  if !text.is_char_boundary(close_offset)
+    || text.as_bytes().get(close_offset) != Some(&b'}')
+  {
+    return None;
+  }
+  let mut depth = 0;
+  for (index, c) in text[..close_offset].char_indices().rev() {
+    match c {
+      '}' => depth += 1,
+      '{' if depth == 0 => {
+        return Some(text[..index].matches('\n').count() as u32);
+      }
+      '{' => depth -= 1,
+      _ => {}
+    }
+  }
+  None
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -539,4 +564,18 @@ const C: char = \"メ メ\";
       ]
     )
   }
+
+  #[test]
+  fn test_find_open_brace_line() {
+    let text = "function a() {\n  if (b) {\n    c();\n  }\n}\n";
+    let last = text.rfind('}').unwrap();
+    assert_eq!(find_open_brace_line(text, last), Some(0));
+    let inner = text[..last].rfind('}').unwrap();
+    assert_eq!(find_open_brace_line(text, inner), Some(1));
+    assert_eq!(find_open_brace_line("a }", 2), None);
+    assert_eq!(find_open_brace_line("{ a }", 2), None);
+    assert_eq!(find_open_brace_line("{ \u{e9}}", 3), None);
+    assert_eq!(find_open_brace_line("{ \u{e9}}", 4), Some(0));
+    assert_eq!(find_open_brace_line("{}", 2), None);
+  }
 }
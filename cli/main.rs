@@ -138,6 +138,12 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     DenoSubcommand::Install(install_flags) => spawn_subcommand(async {
       tools::installer::install_command(flags, install_flags).await
     }),
+    DenoSubcommand::InstalledScripts(installed_scripts_flags) => {
+      spawn_subcommand(async {
+        tools::installer::installed_scripts_command(installed_scripts_flags)
+          .await
+      })
+    }
     DenoSubcommand::Jupyter(jupyter_flags) => spawn_subcommand(async {
       tools::jupyter::kernel(flags, jupyter_flags).await
     }),
@@ -160,6 +166,9 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       spawn_subcommand(async move { tools::repl::run(flags, repl_flags).await })
     }
     DenoSubcommand::Run(run_flags) => spawn_subcommand(async move {
+      if let Some(ref coverage_dir) = run_flags.coverage_dir {
+        set_coverage_dir_env_var(coverage_dir)?;
+      }
       if run_flags.is_stdin() {
         tools::run::run_from_stdin(flags).await
       } else {
@@ -172,14 +181,7 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     DenoSubcommand::Test(test_flags) => {
       spawn_subcommand(async {
         if let Some(ref coverage_dir) = test_flags.coverage_dir {
-          std::fs::create_dir_all(coverage_dir)
-            .with_context(|| format!("Failed creating: {coverage_dir}"))?;
-          // this is set in order to ensure spawned processes use the same
-          // coverage directory
-          env::set_var(
-            "DENO_UNSTABLE_COVERAGE_DIR",
-            PathBuf::from(coverage_dir).canonicalize()?,
-          );
+          set_coverage_dir_env_var(coverage_dir)?;
         }
 
         if test_flags.watch.is_some() {
@@ -219,6 +221,19 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
   handle.await?
 }
 
+/// Creates the coverage directory and exposes its absolute path to spawned
+/// processes, so that subprocesses running Deno write their coverage to the
+/// same directory.
+fn set_coverage_dir_env_var(coverage_dir: &str) -> Result<(), AnyError> {
+  std::fs::create_dir_all(coverage_dir)
+    .with_context(|| format!("Failed creating: {coverage_dir}"))?;
+  env::set_var(
+    "DENO_UNSTABLE_COVERAGE_DIR",
+    PathBuf::from(coverage_dir).canonicalize()?,
+  );
+  Ok(())
+}
+
 fn setup_panic_hook() {
   // This function does two things inside of the panic hook:
   // - Tokio does not exit the process when a task panics, so we define a custom
@@ -39,7 +39,7 @@ pub async fn op_jupyter_broadcast(
   #[serde] metadata: serde_json::Value,
   #[serde] buffers: Vec<deno_core::JsBuffer>,
 ) -> Result<(), AnyError> {
-  let (iopub_socket, last_execution_request) = {
+  let (iopub_socket, last_parent_message) = {
     let s = state.borrow();
 
     (
@@ -49,9 +49,9 @@ pub async fn op_jupyter_broadcast(
     )
   };
 
-  let maybe_last_request = last_execution_request.borrow().clone();
-  if let Some(last_request) = maybe_last_request {
-    last_request
+  let maybe_parent_message = last_parent_message.borrow().clone();
+  if let Some(parent_message) = maybe_parent_message {
+    parent_message
       .new_message(&message_type)
       .with_content(content)
       .with_metadata(metadata)
@@ -2,6 +2,7 @@
 
 use std::collections::BTreeMap;
 use std::env::current_exe;
+use std::io::Cursor;
 use std::io::Read;
 use std::io::Seek;
 use std::io::SeekFrom;
@@ -39,15 +40,26 @@ use crate::file_fetcher::FileFetcher;
 use crate::http_util::HttpClient;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
+use crate::util::fs::normalize_path;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 
+use super::payload::PayloadEncoding;
+use super::payload::KEY_LEN;
 use super::virtual_fs::FileBackedVfs;
 use super::virtual_fs::VfsBuilder;
 use super::virtual_fs::VfsRoot;
 use super::virtual_fs::VirtualDirectory;
 
-const MAGIC_TRAILER: &[u8; 8] = b"d3n0l4nd";
+/// Starts the trailer written by Deno versions before the trailer was
+/// versioned, which only held the positions of the first four sections.
+const LEGACY_MAGIC_TRAILER: &[u8; 8] = b"d3n0l4nd";
+const LEGACY_TRAILER_SIZE: usize = 8 + 4 * 8;
+/// Starts the trailer, followed by its version.
+const MAGIC_TRAILER: &[u8; 8] = b"d3n0l4nv";
+/// The version of the layout of the trailer and the sections it points to,
+/// which must be bumped whenever either changes.
+const TRAILER_VERSION: u64 = 3;
 
 #[derive(Serialize, Deserialize)]
 enum SerializablePackageJsonDepValueParseError {
@@ -137,6 +149,8 @@ pub enum NodeModules {
 
 #[derive(Deserialize, Serialize)]
 pub struct Metadata {
+  /// The version of Deno which compiled the binary.
+  pub deno_version: String,
   pub argv: Vec<String>,
   pub seed: Option<u64>,
   pub permissions: PermissionsOptions,
@@ -153,16 +167,66 @@ pub struct Metadata {
   pub unstable_config: UnstableConfig,
 }
 
-pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
-  let file_path = current_exe().unwrap();
-  let mut file = std::fs::File::open(file_path)?;
-  file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
+/// A directory embedded with `--include-dir`.
+#[derive(Deserialize, Serialize)]
+struct SerializedAssetsDir {
+  /// The absolute path of the directory on the machine it was compiled on,
+  /// which is where it's read from at runtime. The embedded modules keep
+  /// their specifiers from that machine too, so paths resolved relative to
+  /// `import.meta.url` point into the directory on any machine, while paths
+  /// relative to the current working directory don't.
+  root_path: PathBuf,
+  dir: VirtualDirectory,
+  /// The offset of the directory's files from the start of the asset files.
+  files_offset: u64,
+}
+
+#[derive(Default)]
+struct EmbeddedAssets {
+  dirs: Vec<SerializedAssetsDir>,
+  files: Vec<Vec<u8>>,
+}
+
+fn open_with_trailer(
+  exe_path: &Path,
+) -> Result<(std::fs::File, Trailer), AnyError> {
+  let mut file = std::fs::File::open(exe_path)?;
+  let trailer_pos = file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
   let mut trailer = [0; TRAILER_SIZE];
   file.read_exact(&mut trailer)?;
-  let trailer = Trailer::parse(&trailer)?.unwrap();
-  file.seek(SeekFrom::Start(trailer.npm_vfs_pos))?;
-  let mut vfs_data = vec![0; trailer.npm_vfs_len() as usize];
-  file.read_exact(&mut vfs_data)?;
+  let Some(trailer) = Trailer::parse(&trailer, trailer_pos)? else {
+    bail!("'{}' is not a standalone binary.", exe_path.display());
+  };
+  Ok((file, trailer))
+}
+
+fn open_current_exe_with_trailer() -> Result<(std::fs::File, Trailer), AnyError>
+{
+  open_with_trailer(&current_exe().unwrap())
+}
+
+/// Reads and decodes the data between the two positions of the file.
+fn read_section(
+  file: &mut std::fs::File,
+  start: u64,
+  end: u64,
+  encoding: &PayloadEncoding,
+) -> Result<Vec<u8>, AnyError> {
+  file.seek(SeekFrom::Start(start))?;
+  let mut data = vec![0; (end - start) as usize];
+  file.read_exact(&mut data)?;
+  encoding.decode(data)
+}
+
+pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
+  let (mut file, trailer) = open_current_exe_with_trailer()?;
+  let encoding = trailer.encoding();
+  let vfs_data = read_section(
+    &mut file,
+    trailer.npm_vfs_pos,
+    trailer.npm_files_pos,
+    &encoding,
+  )?;
   let mut dir: VirtualDirectory = serde_json::from_slice(&vfs_data)?;
 
   // align the name of the directory with the root dir
@@ -177,9 +241,48 @@ pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
     root_path: root_dir_path,
     start_file_offset: trailer.npm_files_pos,
   };
-  Ok(FileBackedVfs::new(file, fs_root))
+  Ok(FileBackedVfs::new_encoded(file, fs_root, encoding))
 }
 
+/// Loads the directories embedded with `--include-dir`.
+pub fn load_assets_vfs() -> Result<Vec<FileBackedVfs>, AnyError> {
+  let (mut file, trailer) = open_current_exe_with_trailer()?;
+  let encoding = trailer.encoding();
+  let vfs_data = read_section(
+    &mut file,
+    trailer.assets_vfs_pos,
+    trailer.assets_files_pos,
+    &encoding,
+  )?;
+  let dirs: Vec<SerializedAssetsDir> = serde_json::from_slice(&vfs_data)?;
+  let mut result = Vec::with_capacity(dirs.len());
+  for SerializedAssetsDir {
+    root_path,
+    mut dir,
+    files_offset,
+  } in dirs
+  {
+    // align the name of the directory with the root dir
+    dir.name = root_path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    // each vfs seeks independently, so give it its own file handle
+    let file = std::fs::File::open(current_exe().unwrap())?;
+    result.push(FileBackedVfs::new_encoded(
+      file,
+      VfsRoot {
+        dir,
+        root_path,
+        start_file_offset: trailer.assets_files_pos + files_offset,
+      },
+      encoding,
+    ));
+  }
+  Ok(result)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn write_binary_bytes(
   writer: &mut impl Write,
   original_bin: Vec<u8>,
@@ -187,18 +290,24 @@ fn write_binary_bytes(
   eszip: eszip::EszipV2,
   npm_vfs: Option<&VirtualDirectory>,
   npm_files: &Vec<Vec<u8>>,
+  assets: &EmbeddedAssets,
+  encoding: PayloadEncoding,
 ) -> Result<(), AnyError> {
-  let metadata = serde_json::to_string(metadata)?.as_bytes().to_vec();
-  let npm_vfs = serde_json::to_string(&npm_vfs)?.as_bytes().to_vec();
-  let eszip_archive = eszip.into_bytes();
+  let metadata =
+    encoding.encode(serde_json::to_string(metadata)?.as_bytes().to_vec())?;
+  let npm_vfs =
+    encoding.encode(serde_json::to_string(&npm_vfs)?.as_bytes().to_vec())?;
+  let assets_vfs = encoding
+    .encode(serde_json::to_string(&assets.dirs)?.as_bytes().to_vec())?;
+  let eszip_archive = encoding.encode(eszip.into_bytes())?;
 
   writer.write_all(&original_bin)?;
   writer.write_all(&eszip_archive)?;
   writer.write_all(&metadata)?;
   writer.write_all(&npm_vfs)?;
-  for file in npm_files {
-    writer.write_all(file)?;
-  }
+  let npm_files_len = write_files(writer, npm_files)?;
+  writer.write_all(&assets_vfs)?;
+  write_files(writer, &assets.files)?;
 
   // write the trailer, which includes the positions
   // of the data blocks in the file
@@ -207,11 +316,17 @@ fn write_binary_bytes(
     let metadata_pos = eszip_pos + (eszip_archive.len() as u64);
     let npm_vfs_pos = metadata_pos + (metadata.len() as u64);
     let npm_files_pos = npm_vfs_pos + (npm_vfs.len() as u64);
+    let assets_vfs_pos = npm_files_pos + npm_files_len;
+    let assets_files_pos = assets_vfs_pos + (assets_vfs.len() as u64);
     Trailer {
       eszip_pos,
       metadata_pos,
       npm_vfs_pos,
       npm_files_pos,
+      assets_vfs_pos,
+      assets_files_pos,
+      payload_flags: encoding.flags(),
+      payload_key: encoding.key(),
     }
     .as_bytes()
   })?;
@@ -219,50 +334,151 @@ fn write_binary_bytes(
   Ok(())
 }
 
+/// Writes the files of a virtual file system, which were already encoded
+/// one by one, returning the number of bytes written.
+fn write_files(
+  writer: &mut impl Write,
+  files: &[Vec<u8>],
+) -> Result<u64, AnyError> {
+  for file in files {
+    writer.write_all(file)?;
+  }
+  Ok(files.iter().map(|f| f.len() as u64).sum())
+}
+
 pub fn is_standalone_binary(exe_path: &Path) -> bool {
   let Ok(mut output_file) = std::fs::File::open(exe_path) else {
     return false;
   };
-  if output_file
-    .seek(SeekFrom::End(-(TRAILER_SIZE as i64)))
-    .is_err()
-  {
+  has_magic_trailer(&mut output_file, TRAILER_SIZE, MAGIC_TRAILER)
+    || has_magic_trailer(
+      &mut output_file,
+      LEGACY_TRAILER_SIZE,
+      LEGACY_MAGIC_TRAILER,
+    )
+}
+
+fn has_magic_trailer(
+  file: &mut std::fs::File,
+  trailer_size: usize,
+  magic_trailer: &[u8; 8],
+) -> bool {
+  if file.seek(SeekFrom::End(-(trailer_size as i64))).is_err() {
     // This seek may fail because the file is too small to possibly be
     // `deno compile` output.
     return false;
   }
-  let mut trailer = [0; TRAILER_SIZE];
-  if output_file.read_exact(&mut trailer).is_err() {
+  let mut magic = [0; 8];
+  if file.read_exact(&mut magic).is_err() {
     return false;
   };
-  let (magic_trailer, _) = trailer.split_at(8);
-  magic_trailer == MAGIC_TRAILER
+  &magic == magic_trailer
 }
 
 /// This function will try to run this binary as a standalone binary
 /// produced by `deno compile`. It determines if this is a standalone
 /// binary by skipping over the trailer width at the end of the file,
-/// then checking for the magic trailer string `d3n0l4nd`. If found,
+/// then checking for the magic trailer string `d3n0l4nv`. If found,
 /// the bundle is executed. If not, this function exits with `Ok(None)`.
 pub async fn extract_standalone(
   exe_path: &Path,
   cli_args: Vec<String>,
 ) -> Result<Option<(Metadata, eszip::EszipV2)>, AnyError> {
+  let Some((mut metadata, eszip, _)) = read_standalone(exe_path).await? else {
+    return Ok(None);
+  };
+  metadata.argv.append(&mut cli_args[1..].to_vec());
+  Ok(Some((metadata, eszip)))
+}
+
+/// The data embedded in a standalone binary, which is used to inspect it.
+pub struct StandaloneBinaryContents {
+  pub metadata: Metadata,
+  pub eszip: eszip::EszipV2,
+  pub npm_vfs: Option<VirtualDirectory>,
+  /// The directories embedded with `--include-dir` and their paths.
+  pub assets: Vec<(PathBuf, VirtualDirectory)>,
+  pub compressed: bool,
+  pub obfuscated: bool,
+}
+
+/// Reads the data embedded in the standalone binary at the provided path,
+/// returning `Ok(None)` when it isn't a standalone binary.
+pub async fn read_standalone_binary_contents(
+  exe_path: &Path,
+) -> Result<Option<StandaloneBinaryContents>, AnyError> {
+  let Some((metadata, eszip, trailer)) = read_standalone(exe_path).await?
+  else {
+    return Ok(None);
+  };
+  let (mut file, _) = open_with_trailer(exe_path)?;
+  let encoding = trailer.encoding();
+  let npm_vfs = read_section(
+    &mut file,
+    trailer.npm_vfs_pos,
+    trailer.npm_files_pos,
+    &encoding,
+  )?;
+  let npm_vfs: Option<VirtualDirectory> = serde_json::from_slice(&npm_vfs)?;
+  let assets = read_section(
+    &mut file,
+    trailer.assets_vfs_pos,
+    trailer.assets_files_pos,
+    &encoding,
+  )?;
+  let assets: Vec<SerializedAssetsDir> = serde_json::from_slice(&assets)?;
+  Ok(Some(StandaloneBinaryContents {
+    metadata,
+    eszip,
+    npm_vfs,
+    assets: assets
+      .into_iter()
+      .map(|assets_dir| (assets_dir.root_path, assets_dir.dir))
+      .collect(),
+    compressed: encoding.is_compressed(),
+    obfuscated: encoding.is_obfuscated(),
+  }))
+}
+
+async fn read_standalone(
+  exe_path: &Path,
+) -> Result<Option<(Metadata, eszip::EszipV2, Trailer)>, AnyError> {
   let file = std::fs::File::open(exe_path)?;
 
   let mut bufreader =
     deno_core::futures::io::BufReader::new(AllowStdIo::new(file));
 
-  let _trailer_pos = bufreader
+  let trailer_pos = bufreader
     .seek(SeekFrom::End(-(TRAILER_SIZE as i64)))
     .await?;
   let mut trailer = [0; TRAILER_SIZE];
   bufreader.read_exact(&mut trailer).await?;
-  let trailer = match Trailer::parse(&trailer)? {
-    None => return Ok(None),
+  let trailer = match Trailer::parse(&trailer, trailer_pos)? {
+    None => {
+      let mut file = bufreader.into_inner().into_inner();
+      if has_magic_trailer(&mut file, LEGACY_TRAILER_SIZE, LEGACY_MAGIC_TRAILER)
+      {
+        bail!(
+          "'{}' was compiled with an older version of Deno. Please compile it again.",
+          exe_path.display()
+        );
+      }
+      return Ok(None);
+    }
     Some(trailer) => trailer,
   };
 
+  let encoding = trailer.encoding();
+  if !encoding.is_identity() {
+    let (metadata, eszip) = read_encoded_standalone(
+      bufreader.into_inner().into_inner(),
+      &trailer,
+      &encoding,
+    )
+    .await?;
+    return Ok(Some((metadata, eszip, trailer)));
+  }
+
   bufreader.seek(SeekFrom::Start(trailer.eszip_pos)).await?;
 
   let (eszip, loader) = eszip::EszipV2::parse(bufreader)
@@ -283,40 +499,113 @@ pub async fn extract_standalone(
     .await
     .context("Failed to read metadata from the current executable")?;
 
-  let mut metadata: Metadata = serde_json::from_str(&metadata).unwrap();
-  metadata.argv.append(&mut cli_args[1..].to_vec());
+  let metadata: Metadata = serde_json::from_str(&metadata).unwrap();
 
-  Ok(Some((metadata, eszip)))
+  Ok(Some((metadata, eszip, trailer)))
 }
 
-const TRAILER_SIZE: usize = std::mem::size_of::<Trailer>() + 8; // 8 bytes for the magic trailer string
+/// Reads the eszip and metadata of a binary which was compiled with
+/// `--compress` or `--obfuscate`.
+async fn read_encoded_standalone(
+  mut file: std::fs::File,
+  trailer: &Trailer,
+  encoding: &PayloadEncoding,
+) -> Result<(Metadata, eszip::EszipV2), AnyError> {
+  let eszip_data =
+    read_section(&mut file, trailer.eszip_pos, trailer.metadata_pos, encoding)
+      .context("Failed to read eszip archive from the current executable")?;
+  let (eszip, loader) =
+    eszip::EszipV2::parse(deno_core::futures::io::BufReader::new(
+      AllowStdIo::new(Cursor::new(eszip_data)),
+    ))
+    .await
+    .context("Failed to parse eszip header")?;
+  loader.await.context("Failed to parse eszip archive")?;
+
+  let metadata = read_section(
+    &mut file,
+    trailer.metadata_pos,
+    trailer.npm_vfs_pos,
+    encoding,
+  )
+  .context("Failed to read metadata from the current executable")?;
+  let metadata: Metadata = serde_json::from_slice(&metadata)?;
+
+  Ok((metadata, eszip))
+}
+
+const TRAILER_SIZE: usize = std::mem::size_of::<Trailer>() + 16; // 8 bytes for the magic trailer string and 8 for the version
 
 struct Trailer {
   eszip_pos: u64,
   metadata_pos: u64,
   npm_vfs_pos: u64,
   npm_files_pos: u64,
+  assets_vfs_pos: u64,
+  assets_files_pos: u64,
+  payload_flags: u64,
+  payload_key: [u8; KEY_LEN],
 }
 
 impl Trailer {
-  pub fn parse(trailer: &[u8]) -> Result<Option<Trailer>, AnyError> {
+  pub fn parse(
+    trailer: &[u8],
+    trailer_pos: u64,
+  ) -> Result<Option<Trailer>, AnyError> {
     let (magic_trailer, rest) = trailer.split_at(8);
     if magic_trailer != MAGIC_TRAILER {
       return Ok(None);
     }
+    let (version, rest) = rest.split_at(8);
+    let version = u64_from_bytes(version)?;
+    if version != TRAILER_VERSION {
+      bail!(
+        "Unsupported standalone binary version {} (expected {}). Please compile it again with this version of Deno.",
+        version,
+        TRAILER_VERSION
+      );
+    }
 
     let (eszip_archive_pos, rest) = rest.split_at(8);
     let (metadata_pos, rest) = rest.split_at(8);
-    let (npm_vfs_pos, npm_files_pos) = rest.split_at(8);
+    let (npm_vfs_pos, rest) = rest.split_at(8);
+    let (npm_files_pos, rest) = rest.split_at(8);
+    let (assets_vfs_pos, rest) = rest.split_at(8);
+    let (assets_files_pos, rest) = rest.split_at(8);
+    let (payload_flags, payload_key) = rest.split_at(8);
     let eszip_archive_pos = u64_from_bytes(eszip_archive_pos)?;
     let metadata_pos = u64_from_bytes(metadata_pos)?;
     let npm_vfs_pos = u64_from_bytes(npm_vfs_pos)?;
     let npm_files_pos = u64_from_bytes(npm_files_pos)?;
+    let assets_vfs_pos = u64_from_bytes(assets_vfs_pos)?;
+    let assets_files_pos = u64_from_bytes(assets_files_pos)?;
+    let payload_flags = u64_from_bytes(payload_flags)?;
+    let payload_key = payload_key
+      .try_into()
+      .context("Failed to convert the buffer into a fixed-size array")?;
+    // the sections are read into memory by their lengths, so make sure they
+    // are in order and within the file before allocating anything
+    let positions = [
+      eszip_archive_pos,
+      metadata_pos,
+      npm_vfs_pos,
+      npm_files_pos,
+      assets_vfs_pos,
+      assets_files_pos,
+      trailer_pos,
+    ];
+    if positions.windows(2).any(|pair| pair[0] > pair[1]) {
+      bail!("Invalid standalone binary: the trailer is corrupt.");
+    }
     Ok(Some(Trailer {
       eszip_pos: eszip_archive_pos,
       metadata_pos,
       npm_vfs_pos,
       npm_files_pos,
+      assets_vfs_pos,
+      assets_files_pos,
+      payload_flags,
+      payload_key,
     }))
   }
 
@@ -324,12 +613,13 @@ impl Trailer {
     self.npm_vfs_pos - self.metadata_pos
   }
 
-  pub fn npm_vfs_len(&self) -> u64 {
-    self.npm_files_pos - self.npm_vfs_pos
+  pub fn encoding(&self) -> PayloadEncoding {
+    PayloadEncoding::from_parts(self.payload_flags, self.payload_key)
   }
 
   pub fn as_bytes(&self) -> Vec<u8> {
     let mut trailer = MAGIC_TRAILER.to_vec();
+    trailer.write_all(&TRAILER_VERSION.to_be_bytes()).unwrap();
     trailer.write_all(&self.eszip_pos.to_be_bytes()).unwrap();
     trailer.write_all(&self.metadata_pos.to_be_bytes()).unwrap();
     trailer.write_all(&self.npm_vfs_pos.to_be_bytes()).unwrap();
@@ -337,6 +627,16 @@ impl Trailer {
       .write_all(&self.npm_files_pos.to_be_bytes())
       .unwrap();
     trailer
+      .write_all(&self.assets_vfs_pos.to_be_bytes())
+      .unwrap();
+    trailer
+      .write_all(&self.assets_files_pos.to_be_bytes())
+      .unwrap();
+    trailer
+      .write_all(&self.payload_flags.to_be_bytes())
+      .unwrap();
+    trailer.write_all(&self.payload_key).unwrap();
+    trailer
   }
 }
 
@@ -502,13 +802,16 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       .resolve_import_map(self.file_fetcher)
       .await?
       .map(|import_map| (import_map.base_url().clone(), import_map.to_json()));
+    let encoding =
+      PayloadEncoding::new(compile_flags.compress, compile_flags.obfuscate)?;
     let (npm_vfs, npm_files, node_modules) =
       match self.npm_resolver.as_inner() {
         InnerCliNpmResolverRef::Managed(managed) => {
           let snapshot =
             managed.serialized_valid_snapshot_for_system(&self.npm_system_info);
           if !snapshot.as_serialized().packages.is_empty() {
-            let (root_dir, files) = self.build_vfs()?.into_dir_and_files();
+            let (root_dir, files) =
+              self.build_vfs()?.into_encoded_dir_and_files(&encoding)?;
             eszip.add_npm_snapshot(snapshot);
             (
               Some(root_dir),
@@ -528,7 +831,8 @@ impl<'a> DenoCompileBinaryWriter<'a> {
           }
         }
         InnerCliNpmResolverRef::Byonm(_) => {
-          let (root_dir, files) = self.build_vfs()?.into_dir_and_files();
+          let (root_dir, files) =
+            self.build_vfs()?.into_encoded_dir_and_files(&encoding)?;
           (
             Some(root_dir),
             files,
@@ -541,7 +845,14 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         }
       };
 
+    let assets = build_assets(
+      &compile_flags.include_dir,
+      cli_options.initial_cwd(),
+      &encoding,
+    )?;
+
     let metadata = Metadata {
+      deno_version: crate::version::deno().to_string(),
       argv: compile_flags.args.clone(),
       seed: cli_options.seed(),
       location: cli_options.location_flag().clone(),
@@ -574,6 +885,8 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       eszip,
       npm_vfs.as_ref(),
       &npm_files,
+      &assets,
+      encoding,
     )
   }
 
@@ -628,6 +941,37 @@ impl<'a> DenoCompileBinaryWriter<'a> {
   }
 }
 
+/// Builds the virtual file systems of the directories to embed with
+/// `--include-dir`.
+fn build_assets(
+  include_dirs: &[String],
+  cwd: &Path,
+  encoding: &PayloadEncoding,
+) -> Result<EmbeddedAssets, AnyError> {
+  let mut assets = EmbeddedAssets::default();
+  let mut files_offset = 0;
+  for include_dir in include_dirs {
+    let root_path = normalize_path(cwd.join(include_dir));
+    if !root_path.is_dir() {
+      bail!(
+        "Failed to include directory '{}' because it is not a directory.",
+        root_path.display()
+      );
+    }
+    let mut builder = VfsBuilder::new(root_path.clone())?;
+    builder.add_dir_recursive(&root_path)?;
+    let (dir, files) = builder.into_encoded_dir_and_files(encoding)?;
+    assets.dirs.push(SerializedAssetsDir {
+      root_path,
+      dir,
+      files_offset,
+    });
+    files_offset += files.iter().map(|f| f.len() as u64).sum::<u64>();
+    assets.files.extend(files);
+  }
+  Ok(assets)
+}
+
 /// This function sets the subsystem field in the PE header to 2 (GUI subsystem)
 /// For more information about the PE header: https://learn.microsoft.com/en-us/windows/win32/debug/pe-format
 fn set_windows_binary_to_gui(bin: &mut [u8]) -> Result<(), AnyError> {
@@ -666,3 +1010,53 @@ fn set_windows_binary_to_gui(bin: &mut [u8]) -> Result<(), AnyError> {
     .copy_from_slice(&subsystem.to_le_bytes());
   Ok(())
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn trailer(positions: [u64; 6]) -> Trailer {
+    Trailer {
+      eszip_pos: positions[0],
+      metadata_pos: positions[1],
+      npm_vfs_pos: positions[2],
+      npm_files_pos: positions[3],
+      assets_vfs_pos: positions[4],
+      assets_files_pos: positions[5],
+      payload_flags: 0,
+      payload_key: [0; KEY_LEN],
+    }
+  }
+
+  #[test]
+  fn parses_trailer() {
+    let bytes = trailer([10, 20, 30, 40, 50, 60]).as_bytes();
+    assert_eq!(bytes.len(), TRAILER_SIZE);
+    let parsed = Trailer::parse(&bytes, 70).unwrap().unwrap();
+    assert_eq!(parsed.eszip_pos, 10);
+    assert_eq!(parsed.assets_files_pos, 60);
+    assert_eq!(parsed.metadata_len(), 10);
+
+    let mut legacy = bytes.clone();
+    legacy[..8].copy_from_slice(LEGACY_MAGIC_TRAILER);
+    assert!(Trailer::parse(&legacy, 70).unwrap().is_none());
+  }
+
+  #[test]
+  fn errors_for_corrupt_trailer() {
+    // a section ending before it starts
+    let bytes = trailer([10, 30, 20, 40, 50, 60]).as_bytes();
+    assert!(Trailer::parse(&bytes, 70).is_err());
+    // a section ending after the trailer starts
+    let bytes = trailer([10, 20, 30, 40, 50, 60]).as_bytes();
+    assert!(Trailer::parse(&bytes, 55).is_err());
+  }
+
+  #[test]
+  fn errors_for_other_trailer_version() {
+    let mut bytes = trailer([10, 20, 30, 40, 50, 60]).as_bytes();
+    bytes[8..16].copy_from_slice(&(TRAILER_VERSION + 1).to_be_bytes());
+    let err = Trailer::parse(&bytes, 70).err().unwrap();
+    assert!(err.to_string().starts_with("Unsupported standalone binary"));
+  }
+}
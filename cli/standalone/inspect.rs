@@ -0,0 +1,245 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Inspects the contents of an executable created by `deno compile`, which
+//! `deno info` uses to show what's embedded in it.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::error::AnyError;
+use deno_core::url::Url;
+use deno_npm::NpmPackageId;
+use deno_runtime::permissions::PermissionsOptions;
+use serde::Serialize;
+
+use crate::args::UnstableConfig;
+
+use super::binary::read_standalone_binary_contents;
+use super::binary::NodeModules;
+use super::virtual_fs::VfsEntry;
+use super::virtual_fs::VirtualDirectory;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandaloneBinaryInfo {
+  pub deno_version: String,
+  pub entrypoint: ModuleSpecifier,
+  pub argv: Vec<String>,
+  pub permissions: PermissionsOptions,
+  pub v8_flags: Vec<String>,
+  pub location: Option<Url>,
+  pub seed: Option<u64>,
+  pub unstable_config: UnstableConfig,
+  pub compressed: bool,
+  pub obfuscated: bool,
+  pub modules: Vec<EmbeddedModule>,
+  pub npm_packages: Vec<EmbeddedNpmPackage>,
+  /// The size of all the files of the embedded npm packages.
+  pub npm_size: u64,
+  pub included_dirs: Vec<EmbeddedDir>,
+}
+
+#[derive(Serialize)]
+pub struct EmbeddedModule {
+  pub specifier: String,
+  pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct EmbeddedNpmPackage {
+  pub id: String,
+  /// The size of the package's files, when they could be found.
+  pub size: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct EmbeddedDir {
+  pub path: PathBuf,
+  pub size: u64,
+}
+
+/// Gets what's embedded in the standalone binary at the provided path,
+/// optionally writing its modules to `maybe_extract_dir`. Returns `Ok(None)`
+/// when the file isn't a standalone binary.
+pub async fn inspect_standalone_binary(
+  exe_path: &Path,
+  maybe_extract_dir: Option<&Path>,
+) -> Result<Option<StandaloneBinaryInfo>, AnyError> {
+  let Some(mut contents) = read_standalone_binary_contents(exe_path).await?
+  else {
+    return Ok(None);
+  };
+
+  let mut modules = Vec::new();
+  for specifier in contents.eszip.specifiers() {
+    let Some(module) = contents.eszip.get_module(&specifier) else {
+      continue;
+    };
+    if module.specifier != specifier {
+      continue; // redirect
+    }
+    let Some(source) = module.source().await else {
+      continue;
+    };
+    if let Some(extract_dir) = maybe_extract_dir {
+      if let Ok(specifier) = ModuleSpecifier::parse(&specifier) {
+        if let Some(path) = extract_path(extract_dir, &specifier) {
+          std::fs::create_dir_all(path.parent().unwrap())?;
+          std::fs::write(&path, &source)?;
+        }
+      }
+    }
+    modules.push(EmbeddedModule {
+      specifier,
+      size: source.len() as u64,
+    });
+  }
+
+  let node_modules_dir = matches!(
+    contents.metadata.node_modules,
+    Some(NodeModules::Managed {
+      node_modules_dir: true,
+      ..
+    })
+  );
+  let npm_packages = contents
+    .eszip
+    .take_npm_snapshot()
+    .map(|snapshot| {
+      snapshot
+        .as_serialized()
+        .packages
+        .iter()
+        .map(|package| EmbeddedNpmPackage {
+          id: package.id.as_serialized(),
+          size: contents.npm_vfs.as_ref().and_then(|vfs| {
+            npm_package_dir(vfs, &package.id, node_modules_dir).map(dir_size)
+          }),
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  let metadata = contents.metadata;
+  Ok(Some(StandaloneBinaryInfo {
+    deno_version: metadata.deno_version,
+    entrypoint: metadata.entrypoint,
+    argv: metadata.argv,
+    permissions: metadata.permissions,
+    v8_flags: metadata.v8_flags,
+    location: metadata.location,
+    seed: metadata.seed,
+    unstable_config: metadata.unstable_config,
+    compressed: contents.compressed,
+    obfuscated: contents.obfuscated,
+    modules,
+    npm_packages,
+    npm_size: contents.npm_vfs.as_ref().map(dir_size).unwrap_or(0),
+    included_dirs: contents
+      .assets
+      .iter()
+      .map(|(path, dir)| EmbeddedDir {
+        path: path.clone(),
+        size: dir_size(dir),
+      })
+      .collect(),
+  }))
+}
+
+/// Gets the path a module is extracted to, which is made up of the
+/// specifier's scheme, host and path.
+fn extract_path(
+  extract_dir: &Path,
+  specifier: &ModuleSpecifier,
+) -> Option<PathBuf> {
+  let mut path = extract_dir.join(specifier.scheme());
+  if let Some(host) = specifier.host_str() {
+    match specifier.port() {
+      Some(port) => path.push(format!("{host}_{port}")),
+      None => path.push(host),
+    }
+  }
+  for segment in specifier.path_segments()? {
+    if !segment.is_empty() {
+      // drive letters aren't valid in the middle of a path on windows
+      path.push(segment.replace(':', "_"));
+    }
+  }
+  Some(path)
+}
+
+fn npm_package_dir<'a>(
+  vfs: &'a VirtualDirectory,
+  id: &NpmPackageId,
+  node_modules_dir: bool,
+) -> Option<&'a VirtualDirectory> {
+  let name_parts = id.nv.name.split('/').collect::<Vec<_>>();
+  if node_modules_dir {
+    let folder_name =
+      format!("{}@{}", id.nv.name, id.nv.version).replace('/', "+");
+    let package_dir = find_dir(vfs, &[".deno", &folder_name, "node_modules"])?;
+    find_dir(package_dir, &name_parts)
+  } else {
+    let version = id.nv.version.to_string();
+    let name_dir = find_dir(vfs, &name_parts)?;
+    find_dir(name_dir, &[&version])
+  }
+}
+
+fn find_dir<'a>(
+  dir: &'a VirtualDirectory,
+  names: &[&str],
+) -> Option<&'a VirtualDirectory> {
+  let mut current = dir;
+  for name in names {
+    current = current.entries.iter().find_map(|entry| match entry {
+      VfsEntry::Dir(dir) if dir.name == *name => Some(dir),
+      _ => None,
+    })?;
+  }
+  Some(current)
+}
+
+fn dir_size(dir: &VirtualDirectory) -> u64 {
+  dir
+    .entries
+    .iter()
+    .map(|entry| match entry {
+      VfsEntry::Dir(dir) => dir_size(dir),
+      VfsEntry::File(file) => file.len,
+      VfsEntry::Symlink(_) => 0,
+    })
+    .sum()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_extract_path() {
+    let dir = PathBuf::from("out");
+    let cases = [
+      ("file:///a/b/main.ts", Some("out/file/a/b/main.ts")),
+      ("file:///C:/a/main.ts", Some("out/file/C_/a/main.ts")),
+      (
+        "https://deno.land/std/path/mod.ts",
+        Some("out/https/deno.land/std/path/mod.ts"),
+      ),
+      (
+        "http://localhost:4545/mod.ts",
+        Some("out/http/localhost_4545/mod.ts"),
+      ),
+      ("data:text/javascript,1", None),
+    ];
+    for (specifier, expected) in cases {
+      let specifier = ModuleSpecifier::parse(specifier).unwrap();
+      assert_eq!(
+        extract_path(&dir, &specifier),
+        expected.map(|p| p.split('/').collect::<PathBuf>()),
+        "{specifier}"
+      );
+    }
+  }
+}
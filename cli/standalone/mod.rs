@@ -59,15 +59,21 @@ use std::sync::Arc;
 
 mod binary;
 mod file_system;
+mod inspect;
+mod payload;
 mod virtual_fs;
 
 pub use binary::extract_standalone;
 pub use binary::is_standalone_binary;
 pub use binary::DenoCompileBinaryWriter;
+pub use inspect::inspect_standalone_binary;
+pub use inspect::StandaloneBinaryInfo;
 
+use self::binary::load_assets_vfs;
 use self::binary::load_npm_vfs;
 use self::binary::Metadata;
 use self::file_system::DenoCompileFileSystem;
+use self::virtual_fs::FileBackedVfs;
 
 struct SharedModuleLoaderState {
   eszip: eszip::EszipV2,
@@ -201,6 +207,10 @@ impl ModuleLoader for EmbeddedModuleLoader {
     let found_specifier =
       ModuleSpecifier::parse(&module.specifier).expect("invalid url in eszip");
 
+    // TODO: embed a V8 code cache of the modules so that they don't need to
+    // be compiled on every start. This isn't possible yet because deno_core
+    // always compiles modules from source and has no way of providing it
+    // cached data to consume or of getting the cache of a compiled module.
     async move {
       let code = module.source().await.ok_or_else(|| {
         type_error(format!("Module not found: {}", original_specifier))
@@ -316,7 +326,20 @@ pub async fn run(
   let npm_cache_dir = NpmCacheDir::new(root_path.clone());
   let npm_global_cache_dir = npm_cache_dir.get_cache_location();
   let cache_setting = CacheSetting::Only;
-  let (package_json_deps_provider, fs, npm_resolver, maybe_vfs_root) =
+  let assets_vfs = load_assets_vfs().context("Failed to load assets vfs.")?;
+  let mut vfs_roots = assets_vfs
+    .iter()
+    .map(|vfs| vfs.root().to_path_buf())
+    .collect::<Vec<_>>();
+  let create_fs = move |npm_vfs: Option<FileBackedVfs>| {
+    let vfs = npm_vfs.into_iter().chain(assets_vfs).collect::<Vec<_>>();
+    if vfs.is_empty() {
+      Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>
+    } else {
+      Arc::new(DenoCompileFileSystem::new(vfs)) as Arc<dyn deno_fs::FileSystem>
+    }
+  };
+  let (package_json_deps_provider, fs, npm_resolver, maybe_npm_vfs_root) =
     match metadata.node_modules {
       Some(binary::NodeModules::Managed {
         node_modules_dir,
@@ -340,8 +363,7 @@ pub async fn run(
           Arc::new(PackageJsonDepsProvider::new(
             package_json_deps.map(|serialized| serialized.into_deps()),
           ));
-        let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-          as Arc<dyn deno_fs::FileSystem>;
+        let fs = create_fs(Some(vfs));
         let npm_resolver = create_cli_npm_resolver(
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
             snapshot: CliNpmResolverManagedSnapshotOption::Specified(Some(snapshot)),
@@ -377,8 +399,7 @@ pub async fn run(
           Arc::new(PackageJsonDepsProvider::new(
             package_json_deps.map(|serialized| serialized.into_deps()),
           ));
-        let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-          as Arc<dyn deno_fs::FileSystem>;
+        let fs = create_fs(Some(vfs));
         let npm_resolver =
           create_cli_npm_resolver(CliNpmResolverCreateOptions::Byonm(
             CliNpmResolverByonmCreateOptions {
@@ -397,7 +418,7 @@ pub async fn run(
       None => {
         let package_json_deps_provider =
           Arc::new(PackageJsonDepsProvider::new(None));
-        let fs = Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>;
+        let fs = create_fs(None);
         let npm_resolver = create_cli_npm_resolver(
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
             snapshot: CliNpmResolverManagedSnapshotOption::Specified(None),
@@ -464,17 +485,18 @@ pub async fn run(
 
   let permissions = {
     let mut permissions = metadata.permissions;
-    // if running with an npm vfs, grant read access to it
-    if let Some(vfs_root) = maybe_vfs_root {
+    // if running with an npm vfs or embedded assets, grant read access to them
+    vfs_roots.extend(maybe_npm_vfs_root);
+    if !vfs_roots.is_empty() {
       match &mut permissions.allow_read {
         Some(vec) if vec.is_empty() => {
           // do nothing, already granted
         }
         Some(vec) => {
-          vec.push(vfs_root);
+          vec.extend(vfs_roots);
         }
         None => {
-          permissions.allow_read = Some(vec![vfs_root]);
+          permissions.allow_read = Some(vfs_roots);
         }
       }
     }
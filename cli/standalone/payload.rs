@@ -0,0 +1,177 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Encoding of the data embedded in a standalone binary, which may be
+//! compressed with `--compress` and obfuscated with `--obfuscate`.
+//!
+//! Obfuscation uses AES-256-GCM with a random key which is stored in the
+//! binary next to the data, so it only keeps the sources from being stored
+//! as plaintext and is not a security measure.
+
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use ring::aead::Aad;
+use ring::aead::LessSafeKey;
+use ring::aead::Nonce;
+use ring::aead::UnboundKey;
+use ring::aead::AES_256_GCM;
+use ring::aead::NONCE_LEN;
+use ring::rand::SecureRandom;
+use ring::rand::SystemRandom;
+
+const COMPRESSED_FLAG: u64 = 1;
+const OBFUSCATED_FLAG: u64 = 1 << 1;
+
+/// The level is favored towards size, since the speed of decompression
+/// doesn't depend on it.
+const COMPRESSION_LEVEL: i32 = 19;
+
+pub const KEY_LEN: usize = 32;
+
+#[derive(Clone, Copy, Default)]
+pub struct PayloadEncoding {
+  compress: bool,
+  key: Option<[u8; KEY_LEN]>,
+}
+
+impl PayloadEncoding {
+  /// Creates the encoding used when compiling, which generates a random key
+  /// when obfuscating.
+  pub fn new(compress: bool, obfuscate: bool) -> Result<Self, AnyError> {
+    let key = if obfuscate {
+      let mut key = [0; KEY_LEN];
+      SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| anyhow!("Failed to generate an obfuscation key."))?;
+      Some(key)
+    } else {
+      None
+    };
+    Ok(Self { compress, key })
+  }
+
+  /// Creates the encoding from the values stored in the trailer.
+  pub fn from_parts(flags: u64, key: [u8; KEY_LEN]) -> Self {
+    Self {
+      compress: flags & COMPRESSED_FLAG != 0,
+      key: (flags & OBFUSCATED_FLAG != 0).then_some(key),
+    }
+  }
+
+  pub fn flags(&self) -> u64 {
+    let mut flags = 0;
+    if self.compress {
+      flags |= COMPRESSED_FLAG;
+    }
+    if self.key.is_some() {
+      flags |= OBFUSCATED_FLAG;
+    }
+    flags
+  }
+
+  pub fn key(&self) -> [u8; KEY_LEN] {
+    self.key.unwrap_or_default()
+  }
+
+  pub fn is_compressed(&self) -> bool {
+    self.compress
+  }
+
+  pub fn is_obfuscated(&self) -> bool {
+    self.key.is_some()
+  }
+
+  /// Whether the data is stored as is.
+  pub fn is_identity(&self) -> bool {
+    !self.compress && self.key.is_none()
+  }
+
+  pub fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, AnyError> {
+    let mut data = if self.compress {
+      zstd::bulk::compress(&data, COMPRESSION_LEVEL)?
+    } else {
+      data
+    };
+    if let Some(key) = &self.key {
+      let mut nonce = [0; NONCE_LEN];
+      SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| anyhow!("Failed to generate a nonce."))?;
+      less_safe_key(key)
+        .seal_in_place_append_tag(
+          Nonce::assume_unique_for_key(nonce),
+          Aad::empty(),
+          &mut data,
+        )
+        .map_err(|_| anyhow!("Failed to obfuscate the embedded data."))?;
+      let mut output = nonce.to_vec();
+      output.extend(data);
+      data = output;
+    }
+    Ok(data)
+  }
+
+  pub fn decode(&self, mut data: Vec<u8>) -> Result<Vec<u8>, AnyError> {
+    if let Some(key) = &self.key {
+      if data.len() < NONCE_LEN {
+        bail!("Failed to read the obfuscated embedded data.");
+      }
+      let mut ciphertext = data.split_off(NONCE_LEN);
+      let nonce = Nonce::try_assume_unique_for_key(&data)
+        .map_err(|_| anyhow!("Failed to read the obfuscated embedded data."))?;
+      let plaintext_len = less_safe_key(key)
+        .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+        .map_err(|_| anyhow!("Failed to read the obfuscated embedded data."))?
+        .len();
+      ciphertext.truncate(plaintext_len);
+      data = ciphertext;
+    }
+    if self.compress {
+      data = zstd::stream::decode_all(data.as_slice())?;
+    }
+    Ok(data)
+  }
+}
+
+fn less_safe_key(key: &[u8; KEY_LEN]) -> LessSafeKey {
+  LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).unwrap())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn encodes_and_decodes() {
+    let data = b"console.log('hello world');".repeat(10);
+    for (compress, obfuscate) in
+      [(false, false), (true, false), (false, true), (true, true)]
+    {
+      let encoding = PayloadEncoding::new(compress, obfuscate).unwrap();
+      let encoded = encoding.encode(data.clone()).unwrap();
+      if !encoding.is_identity() {
+        assert_ne!(encoded, data);
+      }
+      let encoding =
+        PayloadEncoding::from_parts(encoding.flags(), encoding.key());
+      assert_eq!(encoding.decode(encoded).unwrap(), data);
+    }
+  }
+
+  #[test]
+  fn decoding_with_wrong_key_fails() {
+    let encoded = PayloadEncoding::new(false, true)
+      .unwrap()
+      .encode(b"data".to_vec())
+      .unwrap();
+    // every build generates a new key
+    let err = PayloadEncoding::new(false, true)
+      .unwrap()
+      .decode(encoded)
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Failed to read the obfuscated embedded data."
+    );
+  }
+}
@@ -30,6 +30,8 @@ use thiserror::Error;
 use crate::util;
 use crate::util::fs::canonicalize_path;
 
+use super::payload::PayloadEncoding;
+
 #[derive(Error, Debug)]
 #[error(
   "Failed to strip prefix '{}' from '{}'", root_path.display(), target.display()
@@ -203,6 +205,7 @@ impl VfsBuilder {
             name: name.to_string(),
             offset,
             len: data.len() as u64,
+            encoded_len: None,
           }),
         );
       }
@@ -252,6 +255,47 @@ impl VfsBuilder {
     (self.root_dir, self.files)
   }
 
+  /// Encodes each file separately, so that a file can be decoded on its own
+  /// when it's read, and points the entries of the directory to the encoded
+  /// data.
+  pub fn into_encoded_dir_and_files(
+    self,
+    encoding: &PayloadEncoding,
+  ) -> Result<(VirtualDirectory, Vec<Vec<u8>>), AnyError> {
+    let (mut dir, files) = self.into_dir_and_files();
+    if encoding.is_identity() {
+      return Ok((dir, files));
+    }
+    let mut encoded_offsets = HashMap::with_capacity(files.len());
+    let mut encoded_files = Vec::with_capacity(files.len());
+    let mut offset = 0;
+    let mut encoded_offset = 0;
+    for data in files {
+      // empty files share their offset with the next file and are never
+      // read, so they aren't stored
+      if data.is_empty() {
+        continue;
+      }
+      let len = data.len() as u64;
+      let data = encoding.encode(data)?;
+      let encoded_len = data.len() as u64;
+      encoded_offsets.insert(offset, (encoded_offset, encoded_len));
+      offset += len;
+      encoded_offset += encoded_len;
+      encoded_files.push(data);
+    }
+    dir.visit_files_mut(&mut |file| {
+      let (offset, encoded_len) = if file.len == 0 {
+        (0, 0)
+      } else {
+        encoded_offsets[&file.offset]
+      };
+      file.offset = offset;
+      file.encoded_len = Some(encoded_len);
+    });
+    Ok((dir, encoded_files))
+  }
+
   fn path_relative_root(&self, path: &Path) -> Result<PathBuf, StripRootError> {
     match path.strip_prefix(&self.root_path) {
       Ok(p) => Ok(p.to_path_buf()),
@@ -376,11 +420,27 @@ pub struct VirtualDirectory {
   pub entries: Vec<VfsEntry>,
 }
 
+impl VirtualDirectory {
+  fn visit_files_mut(&mut self, visit: &mut impl FnMut(&mut VirtualFile)) {
+    for entry in &mut self.entries {
+      match entry {
+        VfsEntry::Dir(dir) => dir.visit_files_mut(visit),
+        VfsEntry::File(file) => visit(file),
+        VfsEntry::Symlink(_) => {}
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VirtualFile {
   pub name: String,
   pub offset: u64,
   pub len: u64,
+  /// The length of the file's data in the executable when it's compressed or
+  /// obfuscated, in which case `offset` points to the encoded data.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub encoded_len: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -723,16 +783,29 @@ impl deno_io::fs::File for FileBackedVfsFile {
   }
 }
 
-#[derive(Debug)]
 pub struct FileBackedVfs {
   file: Mutex<File>,
+  encoding: PayloadEncoding,
+  /// The last file which was decoded, since files are usually read in
+  /// several chunks.
+  last_decoded_file: Mutex<Option<(u64, Arc<Vec<u8>>)>>,
   fs_root: VfsRoot,
 }
 
 impl FileBackedVfs {
   pub fn new(file: File, fs_root: VfsRoot) -> Self {
+    Self::new_encoded(file, fs_root, PayloadEncoding::default())
+  }
+
+  pub fn new_encoded(
+    file: File,
+    fs_root: VfsRoot,
+    encoding: PayloadEncoding,
+  ) -> Self {
     Self {
       file: Mutex::new(file),
+      encoding,
+      last_decoded_file: Default::default(),
       fs_root,
     }
   }
@@ -813,11 +886,45 @@ impl FileBackedVfs {
     pos: u64,
     buf: &mut [u8],
   ) -> std::io::Result<usize> {
-    let mut fs_file = self.file.lock();
-    fs_file.seek(SeekFrom::Start(
-      self.fs_root.start_file_offset + file.offset + pos,
-    ))?;
-    fs_file.read(buf)
+    let start = self.fs_root.start_file_offset + file.offset;
+    let Some(encoded_len) = file.encoded_len else {
+      let mut fs_file = self.file.lock();
+      fs_file.seek(SeekFrom::Start(start + pos))?;
+      return fs_file.read(buf);
+    };
+    if pos >= file.len {
+      return Ok(0);
+    }
+    let data = self.decode_file(start, encoded_len)?;
+    let data = data.get(pos as usize..).unwrap_or_default();
+    let len = std::cmp::min(data.len(), buf.len());
+    buf[..len].copy_from_slice(&data[..len]);
+    Ok(len)
+  }
+
+  fn decode_file(
+    &self,
+    start: u64,
+    encoded_len: u64,
+  ) -> std::io::Result<Arc<Vec<u8>>> {
+    let mut last_decoded_file = self.last_decoded_file.lock();
+    if let Some((last_start, data)) = &*last_decoded_file {
+      if *last_start == start {
+        return Ok(data.clone());
+      }
+    }
+    let mut encoded = vec![0; encoded_len as usize];
+    {
+      let mut fs_file = self.file.lock();
+      fs_file.seek(SeekFrom::Start(start))?;
+      fs_file.read_exact(&mut encoded)?;
+    }
+    let data = self.encoding.decode(encoded).map_err(|err| {
+      std::io::Error::new(std::io::ErrorKind::Other, err.to_string())
+    })?;
+    let data = Arc::new(data);
+    *last_decoded_file = Some((start, data.clone()));
+    Ok(data)
   }
 
   pub fn dir_entry(&self, path: &Path) -> std::io::Result<&VirtualDirectory> {
@@ -1043,6 +1150,59 @@ mod test {
     );
   }
 
+  #[test]
+  fn encoded_files() {
+    let temp_dir = TempDir::new();
+    let src_path = temp_dir.path().canonicalize().join("src");
+    src_path.create_dir_all();
+    let src_path = src_path.to_path_buf();
+    let mut builder = VfsBuilder::new(src_path.clone()).unwrap();
+    builder
+      .add_file(&src_path.join("a.txt"), "a".into())
+      .unwrap();
+    builder.add_file(&src_path.join("b.txt"), vec![]).unwrap();
+    builder
+      .add_file(&src_path.join("c.txt"), "cc".into())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("d.txt"), "cc".into())
+      .unwrap();
+    let encoding = PayloadEncoding::new(true, true).unwrap();
+    let (root_dir, files) =
+      builder.into_encoded_dir_and_files(&encoding).unwrap();
+    // the empty and duplicate files aren't stored
+    assert_eq!(files.len(), 2);
+    let virtual_fs_file = temp_dir.path().join("virtual_fs");
+    {
+      let mut file = std::fs::File::create(&virtual_fs_file).unwrap();
+      file.write_all(&files[0]).unwrap();
+      // corrupt the last file, which shouldn't affect reading the others
+      file.write_all(&vec![0; files[1].len()]).unwrap();
+    }
+    let dest_path = temp_dir.path().join("dest").to_path_buf();
+    let virtual_fs = FileBackedVfs::new_encoded(
+      std::fs::File::open(&virtual_fs_file).unwrap(),
+      VfsRoot {
+        dir: root_dir,
+        root_path: dest_path.clone(),
+        start_file_offset: 0,
+      },
+      encoding,
+    );
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("a.txt")), "a");
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("b.txt")), "");
+    assert!(virtual_fs
+      .read_file_all(virtual_fs.file_entry(&dest_path.join("c.txt")).unwrap())
+      .is_err());
+
+    // reads don't go beyond the end of the file
+    let file = virtual_fs.file_entry(&dest_path.join("a.txt")).unwrap();
+    assert_eq!(file.len, 1);
+    let mut buf = vec![0; 3];
+    assert_eq!(virtual_fs.read_file(file, 0, &mut buf).unwrap(), 1);
+    assert_eq!(virtual_fs.read_file(file, 2, &mut buf).unwrap(), 0);
+  }
+
   #[tokio::test]
   async fn test_open_file() {
     let temp_dir = TempDir::new();
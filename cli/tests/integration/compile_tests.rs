@@ -714,6 +714,180 @@ fn dynamic_import_unanalyzable() {
     .assert_exit_code(0);
 }
 
+#[test]
+fn compile_include_dir() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    concat!(
+      "const url = new URL('./public/index.html', import.meta.url);\n",
+      "console.log(Deno.readTextFileSync(url));\n",
+      "const dir = new URL('./public/assets', import.meta.url);\n",
+      "console.log([...Deno.readDirSync(dir)].map((e) => e.name));\n",
+      "const data = Deno.readTextFileSync(new URL('./data.json', dir + '/'));\n",
+      "console.log(JSON.parse(data));\n",
+      "const res = await fetch(import.meta.resolve('./public/assets/data.json'));\n",
+      "console.log(await res.json());\n",
+    ),
+  );
+  temp_dir.create_dir_all("public/assets");
+  temp_dir.write("public/index.html", "<h1>Hello</h1>");
+  temp_dir.write("public/assets/data.json", r#"{ "value": 1 }"#);
+
+  let binary_path = if cfg!(windows) {
+    temp_dir.path().join("binary.exe")
+  } else {
+    temp_dir.path().join("binary")
+  };
+  context
+    .new_command()
+    .args("compile --include-dir public --output binary main.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  // the files are read from the executable
+  temp_dir.remove_dir_all("public");
+  context
+    .new_command()
+    .name(&binary_path)
+    .run()
+    .assert_matches_text(
+      "<h1>Hello</h1>\n[ \"data.json\" ]\n{ value: 1 }\n{ value: 1 }\n",
+    )
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args("compile --include-dir missing --output binary main.ts")
+    .run()
+    .assert_matches_text(
+      "[WILDCARD]error: Writing [WILDCARD]binary[WILDCARD]\n\nCaused by:\n    Failed to include directory '[WILDCARD]missing' because it is not a directory.\n",
+    )
+    .assert_exit_code(1);
+}
+
+#[test]
+fn compile_compress_and_obfuscate() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    concat!(
+      "const url = new URL('./public/index.html', import.meta.url);\n",
+      "console.log('embedded source text');\n",
+      "console.log(Deno.readTextFileSync(url));\n",
+    ),
+  );
+  temp_dir.create_dir_all("public");
+  temp_dir.write("public/index.html", "<h1>Hello</h1>");
+
+  let binary_path = if cfg!(windows) {
+    temp_dir.path().join("binary.exe")
+  } else {
+    temp_dir.path().join("binary")
+  };
+  for (args, obfuscated) in [
+    ("--compress", false),
+    ("--obfuscate", true),
+    ("--compress --obfuscate", true),
+  ] {
+    context
+      .new_command()
+      .args(format!(
+        "compile {args} --include-dir public --output binary main.ts"
+      ))
+      .run()
+      .skip_output_check()
+      .assert_exit_code(0);
+
+    if obfuscated {
+      // the sources are not stored as plaintext
+      let binary = std::fs::read(&binary_path).unwrap();
+      assert!(!binary
+        .windows(b"embedded source text".len())
+        .any(|window| window == b"embedded source text"));
+    }
+
+    context
+      .new_command()
+      .name(&binary_path)
+      .run()
+      .assert_matches_text("embedded source text\n<h1>Hello</h1>\n")
+      .assert_exit_code(0);
+  }
+}
+
+#[test]
+fn compile_info() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    "import { value } from './lib.ts';\nconsole.log(value);\n",
+  );
+  temp_dir.write("lib.ts", "export const value: number = 1;\n");
+  context
+    .new_command()
+    .args("compile --allow-read --allow-env=HOME --output binary main.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  let binary_name = if cfg!(windows) {
+    "binary.exe"
+  } else {
+    "binary"
+  };
+  context
+    .new_command()
+    .args(format!("info {binary_name}"))
+    .run()
+    .assert_matches_text(concat!(
+      "entrypoint: file:///[WILDCARD]main.ts\n",
+      "deno version: [WILDCARD]\n",
+      "permissions: --allow-env=HOME --allow-read\n",
+      "\n",
+      "modules: 2 ([WILDCARD])\n",
+      "[WILDCARD]",
+    ))
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args(format!("info --extract sources {binary_name}"))
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let mut extracted = walkdir::WalkDir::new(temp_dir.path().join("sources"))
+    .into_iter()
+    .filter_map(|entry| entry.ok())
+    .filter(|entry| entry.file_type().is_file())
+    .map(|entry| entry.into_path())
+    .collect::<Vec<_>>();
+  extracted.sort();
+  assert_eq!(
+    extracted
+      .iter()
+      .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+      .collect::<Vec<_>>(),
+    vec!["lib.ts", "main.ts"],
+  );
+  // the embedded modules are transpiled
+  let lib_source = std::fs::read_to_string(&extracted[0]).unwrap();
+  assert!(!lib_source.contains(": number"), "{lib_source}");
+
+  context
+    .new_command()
+    .args("info --extract sources main.ts")
+    .run()
+    .assert_matches_text(
+      "error: --extract can only be used with an executable created by 'deno compile'.\n",
+    )
+    .assert_exit_code(1);
+}
+
 #[test]
 fn compile_npm_specifiers() {
   let context = TestContextBuilder::for_npm().use_temp_cwd().build();
@@ -4,6 +4,7 @@ use deno_core::serde_json;
 use std::fs;
 use test_util as util;
 use test_util::TempDir;
+use util::assert_contains;
 use util::assert_starts_with;
 use util::env_vars_for_npm_tests;
 use util::TestContext;
@@ -631,6 +632,305 @@ File         | Branch % | Line % |
   );
 }
 
+#[test]
+fn test_cobertura_and_json_reporters() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let cobertura_path = tempdir.join("cobertura.xml");
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--cobertura".to_string(),
+      format!("--output={}", cobertura_path),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+  let xml = cobertura_path.read_to_string();
+  assert_contains!(xml, "<coverage lines-valid=\"");
+  assert_contains!(xml, "<package name=\"baz\"");
+  assert_contains!(xml, "<class name=\"quux.ts\" filename=\"baz/quux.ts\"");
+  assert_contains!(xml, "<class name=\"foo.ts\" filename=\"foo.ts\"");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--json".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_eq!(output.stderr(), "");
+  let summary: serde_json::Value =
+    serde_json::from_str(output.stdout()).unwrap();
+  let files = summary["files"].as_array().unwrap();
+  assert_eq!(files.len(), 4);
+  assert_eq!(summary["total"]["branches"]["total"], 5);
+  assert_eq!(summary["total"]["branches"]["covered"], 2);
+  let bar = files
+    .iter()
+    .find(|file| file["file"].as_str().unwrap().ends_with("bar.ts"))
+    .unwrap();
+  assert_eq!(bar["lines"]["covered"], 4);
+  assert_eq!(bar["lines"]["total"], 7);
+
+  // the output file is written even when no files are reported
+  let json_path = tempdir.join("coverage.json");
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--json".to_string(),
+      "--exclude=.".to_string(),
+      format!("--output={}", json_path),
+      format!("{}/", tempdir),
+    ])
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_eq!(output.stdout(), "");
+  let summary: serde_json::Value =
+    serde_json::from_str(&json_path.read_to_string()).unwrap();
+  assert_eq!(summary["files"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_coverage_thresholds() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--threshold-lines=60".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--threshold-lines=60".to_string(),
+      "--threshold-branches=50".to_string(),
+      "--threshold-per-file=50".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "[WILDCARD] All files   |     40.0 |   61.0 |
+----------------------------------
+threshold Line coverage of 28.6% is below the per file threshold of 50%: [WILDCARD]quux.ts
+threshold Branch coverage of 40.0% is below the threshold of 50%
+error: Coverage is below 2 thresholds
+",
+  );
+
+  // the thresholds of the discovered config file are used
+  context.temp_dir().write(
+    "deno.json",
+    r#"{ "test": { "coverage": { "thresholds": { "branches": 50 } } } }"#,
+  );
+  let output = context
+    .new_command()
+    .current_dir(context.temp_dir().path())
+    .args_vec(vec!["coverage".to_string(), format!("{}/", tempdir)])
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.combined_output(),
+    "Branch coverage of 40.0% is below the threshold of 50%"
+  );
+}
+
+#[test]
+fn test_diff_coverage() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let diff_path = tempdir.path().join("changes.diff");
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  // marks lines 3 to 5 of bar.ts as changed
+  diff_path.write(concat!(
+    "diff --git a/coverage/multisource/bar.ts b/coverage/multisource/bar.ts\n",
+    "--- a/coverage/multisource/bar.ts\n",
+    "+++ b/coverage/multisource/bar.ts\n",
+    "@@ -3,2 +3,3 @@ export function bar<T>(cond: T) {\n",
+  ));
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      format!("--diff-file={}", diff_path),
+      "--diff-threshold=100".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "[WILDCARD]
+Diff coverage: [WILDCARD]% ([WILDCARD]/3 changed lines)
+ coverage/multisource/bar.ts uncovered lines [WILDCARD]
+error: Diff coverage of [WILDCARD]% is below the threshold of 100%
+",
+  );
+
+  // the diff coverage doesn't end up in the output of the reporter
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--lcov".to_string(),
+      format!("--diff-file={}", diff_path),
+      format!("{}/", tempdir),
+    ])
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_starts_with!(output.stdout(), "SF:");
+  assert_contains!(output.stderr(), "Diff coverage:");
+
+  // paths that don't match any file fail the threshold
+  diff_path.write(concat!(
+    "--- multisource/bar.ts\n",
+    "+++ multisource/bar.ts\n",
+    "@@ -3,2 +3,3 @@ export function bar<T>(cond: T) {\n",
+  ));
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      format!("--diff-file={}", diff_path),
+      "--diff-threshold=50".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.combined_output(),
+    "none of the changed lines have coverage data"
+  );
+}
+
+#[test]
+fn test_run_coverage_with_worker_and_subprocess() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "run".to_string(),
+      "--quiet".to_string(),
+      "--allow-read".to_string(),
+      "--allow-run".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/run/main.ts".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "3
+hello main from worker
+hello main from subprocess
+",
+  );
+
+  let output = context
+    .new_command()
+    .args_vec(vec!["coverage".to_string(), format!("{}/", tempdir)])
+    .run();
+  output.assert_exit_code(0);
+  let output = output.combined_output();
+  assert_contains!(output, "coverage/run/main.ts ... 100.000%");
+  assert_contains!(output, "coverage/run/math.ts ... 100.000%");
+  assert_contains!(output, "coverage/run/worker.ts ... 100.000%");
+  assert_contains!(output, "coverage/run/subprocess.ts ... 100.000%");
+}
+
+#[test]
+fn test_run_coverage_with_terminated_worker() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "run".to_string(),
+      "--quiet".to_string(),
+      "--allow-read".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/run/terminated_worker.ts".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.assert_matches_text("hello main from worker\n");
+
+  let output = context
+    .new_command()
+    .args_vec(vec!["coverage".to_string(), format!("{}/", tempdir)])
+    .run();
+  output.assert_exit_code(0);
+  let output = output.combined_output();
+  assert_contains!(output, "coverage/run/terminated_worker.ts ... 100.000%");
+  assert_contains!(output, "coverage/run/worker.ts ... 100.000%");
+}
+
 itest!(no_files_found {
   args: "coverage doesnt_exist.js",
   exit_code: 1,
@@ -2,6 +2,7 @@
 
 use test_util as util;
 use util::assert_contains;
+use util::assert_not_contains;
 use util::PathRef;
 use util::TestContext;
 use util::TestContextBuilder;
@@ -215,6 +216,24 @@ itest!(fmt_stdin_ipynb {
   output_str: Some(include_str!("../testdata/fmt/badly_formatted_fixed.ipynb")),
 });
 
+itest!(fmt_stdin_css {
+  args: "fmt --unstable-css --ext=css -",
+  input: Some("a{color:red;   margin : 0}"),
+  output_str: Some("a {\n  color: red;\n  margin: 0;\n}\n"),
+});
+
+itest!(fmt_stdin_html {
+  args: "fmt --unstable-html --ext=html -",
+  input: Some("<div><p>Hello</p>\n<script>const a  =  1</script></div>"),
+  output_str: Some("<div>\n  <p>Hello</p>\n  <script>\n    const a = 1;\n  </script>\n</div>\n"),
+});
+
+itest!(fmt_stdin_yaml {
+  args: "fmt --unstable-yaml --ext=yaml -",
+  input: Some("key:    value\nlist:\n    -   a\n    -   b\n"),
+  output_str: Some("key: value\nlist:\n  - a\n  - b\n"),
+});
+
 itest!(fmt_stdin_check_formatted {
   args: "fmt --check -",
   input: Some("const a = 1;\n"),
@@ -350,3 +369,117 @@ fn fmt_with_glob_config_and_flags() {
 
   assert_contains!(output, "Found 2 not formatted files in 2 files");
 }
+
+#[test]
+fn fmt_css_html_yaml_with_config() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", r#"{ "fmt": { "indentWidth": 4 } }"#);
+  temp_dir.write("styles.css", "a {\n    color: red;\n}\n");
+  temp_dir.write("index.html", "<div>\n<p>Hello</p>\n</div>\n");
+  temp_dir.write("ci.yml", "steps:\n  - run: deno test\n");
+  temp_dir.write(
+    "ignored.yml",
+    "# deno-fmt-ignore-file\nsteps:\n  -    run: deno test\n",
+  );
+
+  // formatting these languages is unstable, so it has to be enabled
+  context
+    .new_command()
+    .args("fmt --check")
+    .run()
+    .assert_matches_text("Checked 1 file\n");
+  let output = context
+    .new_command()
+    .args("fmt --check --unstable-yaml")
+    .run();
+  output.assert_exit_code(1);
+  output
+    .assert_matches_text("[WILDCARD]Found 1 not formatted file in 3 files\n");
+
+  temp_dir.write(
+    "deno.json",
+    r#"{
+  "fmt": { "indentWidth": 4 },
+  "unstable": ["fmt-css", "fmt-html", "fmt-yaml"]
+}
+"#,
+  );
+  let output = context.new_command().args("fmt --check").run();
+  output.assert_exit_code(1);
+  let output_text = output.combined_output();
+  assert_contains!(output_text, "index.html");
+  assert_contains!(output_text, "ci.yml");
+  assert_not_contains!(output_text, "styles.css");
+  assert_not_contains!(output_text, "ignored.yml");
+  assert_contains!(output_text, "Found 2 not formatted files in 5 files");
+
+  context
+    .new_command()
+    .args("fmt")
+    .run()
+    .assert_matches_text("Checked 5 files\n");
+  assert_eq!(
+    temp_dir.read_to_string("index.html"),
+    "<div>\n    <p>Hello</p>\n</div>\n"
+  );
+  assert_eq!(
+    temp_dir.read_to_string("ci.yml"),
+    "steps:\n    - run: deno test\n"
+  );
+}
+
+#[test]
+fn fmt_changed_since() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  let git = |args: &[&str]| {
+    let status = std::process::Command::new("git")
+      .args(args)
+      .current_dir(temp_dir.path())
+      .stdout(std::process::Stdio::null())
+      .stderr(std::process::Stdio::null())
+      .status()
+      .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+  };
+  temp_dir.write("a.ts", "const a=1\nconst b=2\nconst c=3\n");
+  temp_dir.write("b.ts", "const d=4\n");
+  git(&["init", "-q"]);
+  git(&["add", "."]);
+  git(&[
+    "-c",
+    "user.name=deno",
+    "-c",
+    "user.email=deno@example.com",
+    "commit",
+    "-q",
+    "-m",
+    "initial",
+  ]);
+  temp_dir.write("a.ts", "const a=1\nconst b=20\nconst c=3\n");
+  temp_dir.write("new.ts", "const e=5\n");
+
+  let output = context
+    .new_command()
+    .args("fmt --check --changed-since=HEAD")
+    .run();
+  output.assert_exit_code(1);
+  let output_text = output.combined_output();
+  assert_contains!(output_text, "a.ts");
+  assert_contains!(output_text, "new.ts");
+  assert_not_contains!(output_text, "b.ts");
+  assert_contains!(output_text, "Found 2 not formatted files in 2 files");
+
+  context
+    .new_command()
+    .args("fmt --changed-since=HEAD")
+    .run()
+    .assert_matches_text("[WILDCARD]Checked 2 files\n");
+  assert_eq!(
+    temp_dir.read_to_string("a.ts"),
+    "const a=1\nconst b = 20;\nconst c=3\n"
+  );
+  assert_eq!(temp_dir.read_to_string("b.ts"), "const d=4\n");
+  assert_eq!(temp_dir.read_to_string("new.ts"), "const e = 5;\n");
+}
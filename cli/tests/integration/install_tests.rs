@@ -248,3 +248,70 @@ fn check_local_by_default2() {
     .skip_output_check()
     .assert_exit_code(0);
 }
+
+#[test]
+fn list_and_upgrade_installed_scripts() {
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+
+  context
+    .new_command()
+    .args("install --list --root ./root")
+    .run()
+    .assert_matches_text("No scripts are installed in [WILDCARD]")
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args(
+      "install --root ./root --allow-read --name denotest_bin npm:@denotest/bin@0.5.0",
+    )
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args("install --list --root ./root")
+    .run()
+    .assert_matches_text(concat!(
+      "denotest_bin\n",
+      "  url: npm:@denotest/bin@0.5.0\n",
+      "  version: 0.5.0\n",
+      "  flags: --allow-read --lock [WILDCARD]\n",
+    ))
+    .assert_exit_code(0);
+
+  let output = context
+    .new_command()
+    .args("install --upgrade --root ./root denotest_bin")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.combined_output(),
+    "Upgrading denotest_bin from npm:@denotest/bin@0.5.0 to npm:@denotest/bin@1.0.0"
+  );
+
+  let output = context
+    .new_command()
+    .args("install --list --root ./root")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "url: npm:@denotest/bin@1.0.0");
+  assert_contains!(output.combined_output(), "flags: --allow-read --lock ");
+
+  context
+    .new_command()
+    .args("install --upgrade --root ./root")
+    .run()
+    .assert_matches_text(
+      "denotest_bin is up to date (npm:@denotest/bin@1.0.0)\n",
+    )
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args("install --upgrade --root ./root other")
+    .run()
+    .assert_matches_text("error: No installation found for other\n")
+    .assert_exit_code(1);
+}
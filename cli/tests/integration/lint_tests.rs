@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
 use test_util::assert_contains;
 use test_util::TestContextBuilder;
 
@@ -209,3 +210,168 @@ fn lint_with_glob_config_and_flags() {
   assert_contains!(output, "Found 2 problems");
   assert_contains!(output, "Checked 2 files");
 }
+
+#[test]
+fn lint_fix() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    "let a = 1;;\nlet b: number = 2;\nlet c = 3;\nc++;\nconsole.log(a, b, c);\n",
+  );
+
+  let output = context.new_command().args("lint --fix-dry-run").run();
+  // the diagnostics of the unfixed file are reported
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "(prefer-const)");
+  assert_contains!(output, "(no-extra-semi)");
+  assert_contains!(output, "Checked 1 file");
+  // the file is left untouched
+  assert_eq!(
+    temp_dir.read_to_string("main.ts"),
+    "let a = 1;;\nlet b: number = 2;\nlet c = 3;\nc++;\nconsole.log(a, b, c);\n",
+  );
+
+  let output = context.new_command().args("lint --fix").run();
+  output.assert_exit_code(0);
+  assert_eq!(
+    temp_dir.read_to_string("main.ts"),
+    "const a = 1;\nconst b = 2;\nlet c = 3;\nc++;\nconsole.log(a, b, c);\n",
+  );
+}
+
+#[test]
+fn lint_plugins() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", r#"{ "lint": { "plugins": ["./plugin.ts"] } }"#);
+  temp_dir.write(
+    "plugin.ts",
+    r#"export default {
+  name: "house",
+  rules: {
+    "no-lodash": {
+      create(context) {
+        return {
+          ImportDeclaration(node) {
+            if (node.source.value === "npm:lodash") {
+              context.report({ node, message: "Use the standard library" });
+            }
+          },
+        };
+      },
+    },
+  },
+};
+"#,
+  );
+  temp_dir.write(
+    "main.ts",
+    r#"import _ from "npm:lodash";
+// deno-lint-ignore house/no-lodash
+import _2 from "npm:lodash";
+console.log(_, _2);
+"#,
+  );
+
+  let output = context.new_command().args("lint main.ts").run();
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "(house/no-lodash) Use the standard library");
+  assert_contains!(output, "main.ts:1:1");
+  assert_contains!(output, "Found 1 problem");
+
+  // the file ignore comment only applies at the top of the file and ignore
+  // comments for codes the plugins don't define are still reported
+  temp_dir.write(
+    "other.ts",
+    r#"import _ from "npm:lodash";
+// deno-lint-ignore-file house/no-lodash
+// deno-lint-ignore other/rule
+console.log(_);
+"#,
+  );
+  let output = context.new_command().args("lint other.ts").run();
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "(house/no-lodash) Use the standard library");
+  assert_contains!(output, "(ban-unknown-rule-code)");
+
+  // every plugin is only evaluated once, including the first one
+  temp_dir.write(
+    "deno.json",
+    r#"{ "lint": { "plugins": ["./counter.ts", "./plugin.ts"] } }"#,
+  );
+  temp_dir.write(
+    "counter.ts",
+    r#"globalThis.evaluations = (globalThis.evaluations ?? 0) + 1;
+export default {
+  name: "counter",
+  rules: {
+    "evaluations": {
+      create(context) {
+        return {
+          Module(node) {
+            context.report({
+              node,
+              message: `Evaluated ${globalThis.evaluations} time(s)`,
+            });
+          },
+        };
+      },
+    },
+  },
+};
+"#,
+  );
+  let output = context.new_command().args("lint main.ts").run();
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "(counter/evaluations) Evaluated 1 time(s)");
+  assert_contains!(output, "(house/no-lodash) Use the standard library");
+}
+
+#[test]
+fn lint_sarif_and_github_reporters() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "debugger;\n");
+
+  let output = context
+    .new_command()
+    .args("lint --sarif main.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_eq!(output.stderr(), "");
+  let sarif: serde_json::Value = serde_json::from_str(output.stdout()).unwrap();
+  assert_eq!(sarif["version"], "2.1.0");
+  let result = &sarif["runs"][0]["results"][0];
+  assert_eq!(result["ruleId"], "no-debugger");
+  assert_eq!(
+    result["locations"][0]["physicalLocation"]["region"]["startLine"],
+    1
+  );
+  assert_eq!(sarif["runs"][0]["columnKind"], "unicodeCodePoints");
+  let artifact_location =
+    &result["locations"][0]["physicalLocation"]["artifactLocation"];
+  assert_eq!(artifact_location["uri"], "main.ts");
+  assert_eq!(artifact_location["uriBaseId"], "%SRCROOT%");
+  let src_root = sarif["runs"][0]["originalUriBaseIds"]["%SRCROOT%"]["uri"]
+    .as_str()
+    .unwrap();
+  assert!(src_root.starts_with("file:///") && src_root.ends_with('/'));
+
+  let output = context
+    .new_command()
+    .args("lint --github main.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(output.stderr(), "Found 1 problem");
+  assert_contains!(
+    output.stdout(),
+    "::error file=main.ts,line=1,endLine=1,col=1,endColumn=10,title=no-debugger::"
+  );
+}
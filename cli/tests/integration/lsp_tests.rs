@@ -8018,6 +8018,138 @@ fn lsp_diagnostics_deprecated() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_pull_diagnostics() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize(|builder| {
+    builder.with_capabilities(|c| {
+      let doc = c.text_document.as_mut().unwrap();
+      doc.diagnostic = Some(Default::default());
+      let workspace = c.workspace.as_mut().unwrap();
+      workspace.diagnostic = Some(lsp::DiagnosticWorkspaceClientCapabilities {
+        refresh_support: Some(true),
+      });
+    });
+  });
+  let diagnostics = client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "export const a: string = 1;\n",
+    },
+  }));
+  // nothing is published to clients which pull diagnostics, they're asked
+  // to pull them again instead
+  assert_eq!(json!(diagnostics.all_messages()), json!([]));
+  let (id, method, _) = client.read_request::<Value>();
+  assert_eq!(method, "workspace/diagnostic/refresh");
+  client.write_response(id, json!(null));
+
+  let res = client.write_request(
+    "textDocument/diagnostic",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+    }),
+  );
+  assert_eq!(res["kind"], json!("full"));
+  let result_id = res["resultId"].as_str().unwrap().to_string();
+  assert_eq!(
+    res["items"],
+    json!([{
+      "range": {
+        "start": { "line": 0, "character": 13 },
+        "end": { "line": 0, "character": 14 }
+      },
+      "severity": 1,
+      "code": 2322,
+      "source": "deno-ts",
+      "message": "Type 'number' is not assignable to type 'string'."
+    }])
+  );
+
+  let res = client.write_request(
+    "textDocument/diagnostic",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "previousResultId": result_id,
+    }),
+  );
+  assert_eq!(
+    res,
+    json!({
+      "kind": "unchanged",
+      "resultId": result_id,
+    })
+  );
+
+  let res = client.write_request(
+    "workspace/diagnostic",
+    json!({
+      "previousResultIds": [],
+    }),
+  );
+  let items = res["items"].as_array().unwrap();
+  assert_eq!(items.len(), 1);
+  assert_eq!(items[0]["uri"], json!("file:///a/file.ts"));
+  assert_eq!(items[0]["version"], json!(1));
+  assert_eq!(items[0]["kind"], json!("full"));
+  assert_eq!(items[0]["items"][0]["code"], json!(2322));
+
+  let res = client.write_request(
+    "workspace/diagnostic",
+    json!({
+      "previousResultIds": [{
+        "uri": "file:///a/file.ts",
+        "value": result_id,
+      }],
+    }),
+  );
+  assert_eq!(
+    res,
+    json!({
+      "items": [{
+        "kind": "unchanged",
+        "uri": "file:///a/file.ts",
+        "version": 1,
+        "resultId": result_id,
+      }],
+    })
+  );
+  client.shutdown();
+}
+
+#[test]
+fn lsp_pull_diagnostics_without_refresh_support() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize(|builder| {
+    builder.with_capabilities(|c| {
+      let doc = c.text_document.as_mut().unwrap();
+      doc.diagnostic = Some(Default::default());
+    });
+  });
+  // the client couldn't be told when the diagnostics change, so they're
+  // published instead
+  let diagnostics = client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "export const a: string = 1;\n",
+    },
+  }));
+  let messages = diagnostics.all_messages();
+  assert_eq!(messages.len(), 1);
+  assert_eq!(json!(messages[0].diagnostics[0].code), json!(2322));
+  client.shutdown();
+}
+
 #[test]
 fn lsp_diagnostics_deno_types() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -8449,6 +8581,187 @@ fn lsp_format_mbc() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_range_format() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a=1\nconst b=2\nconst c=3\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/rangeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "range": {
+        "start": { "line": 1, "character": 0 },
+        "end": { "line": 1, "character": 9 }
+      },
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  let edits = res.as_array().unwrap();
+  assert!(!edits.is_empty());
+  for edit in edits {
+    assert_eq!(edit["range"]["start"]["line"], json!(1));
+    assert_eq!(edit["range"]["end"]["line"], json!(1));
+  }
+  client.shutdown();
+}
+
+#[test]
+fn lsp_on_type_format() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a=1;\nfunction b(){\nreturn a}\nconst c=3;\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/onTypeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "position": { "line": 2, "character": 9 },
+      "ch": "}",
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  let edits = res.as_array().unwrap();
+  assert!(!edits.is_empty());
+  for edit in edits {
+    let start_line = edit["range"]["start"]["line"].as_u64().unwrap();
+    let end_line = edit["range"]["end"]["line"].as_u64().unwrap();
+    assert!((1..=2).contains(&start_line));
+    assert!((1..=2).contains(&end_line));
+  }
+
+  let res = client.write_request(
+    "textDocument/onTypeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "position": { "line": 0, "character": 10 },
+      "ch": ";",
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  let edits = res.as_array().unwrap();
+  assert!(!edits.is_empty());
+  for edit in edits {
+    assert_eq!(edit["range"]["start"]["line"], json!(0));
+    assert_eq!(edit["range"]["end"]["line"], json!(0));
+  }
+  client.shutdown();
+}
+
+#[test]
+fn lsp_document_links() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  let config_text = r#"{
+  "imports": {
+    "chalk": "npm:chalk@5",
+    "~/": "./src/"
+  },
+  "lock": "./deno.lock"
+}
+"#;
+  temp_dir.write("deno.json", config_text);
+  let mut client = context.new_lsp_command().build();
+  client.initialize(|builder| {
+    builder.set_config("./deno.json");
+  });
+  client.did_open(json!({
+    "textDocument": {
+      "uri": temp_dir.uri().join("deno.json").unwrap(),
+      "languageId": "json",
+      "version": 1,
+      "text": config_text,
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/documentLink",
+    json!({
+      "textDocument": {
+        "uri": temp_dir.uri().join("deno.json").unwrap(),
+      },
+    }),
+  );
+  assert_eq!(
+    res,
+    json!([{
+      "range": {
+        "start": { "line": 2, "character": 14 },
+        "end": { "line": 2, "character": 25 }
+      },
+      "target": "https://www.npmjs.com/package/chalk"
+    }, {
+      "range": {
+        "start": { "line": 3, "character": 11 },
+        "end": { "line": 3, "character": 17 }
+      },
+      "target": temp_dir.uri().join("src/").unwrap(),
+    }, {
+      "range": {
+        "start": { "line": 5, "character": 11 },
+        "end": { "line": 5, "character": 22 }
+      },
+      "target": temp_dir.uri().join("deno.lock").unwrap(),
+    }])
+  );
+
+  client.did_open(json!({
+    "textDocument": {
+      "uri": temp_dir.uri().join("file.ts").unwrap(),
+      "languageId": "typescript",
+      "version": 1,
+      "text": "import chalk from \"npm:chalk@5\";\nimport \"https://deno.land/x/a/mod.ts\";\nimport \"./other.ts\";\n",
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/documentLink",
+    json!({
+      "textDocument": {
+        "uri": temp_dir.uri().join("file.ts").unwrap(),
+      },
+    }),
+  );
+  let links = res.as_array().unwrap();
+  assert_eq!(links.len(), 2);
+  assert_eq!(links[0]["range"]["start"]["line"], json!(0));
+  assert_eq!(
+    links[0]["target"],
+    json!("https://www.npmjs.com/package/chalk")
+  );
+  assert_eq!(links[1]["range"]["start"]["line"], json!(1));
+  assert_eq!(links[1]["target"], json!("https://deno.land/x/a/mod.ts"));
+  client.shutdown();
+}
+
 #[test]
 fn lsp_format_exclude_with_config() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -1108,3 +1108,108 @@ fn pty_promise_was_collected_regression_test() {
   assert_contains!(out, "Uint8Array(67108864)");
   assert!(err.is_empty());
 }
+
+#[test]
+fn pty_type_command() {
+  util::with_pty(&["repl"], |mut console| {
+    console.write_line("let a = 1;");
+    console.expect("undefined");
+    console.write_line(":type a");
+    console.expect("number");
+    console.write_line(":type [a, 'b']");
+    console.expect("(string | number)[]");
+    console.write_line(":type");
+    console.expect("error: Usage: :type <expression>");
+  });
+}
+
+#[test]
+fn pty_type_check() {
+  util::with_pty(&["repl", "--check"], |mut console| {
+    console.write_line("const a: number = 1;");
+    console.expect("undefined");
+    console.write_line("const b: string = a;");
+    console.expect(
+      "TS2322 [ERROR]: Type 'number' is not assignable to type 'string'.",
+    );
+    // the line with the error isn't evaluated
+    console.write_line("typeof b");
+    console.expect("\"undefined\"");
+    // redeclarations and object literals are allowed like without --check
+    console.write_line("const a = 'a';");
+    console.expect("undefined");
+    console.write_line("{ b: a }");
+    console.expect("{ b: \"a\" }");
+  });
+}
+
+#[test]
+fn pty_save_and_load() {
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  context
+    .new_command()
+    .args_vec(["repl"])
+    .with_pty(|mut console| {
+      console.write_line("const a = 1;");
+      console.expect("undefined");
+      console.write_line("throw new Error('failed');");
+      console.expect("Uncaught Error: failed");
+      console.write_line("const b = a + 1;");
+      console.expect("undefined");
+      console.write_line(".save session.ts");
+      console.expect("Saved 2 input(s) to 'session.ts'");
+    });
+  temp_dir
+    .path()
+    .join("session.ts")
+    .assert_matches_text("const a = 1;\nconst b = a + 1;\n");
+
+  context
+    .new_command()
+    .args_vec(["repl"])
+    .with_pty(|mut console| {
+      console.write_line(".load session.ts");
+      console.expect("undefined");
+      console.write_line("b");
+      console.expect("2");
+      console.write_line(".load missing.ts");
+      console.expect("error: Failed to read 'missing.ts'");
+    });
+}
+
+#[test]
+fn pty_help_command() {
+  util::with_pty(&["repl"], |mut console| {
+    console.write_line(".help");
+    console.expect_all(&[
+      ".editor",
+      "Enter editor mode to write multiple lines",
+      ".save <file>",
+      ":type <expr>",
+    ]);
+    console.write_line(".foo");
+    console.expect(
+      "error: Unknown command '.foo'. Type .help for the list of commands.",
+    );
+    // numbers aren't commands
+    console.write_line(".5 + 1");
+    console.expect("1.5");
+  });
+}
+
+#[cfg(unix)]
+#[test]
+fn pty_editor_command() {
+  util::with_pty(&["repl"], |mut console| {
+    console.write_line(".editor");
+    console.expect("// Entering editor mode");
+    console.write_line("const a = 1");
+    console.write_line("a + 1");
+    // ctrl+d
+    console.write_raw("\u{4}");
+    console.expect("2");
+    console.write_line("a");
+    console.expect("1");
+  });
+}
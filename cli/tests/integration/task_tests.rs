@@ -64,6 +64,58 @@ fn task_emoji() {
     });
 }
 
+itest!(task_depends_on {
+  args: "task --config task/depends_on/deno.json build",
+  output: "task/depends_on/build.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(task_depends_on_concurrent {
+  args: "task --config task/depends_on/deno.json all",
+  output: "task/depends_on/all.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(task_depends_on_failure {
+  args: "task --config task/depends_on/deno.json after_fail",
+  output: "task/depends_on/after_fail.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 3,
+});
+
+itest!(task_depends_on_cycle {
+  args: "task --config task/depends_on/deno.json cycle_a",
+  output: "task/depends_on/cycle.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 1,
+});
+
+itest!(task_parallel {
+  args: "task --config task/depends_on/deno.json --parallel a b",
+  output: "task/depends_on/all.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(task_recursive {
+  args: "task --config task/workspace/deno.json --recursive build",
+  output: "task/workspace/build.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(task_recursive_filter {
+  args:
+    "task --config task/workspace/deno.json --recursive --filter *app build",
+  output: "task/workspace/build_filter.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(task_recursive_failure {
+  args: "task --config task/workspace/deno.json --recursive test",
+  output: "task/workspace/test.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 3,
+});
+
 itest!(task_boolean_logic {
   args: "task -q --config task/deno_json/deno.json boolean_logic",
   output: "task/deno_json/task_boolean_logic.out",
@@ -355,3 +407,40 @@ fn task_byonm() {
 "#,
     );
 }
+
+#[test]
+fn task_skips_up_to_date_inputs() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir.join("deno.json").write_json(&json!({
+    "tasks": {
+      "gen": {
+        "command": "echo generated > out.txt",
+        "inputs": ["in.txt"],
+        "outputs": ["out.txt"]
+      }
+    }
+  }));
+  temp_dir.join("in.txt").write("1");
+
+  let run_gen = || {
+    context
+      .new_command()
+      .env("NO_COLOR", "1")
+      .args_vec(["task", "gen"])
+      .run()
+  };
+  run_gen().assert_matches_text("Task gen echo generated > out.txt\n");
+  assert!(temp_dir.join("out.txt").exists());
+  run_gen().assert_matches_text("Task gen (up to date)\n");
+
+  // changing an input runs the task again
+  temp_dir.join("in.txt").write("2");
+  run_gen().assert_matches_text("Task gen echo generated > out.txt\n");
+  run_gen().assert_matches_text("Task gen (up to date)\n");
+
+  // so does removing an output
+  temp_dir.join("out.txt").remove_file();
+  run_gen().assert_matches_text("Task gen echo generated > out.txt\n");
+  assert!(temp_dir.join("out.txt").exists());
+}
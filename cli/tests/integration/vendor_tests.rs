@@ -549,26 +549,33 @@ fn vendor_npm_node_specifiers() {
         "Download http://localhost:4545/npm/registry/@denotest/esm-basic\n",
         "Download http://localhost:4545/npm/registry/@denotest/esm-basic/1.0.0.tgz\n",
         "{}\n",
+        "{}\n",
         "Initialize @denotest/esm-basic@1.0.0\n",
         "{}\n\n",
         "{}\n",
       ),
       vendored_text("1 module", "vendor/"),
+      vendored_npm_dir_text("1 npm package", "vendor/"),
       vendored_npm_package_text("1 npm package"),
       success_text_updated_deno_json("vendor/")
     )
   );
+  let npm_package_dir = temp_dir
+    .path()
+    .join("vendor/npm/localhost_4545/@denotest/esm-basic");
+  assert!(npm_package_dir.join("registry.json").exists());
+  assert!(npm_package_dir.join("1.0.0/package.json").exists());
   let output = context.new_command().args("run -A my_app.ts").run();
   output.assert_matches_text("true 5\n");
   assert!(temp_dir.path().join("node_modules").exists());
   assert!(temp_dir.path().join("deno.lock").exists());
 
   // now try re-vendoring with a lockfile
-  let output = context.new_command().args("vendor --force my_app.ts").run();
+  let output = context.new_command().args("vendor my_app.ts").run();
   output.assert_matches_text(format!(
     "{}\n{}\n\n{}\n",
-    ignoring_import_map_text(),
     vendored_text("1 module", "vendor/"),
+    vendored_npm_dir_text("1 npm package", "vendor/"),
     success_text_updated_deno_json("vendor/"),
   ));
 
@@ -582,8 +589,8 @@ fn vendor_npm_node_specifiers() {
     .run();
   output.assert_matches_text(format!(
     "{}\n{}\n\n{}\n",
-    ignoring_import_map_text(),
     vendored_text("1 module", "vendor/"),
+    vendored_npm_dir_text("1 npm package", "vendor/"),
     success_text_updated_deno_json("vendor/")
   ));
   assert!(!temp_dir.path().join("node_modules").exists());
@@ -597,8 +604,9 @@ fn vendor_npm_node_specifiers() {
     .args("vendor --node-modules-dir --force my_app.ts")
     .run();
   output.assert_matches_text(format!(
-    "Initialize @denotest/esm-basic@1.0.0\n{}\n\n{}\n",
+    "Initialize @denotest/esm-basic@1.0.0\n{}\n{}\n\n{}\n",
     vendored_text("1 module", "vendor/"),
+    vendored_npm_dir_text("1 npm package", "vendor/"),
     use_import_map_text("vendor/")
   ));
 }
@@ -624,15 +632,67 @@ fn vendor_only_npm_specifiers() {
         "Download http://localhost:4545/npm/registry/@denotest/esm-basic\n",
         "Download http://localhost:4545/npm/registry/@denotest/esm-basic/1.0.0.tgz\n",
         "{}\n",
+        "{}\n",
         "Initialize @denotest/esm-basic@1.0.0\n",
+        "{}\n\n",
         "{}\n",
       ),
       vendored_text("0 modules", "vendor/"),
+      vendored_npm_dir_text("1 npm package", "vendor/"),
       vendored_npm_package_text("1 npm package"),
+      success_text_updated_deno_json("vendor/"),
     )
   );
 }
 
+#[test]
+fn revendor_updates_output_dir() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("my_app.ts", "import 'http://localhost:4545/vendor/mod.ts';");
+
+  let output = context.new_command().args("vendor my_app.ts").run();
+  output.assert_matches_text(format!(
+    concat!(
+      "Download http://localhost:4545/vendor/mod.ts\n",
+      "Download http://localhost:4545/vendor/logger.ts\n",
+      "{}\n",
+    ),
+    success_text("2 modules", "vendor/", true),
+  ));
+  assert!(temp_dir
+    .path()
+    .join("vendor/localhost_4545/vendor/mod.ts")
+    .exists());
+
+  // re-vendoring doesn't require `--force` and removes the unused modules,
+  // but leaves an npm directory it didn't create alone
+  temp_dir.write(
+    "my_app.ts",
+    "import 'http://localhost:4545/vendor/logger.ts';",
+  );
+  temp_dir.create_dir_all("vendor/npm");
+  temp_dir.write("vendor/npm/notes.txt", "");
+  let output = context.new_command().args("vendor my_app.ts").run();
+  output.assert_matches_text(format!(
+    "{}\nRemoved 1 file which is no longer used.\n\n{}\n",
+    vendored_text("1 module", "vendor/"),
+    use_import_map_text("vendor/"),
+  ));
+  assert!(!temp_dir
+    .path()
+    .join("vendor/localhost_4545/vendor/mod.ts")
+    .exists());
+  assert!(temp_dir
+    .path()
+    .join("vendor/localhost_4545/vendor/logger.ts")
+    .exists());
+  assert!(temp_dir.path().join("vendor/npm/notes.txt").exists());
+}
+
 fn success_text(module_count: &str, dir: &str, has_import_map: bool) -> String {
   let mut text = format!("Vendored {module_count} into {dir} directory.");
   if has_import_map {
@@ -670,6 +730,14 @@ fn vendored_npm_package_text(package_count: &str) -> String {
   )
 }
 
+fn vendored_npm_dir_text(package_count: &str, dir: &str) -> String {
+  format!(
+    "Vendored {} into {} directory.",
+    package_count,
+    PathBuf::from(dir).join("npm").display(),
+  )
+}
+
 fn success_text_updated_deno_json(dir: &str) -> String {
   format!(
     concat!(
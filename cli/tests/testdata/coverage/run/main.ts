@@ -0,0 +1,19 @@
+import { add } from "./math.ts";
+
+console.log(add(1, 2));
+
+const worker = new Worker(import.meta.resolve("./worker.ts"), {
+  type: "module",
+});
+await new Promise<void>((resolve) => {
+  worker.onmessage = (e) => {
+    console.log(e.data);
+    worker.terminate();
+    resolve();
+  };
+});
+
+const output = await new Deno.Command(Deno.execPath(), {
+  args: ["run", "--quiet", import.meta.resolve("./subprocess.ts")],
+}).output();
+console.log(new TextDecoder().decode(output.stdout).trim());
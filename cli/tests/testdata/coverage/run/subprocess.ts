@@ -0,0 +1,5 @@
+export function greet(name: string): string {
+  return `hello ${name} from subprocess`;
+}
+
+console.log(greet("main"));
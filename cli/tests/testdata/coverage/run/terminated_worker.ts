@@ -0,0 +1,9 @@
+const worker = new Worker(import.meta.resolve("./worker.ts"), {
+  type: "module",
+});
+worker.onmessage = (e) => {
+  console.log(e.data);
+  // nothing is left to run after the worker is terminated, so the process
+  // exits while the worker is still writing its coverage
+  worker.terminate();
+};
@@ -1,9 +1,11 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { assertEquals, assertThrows } from "./test_util.ts";
+import { assertEquals, assertRejects, assertThrows } from "./test_util.ts";
 
 // @ts-expect-error TypeScript (as of 3.7) does not support indexing namespaces by symbol
 const format = Deno[Deno.internal].jupyter.formatInner;
+// @ts-expect-error TypeScript (as of 3.7) does not support indexing namespaces by symbol
+const CommManager = Deno[Deno.internal].jupyter.CommManager;
 
 Deno.test("Deno.jupyter is not available", () => {
   assertThrows(
@@ -77,3 +79,70 @@ Deno.test(
     await assertFormattedAs(example, { "application/json": { x: 3 } });
   },
 );
+
+Deno.test("comms opened by the kernel receive messages", async () => {
+  const broadcasts: unknown[][] = [];
+  const comms = new CommManager((...args: unknown[]) => {
+    broadcasts.push(args);
+    return Promise.resolve();
+  });
+
+  const comm: Deno.jupyter.Comm = await comms.open("my_target", { a: 1 });
+  assertEquals(comm.targetName, "my_target");
+  assertEquals(broadcasts, [[
+    "comm_open",
+    { comm_id: comm.id, target_name: "my_target", data: { a: 1 } },
+    { metadata: {}, buffers: [] },
+  ]]);
+  assertEquals(comms.info(), { [comm.id]: { target_name: "my_target" } });
+  assertEquals(comms.info("other_target"), {});
+
+  const messages: Deno.jupyter.CommMessage[] = [];
+  comm.onMessage((message) => {
+    messages.push(message);
+  });
+  await comms.handleMessage(comm.id, { b: 2 }, [[1, 2, 3]]);
+  assertEquals(messages, [
+    { data: { b: 2 }, buffers: [new Uint8Array([1, 2, 3])] },
+  ]);
+
+  let closed = false;
+  comm.onClose(() => {
+    closed = true;
+  });
+  await comms.handleClose(comm.id, {});
+  assertEquals(closed, true);
+  assertEquals(comm.closed, true);
+  assertEquals(comms.info(), {});
+  await assertRejects(() => comm.send({}), Error, "is closed");
+});
+
+Deno.test("comms opened by the frontend are passed to targets", async () => {
+  const broadcasts: unknown[][] = [];
+  const comms = new CommManager((...args: unknown[]) => {
+    broadcasts.push(args);
+    return Promise.resolve();
+  });
+
+  assertEquals(await comms.handleOpen("id1", "my_target", {}, []), false);
+
+  comms.registerTarget(
+    "my_target",
+    (comm: Deno.jupyter.Comm, message: Deno.jupyter.CommMessage) => {
+      comm.onMessage(async ({ data }) => {
+        await comm.send({ echo: data });
+      });
+      assertEquals(message, { data: { a: 1 }, buffers: [] });
+    },
+  );
+  assertEquals(
+    await comms.handleOpen("id2", "my_target", { a: 1 }, []),
+    true,
+  );
+  await comms.handleMessage("id2", { b: 2 }, []);
+  assertEquals(broadcasts, [[
+    "comm_msg",
+    { comm_id: "id2", data: { echo: { b: 2 } } },
+    { metadata: {}, buffers: [] },
+  ]]);
+});
@@ -232,6 +232,9 @@ mod test {
         target: Some("x86_64-unknown-linux-gnu".to_string()),
         no_terminal: false,
         include: vec![],
+        include_dir: vec![],
+        compress: false,
+        obfuscate: false,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -253,6 +256,9 @@ mod test {
         args: Vec::new(),
         target: Some("x86_64-pc-windows-msvc".to_string()),
         include: vec![],
+        include_dir: vec![],
+        compress: false,
+        obfuscate: false,
         no_terminal: false,
       },
       &std::env::current_dir().unwrap(),
@@ -0,0 +1,167 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Coverage of the lines which were changed by a diff, so that untested new
+//! code isn't hidden by the coverage of the rest of a code base.
+
+use super::CoverageReport;
+use crate::args::CoverageDiffBase;
+use crate::colors;
+use crate::util::fs::canonicalize_path;
+use crate::util::git_diff::ChangedLines;
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+use std::path::Path;
+
+pub fn resolve_changed_lines(
+  diff_base: &CoverageDiffBase,
+  cwd: &Path,
+) -> Result<ChangedLines, AnyError> {
+  match diff_base {
+    CoverageDiffBase::GitRef(git_ref) => ChangedLines::from_git(cwd, git_ref),
+    CoverageDiffBase::File(path) => {
+      let diff =
+        std::fs::read_to_string(cwd.join(path)).with_context(|| {
+          format!("Failed reading diff file '{}'", path.display())
+        })?;
+      let root = canonicalize_path(cwd)?;
+      Ok(ChangedLines::from_unified_diff(&diff, &root))
+    }
+  }
+}
+
+struct FileDiffCoverage {
+  display_path: String,
+  covered: usize,
+  /// Zero based line indexes.
+  uncovered_lines: Vec<usize>,
+}
+
+/// Prints the coverage of the changed lines of each file to stderr, so it
+/// doesn't end up in the output of the reporters, and returns an error when
+/// it's below the threshold.
+pub fn report_diff_coverage(
+  reports: &[CoverageReport],
+  changed_lines: &ChangedLines,
+  cwd: &Path,
+  maybe_threshold: Option<u8>,
+) -> Result<(), AnyError> {
+  let cwd = canonicalize_path(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+  let mut files = Vec::new();
+  for report in reports {
+    let Some(path) = report
+      .url
+      .to_file_path()
+      .ok()
+      .and_then(|path| canonicalize_path(&path).ok())
+    else {
+      continue;
+    };
+    let Some(ranges) = changed_lines.get(&path) else {
+      continue;
+    };
+    let mut covered = 0;
+    let mut uncovered_lines = Vec::new();
+    for (line_index, count) in &report.found_lines {
+      if !ranges.iter().any(|range| range.contains(line_index)) {
+        continue;
+      }
+      if *count > 0 {
+        covered += 1;
+      } else {
+        uncovered_lines.push(*line_index);
+      }
+    }
+    if covered + uncovered_lines.len() == 0 {
+      continue;
+    }
+    let display_path = path
+      .strip_prefix(&cwd)
+      .unwrap_or(&path)
+      .display()
+      .to_string()
+      .replace('\\', "/");
+    files.push(FileDiffCoverage {
+      display_path,
+      covered,
+      uncovered_lines,
+    });
+  }
+
+  let covered = files.iter().map(|f| f.covered).sum::<usize>();
+  let total =
+    covered + files.iter().map(|f| f.uncovered_lines.len()).sum::<usize>();
+  eprintln!();
+  if total == 0 {
+    eprintln!(
+      "{} Diff coverage: none of the changed lines have coverage data. The paths in the diff need to be relative to the current directory and, like in the output of git diff, prefixed with \"b/\".",
+      colors::yellow("Warning")
+    );
+    if let Some(threshold) = maybe_threshold {
+      return Err(generic_error(format!(
+        "Diff coverage can't be checked against the threshold of {threshold}% without changed lines that have coverage data"
+      )));
+    }
+    return Ok(());
+  }
+  let percent = covered as f64 / total as f64 * 100.0;
+  eprintln!(
+    "Diff coverage: {:.1}% ({}/{} changed lines)",
+    percent, covered, total
+  );
+  files.sort_by(|a, b| a.display_path.cmp(&b.display_path));
+  for file in &files {
+    if file.uncovered_lines.is_empty() {
+      continue;
+    }
+    eprintln!(
+      " {} uncovered lines {}",
+      colors::red(&file.display_path),
+      format_line_ranges(&file.uncovered_lines)
+    );
+  }
+
+  if let Some(threshold) = maybe_threshold {
+    if percent < threshold as f64 {
+      return Err(generic_error(format!(
+        "Diff coverage of {percent:.1}% is below the threshold of {threshold}%"
+      )));
+    }
+  }
+  Ok(())
+}
+
+/// Formats sorted zero based line indexes as one based line ranges,
+/// like "3-5, 9".
+fn format_line_ranges(line_indexes: &[usize]) -> String {
+  let mut ranges: Vec<(usize, usize)> = Vec::new();
+  for line_index in line_indexes {
+    match ranges.last_mut() {
+      Some((_, end)) if *end + 1 == *line_index => *end = *line_index,
+      _ => ranges.push((*line_index, *line_index)),
+    }
+  }
+  ranges
+    .into_iter()
+    .map(|(start, end)| {
+      if start == end {
+        format!("{}", start + 1)
+      } else {
+        format!("{}-{}", start + 1, end + 1)
+      }
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_format_line_ranges() {
+    assert_eq!(format_line_ranges(&[]), "");
+    assert_eq!(format_line_ranges(&[0]), "1");
+    assert_eq!(format_line_ranges(&[2, 3, 4, 8, 10, 11]), "3-5, 9, 11-12");
+  }
+}
@@ -20,11 +20,16 @@ use deno_core::anyhow::anyhow;
 use deno_core::anyhow::Context;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
+use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::FutureExt;
+use deno_core::parking_lot::Condvar;
+use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
 use deno_core::sourcemap::SourceMap;
 use deno_core::url::Url;
 use deno_core::LocalInspectorSession;
 use deno_core::ModuleCodeString;
+use deno_runtime::web_worker::WebWorkerCoverageCollector;
 use regex::Regex;
 use std::fs;
 use std::fs::File;
@@ -32,23 +37,77 @@ use std::io::BufWriter;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 use text_lines::TextLines;
 use uuid::Uuid;
 
+mod diff;
 mod merge;
 mod range_tree;
 mod reporter;
+mod threshold;
 mod util;
 use merge::ProcessCoverage;
 
+/// Counts the web workers whose coverage hasn't been written yet. A worker
+/// that is terminated writes its coverage on its own thread after its host
+/// has moved on, so the main worker waits for them before writing its own
+/// coverage, as the process could otherwise exit first.
+#[derive(Debug, Default)]
+pub struct PendingWorkerCoverage {
+  count: Mutex<usize>,
+  written: Condvar,
+}
+
+impl PendingWorkerCoverage {
+  fn add(&self) {
+    *self.count.lock() += 1;
+  }
+
+  fn remove(&self) {
+    let mut count = self.count.lock();
+    *count -= 1;
+    if *count == 0 {
+      self.written.notify_all();
+    }
+  }
+
+  /// Blocks until all web workers have written their coverage. The execution
+  /// of a terminated worker is always stopped, so this doesn't wait for code
+  /// that keeps running.
+  fn wait(&self) {
+    let mut count = self.count.lock();
+    self.written.wait_while(&mut count, |count| *count > 0);
+  }
+}
+
 pub struct CoverageCollector {
   pub dir: PathBuf,
   session: LocalInspectorSession,
+  maybe_pending_worker_coverage: Option<Arc<PendingWorkerCoverage>>,
 }
 
 impl CoverageCollector {
   pub fn new(dir: PathBuf, session: LocalInspectorSession) -> Self {
-    Self { dir, session }
+    Self {
+      dir,
+      session,
+      maybe_pending_worker_coverage: None,
+    }
+  }
+
+  /// Creates a collector which waits for the coverage of the web workers
+  /// before writing its own.
+  pub fn new_for_main_worker(
+    dir: PathBuf,
+    session: LocalInspectorSession,
+    pending_worker_coverage: Arc<PendingWorkerCoverage>,
+  ) -> Self {
+    Self {
+      dir,
+      session,
+      maybe_pending_worker_coverage: Some(pending_worker_coverage),
+    }
   }
 
   async fn enable_debugger(&mut self) -> Result<(), AnyError> {
@@ -125,6 +184,9 @@ impl CoverageCollector {
   }
 
   pub async fn stop_collecting(&mut self) -> Result<(), AnyError> {
+    if let Some(pending_worker_coverage) = &self.maybe_pending_worker_coverage {
+      pending_worker_coverage.wait();
+    }
     fs::create_dir_all(&self.dir)?;
 
     let script_coverages = self.take_precise_coverage().await?.result;
@@ -158,6 +220,43 @@ impl CoverageCollector {
   }
 }
 
+/// Collects the coverage of a web worker, which is pending until the
+/// collector is dropped after the coverage was written.
+pub struct WorkerCoverageCollector {
+  collector: CoverageCollector,
+  pending_worker_coverage: Arc<PendingWorkerCoverage>,
+}
+
+impl WorkerCoverageCollector {
+  pub fn new(
+    dir: PathBuf,
+    session: LocalInspectorSession,
+    pending_worker_coverage: Arc<PendingWorkerCoverage>,
+  ) -> Self {
+    pending_worker_coverage.add();
+    Self {
+      collector: CoverageCollector::new(dir, session),
+      pending_worker_coverage,
+    }
+  }
+}
+
+impl Drop for WorkerCoverageCollector {
+  fn drop(&mut self) {
+    self.pending_worker_coverage.remove();
+  }
+}
+
+impl WebWorkerCoverageCollector for WorkerCoverageCollector {
+  fn start_collecting(&mut self) -> LocalBoxFuture<'_, Result<(), AnyError>> {
+    self.collector.start_collecting().boxed_local()
+  }
+
+  fn stop_collecting(&mut self) -> LocalBoxFuture<'_, Result<(), AnyError>> {
+    self.collector.stop_collecting().boxed_local()
+  }
+}
+
 #[derive(Debug, Clone)]
 struct BranchCoverageItem {
   line_index: usize,
@@ -467,6 +566,15 @@ pub async fn cover_files(
   let file_fetcher = factory.file_fetcher()?;
   let cli_options = factory.cli_options();
   let emitter = factory.emitter()?;
+  let thresholds =
+    cli_options.resolve_coverage_thresholds(coverage_flags.thresholds)?;
+  let maybe_changed_lines = coverage_flags
+    .diff_base
+    .as_ref()
+    .map(|diff_base| {
+      diff::resolve_changed_lines(diff_base, cli_options.initial_cwd())
+    })
+    .transpose()?;
 
   assert!(!coverage_flags.files.include.is_empty());
 
@@ -498,8 +606,6 @@ pub async fn cover_files(
     vec![]
   };
 
-  let mut reporter = reporter::create(coverage_flags.r#type);
-
   let out_mode = match coverage_flags.output {
     Some(ref path) => match File::create(path) {
       Ok(_) => Some(PathBuf::from(path)),
@@ -510,6 +616,9 @@ pub async fn cover_files(
     None => None,
   };
 
+  let mut reporter = reporter::create(coverage_flags.r#type, out_mode.clone());
+
+  let mut coverage_reports = Vec::new();
   for script_coverage in script_coverages {
     let module_specifier = deno_core::resolve_url_or_path(
       &script_coverage.url,
@@ -572,10 +681,20 @@ pub async fn cover_files(
 
     if !coverage_report.found_lines.is_empty() {
       reporter.report(&coverage_report, &original_source)?;
+      coverage_reports.push(coverage_report);
     }
   }
 
   reporter.done(&coverage_root);
 
-  Ok(())
+  if let Some(changed_lines) = &maybe_changed_lines {
+    diff::report_diff_coverage(
+      &coverage_reports,
+      changed_lines,
+      cli_options.initial_cwd(),
+      coverage_flags.diff_threshold,
+    )?;
+  }
+
+  threshold::check_thresholds(&coverage_reports, &thresholds)
 }
@@ -5,7 +5,10 @@ use super::CoverageReport;
 use crate::args::CoverageType;
 use crate::colors;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use deno_core::url::Url;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
@@ -21,19 +24,76 @@ pub struct CoverageStats<'a> {
   pub line_miss: usize,
   pub branch_hit: usize,
   pub branch_miss: usize,
+  pub function_hit: usize,
+  pub function_miss: usize,
   pub parent: Option<String>,
   pub file_text: Option<String>,
   pub report: Option<&'a CoverageReport>,
 }
 
+impl<'a> CoverageStats<'a> {
+  /// Adds the covered and uncovered lines, branches and functions of the
+  /// report.
+  pub fn add_report(&mut self, report: &CoverageReport) {
+    self.line_hit += report
+      .found_lines
+      .iter()
+      .filter(|(_, count)| *count > 0)
+      .count();
+    self.line_miss += report
+      .found_lines
+      .iter()
+      .filter(|(_, count)| *count == 0)
+      .count();
+    self.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
+    self.branch_miss += report.branches.iter().filter(|b| !b.is_hit).count();
+    self.function_hit += report
+      .named_functions
+      .iter()
+      .filter(|f| f.execution_count > 0)
+      .count();
+    self.function_miss += report
+      .named_functions
+      .iter()
+      .filter(|f| f.execution_count == 0)
+      .count();
+  }
+
+  pub fn line_percent(&self) -> f64 {
+    percent(self.line_hit, self.line_miss)
+  }
+
+  pub fn branch_percent(&self) -> f64 {
+    percent(self.branch_hit, self.branch_miss)
+  }
+
+  pub fn function_percent(&self) -> f64 {
+    percent(self.function_hit, self.function_miss)
+  }
+}
+
+/// Nothing to cover counts as fully covered.
+fn percent(hit: usize, miss: usize) -> f64 {
+  if hit + miss == 0 {
+    100.0
+  } else {
+    hit as f64 / (hit + miss) as f64 * 100.0
+  }
+}
+
 type CoverageSummary<'a> = HashMap<String, CoverageStats<'a>>;
 
-pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
+pub fn create(
+  kind: CoverageType,
+  output: Option<PathBuf>,
+) -> Box<dyn CoverageReporter + Send> {
   match kind {
     CoverageType::Summary => Box::new(SummaryCoverageReporter::new()),
     CoverageType::Lcov => Box::new(LcovCoverageReporter::new()),
     CoverageType::Detailed => Box::new(DetailedCoverageReporter::new()),
     CoverageType::Html => Box::new(HtmlCoverageReporter::new()),
+    CoverageType::Cobertura => Box::new(CoberturaCoverageReporter::new(output)),
+    CoverageType::Json => Box::new(JsonCoverageReporter::new(output)),
   }
 }
 
@@ -77,19 +137,7 @@ pub trait CoverageReporter {
           ..CoverageStats::default()
         });
 
-        stats.line_hit += report
-          .found_lines
-          .iter()
-          .filter(|(_, count)| *count > 0)
-          .count();
-        stats.line_miss += report
-          .found_lines
-          .iter()
-          .filter(|(_, count)| *count == 0)
-          .count();
-        stats.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
-        stats.branch_miss +=
-          report.branches.iter().filter(|b| !b.is_hit).count();
+        stats.add_report(report);
 
         file_text = None;
         summary_path = path.parent();
@@ -229,12 +277,7 @@ impl CoverageReporter for LcovCoverageReporter {
     };
     let mut out_writer = out_mode?;
 
-    let file_path = coverage_report
-      .url
-      .to_file_path()
-      .ok()
-      .and_then(|p| p.to_str().map(|p| p.to_string()))
-      .unwrap_or_else(|| coverage_report.url.to_string());
+    let file_path = report_file_path(coverage_report);
     writeln!(out_writer, "SF:{file_path}")?;
 
     for function in &coverage_report.named_functions {
@@ -304,6 +347,294 @@ impl CoverageReporter for LcovCoverageReporter {
   }
 }
 
+/// Writes the output of a reporter to the file of the `--output` flag or to
+/// stdout when there's no file.
+fn write_report_output(
+  maybe_output: Option<&PathBuf>,
+  text: &str,
+) -> Result<(), AnyError> {
+  match maybe_output {
+    Some(path) => fs::write(path, text)?,
+    None => {
+      let mut stdout = io::stdout();
+      stdout.write_all(text.as_bytes())?;
+      stdout.flush()?;
+    }
+  }
+  Ok(())
+}
+
+fn report_file_path(report: &CoverageReport) -> String {
+  report
+    .url
+    .to_file_path()
+    .ok()
+    .and_then(|p| p.to_str().map(|p| p.to_string()))
+    .unwrap_or_else(|| report.url.to_string())
+}
+
+/// Reports coverage in the Cobertura XML format, which is rendered by many
+/// CI systems.
+struct CoberturaCoverageReporter {
+  file_reports: Vec<CoverageReport>,
+  output: Option<PathBuf>,
+}
+
+impl CoberturaCoverageReporter {
+  pub fn new(output: Option<PathBuf>) -> CoberturaCoverageReporter {
+    CoberturaCoverageReporter {
+      file_reports: Vec::new(),
+      output,
+    }
+  }
+
+  fn create_xml(&self) -> String {
+    let mut totals = CoverageStats::default();
+    for report in &self.file_reports {
+      totals.add_report(report);
+    }
+    let maybe_root = util::find_root(
+      self.file_reports.iter().map(|report| &report.url).collect(),
+    );
+    let root_path = maybe_root
+      .as_ref()
+      .and_then(|root| root.to_file_path().ok())
+      .map(|root| root.to_string_lossy().to_string())
+      .unwrap_or_default();
+
+    // classes by package, where packages are directories relative to the root
+    let mut packages: BTreeMap<String, Vec<(String, &CoverageReport)>> =
+      BTreeMap::new();
+    for report in &self.file_reports {
+      let relative_path = maybe_root
+        .as_ref()
+        .and_then(|root| root.make_relative(&report.url))
+        .map(|path| {
+          percent_encoding::percent_decode_str(&path)
+            .decode_utf8_lossy()
+            .to_string()
+        })
+        .unwrap_or_else(|| report.url.to_string());
+      let package_name = match relative_path.rfind('/') {
+        Some(index) => relative_path[..index].to_string(),
+        None => ".".to_string(),
+      };
+      packages
+        .entry(package_name)
+        .or_default()
+        .push((relative_path, report));
+    }
+
+    let timestamp = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map(|d| d.as_millis())
+      .unwrap_or(0);
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" ?>\n");
+    xml.push_str("<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n");
+    xml.push_str(&format!(
+      "<coverage lines-valid=\"{}\" lines-covered=\"{}\" line-rate=\"{}\" branches-valid=\"{}\" branches-covered=\"{}\" branch-rate=\"{}\" timestamp=\"{}\" complexity=\"0\" version=\"0.1\">\n",
+      totals.line_hit + totals.line_miss,
+      totals.line_hit,
+      rate(totals.line_percent()),
+      totals.branch_hit + totals.branch_miss,
+      totals.branch_hit,
+      rate(totals.branch_percent()),
+      timestamp,
+    ));
+    xml.push_str(&format!(
+      "  <sources>\n    <source>{}</source>\n  </sources>\n",
+      escape_xml(&root_path)
+    ));
+    xml.push_str("  <packages>\n");
+    for (package_name, classes) in &packages {
+      let mut package_totals = CoverageStats::default();
+      for (_, report) in classes {
+        package_totals.add_report(report);
+      }
+      xml.push_str(&format!(
+        "    <package name=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">\n",
+        escape_xml(package_name),
+        rate(package_totals.line_percent()),
+        rate(package_totals.branch_percent()),
+      ));
+      xml.push_str("      <classes>\n");
+      for (relative_path, report) in classes {
+        self.write_class(&mut xml, relative_path, report);
+      }
+      xml.push_str("      </classes>\n");
+      xml.push_str("    </package>\n");
+    }
+    xml.push_str("  </packages>\n");
+    xml.push_str("</coverage>\n");
+    xml
+  }
+
+  fn write_class(
+    &self,
+    xml: &mut String,
+    relative_path: &str,
+    report: &CoverageReport,
+  ) {
+    let mut totals = CoverageStats::default();
+    totals.add_report(report);
+    let name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+    xml.push_str(&format!(
+      "        <class name=\"{}\" filename=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">\n",
+      escape_xml(name),
+      escape_xml(relative_path),
+      rate(totals.line_percent()),
+      rate(totals.branch_percent()),
+    ));
+
+    xml.push_str("          <methods>\n");
+    for function in &report.named_functions {
+      let line_number = function.line_index + 1;
+      xml.push_str(&format!(
+        "            <method name=\"{}\" signature=\"\" line-rate=\"{}\" branch-rate=\"0\">\n",
+        escape_xml(&function.name),
+        if function.execution_count > 0 { "1" } else { "0" },
+      ));
+      xml.push_str(&format!(
+        "              <lines>\n                <line number=\"{}\" hits=\"{}\"/>\n              </lines>\n",
+        line_number, function.execution_count,
+      ));
+      xml.push_str("            </method>\n");
+    }
+    xml.push_str("          </methods>\n");
+
+    // (hit, total) branches by line index
+    let mut line_branches: HashMap<usize, (usize, usize)> = HashMap::new();
+    for branch in &report.branches {
+      let entry = line_branches.entry(branch.line_index).or_default();
+      if branch.is_hit {
+        entry.0 += 1;
+      }
+      entry.1 += 1;
+    }
+
+    xml.push_str("          <lines>\n");
+    for (line_index, count) in &report.found_lines {
+      match line_branches.get(line_index) {
+        Some((hit, total)) => xml.push_str(&format!(
+          "            <line number=\"{}\" hits=\"{}\" branch=\"true\" condition-coverage=\"{}% ({}/{})\"/>\n",
+          line_index + 1,
+          count,
+          hit * 100 / total,
+          hit,
+          total,
+        )),
+        None => xml.push_str(&format!(
+          "            <line number=\"{}\" hits=\"{}\" branch=\"false\"/>\n",
+          line_index + 1,
+          count,
+        )),
+      }
+    }
+    xml.push_str("          </lines>\n");
+    xml.push_str("        </class>\n");
+  }
+}
+
+impl CoverageReporter for CoberturaCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    _file_text: &str,
+  ) -> Result<(), AnyError> {
+    self.file_reports.push(coverage_report.clone());
+    Ok(())
+  }
+
+  fn done(&mut self, _coverage_root: &Path) {
+    write_report_output(self.output.as_ref(), &self.create_xml()).unwrap();
+  }
+}
+
+/// Formats a percentage as a Cobertura rate between 0 and 1.
+fn rate(percent: f64) -> String {
+  format!("{:.4}", percent / 100.0)
+}
+
+fn escape_xml(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+/// Reports a machine readable summary of the line, branch and function
+/// coverage of each file.
+struct JsonCoverageReporter {
+  file_reports: Vec<CoverageReport>,
+  output: Option<PathBuf>,
+}
+
+impl JsonCoverageReporter {
+  pub fn new(output: Option<PathBuf>) -> JsonCoverageReporter {
+    JsonCoverageReporter {
+      file_reports: Vec::new(),
+      output,
+    }
+  }
+
+  fn create_json(&self) -> serde_json::Value {
+    fn totals_json(totals: &CoverageStats) -> serde_json::Value {
+      json!({
+        "lines": {
+          "covered": totals.line_hit,
+          "total": totals.line_hit + totals.line_miss,
+          "percent": totals.line_percent(),
+        },
+        "branches": {
+          "covered": totals.branch_hit,
+          "total": totals.branch_hit + totals.branch_miss,
+          "percent": totals.branch_percent(),
+        },
+        "functions": {
+          "covered": totals.function_hit,
+          "total": totals.function_hit + totals.function_miss,
+          "percent": totals.function_percent(),
+        },
+      })
+    }
+
+    let mut totals = CoverageStats::default();
+    let mut files = Vec::with_capacity(self.file_reports.len());
+    for report in &self.file_reports {
+      totals.add_report(report);
+      let mut file_totals = CoverageStats::default();
+      file_totals.add_report(report);
+      let mut file = totals_json(&file_totals);
+      file["file"] = json!(report_file_path(report));
+      files.push(file);
+    }
+    json!({
+      "total": totals_json(&totals),
+      "files": files,
+    })
+  }
+}
+
+impl CoverageReporter for JsonCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    _file_text: &str,
+  ) -> Result<(), AnyError> {
+    self.file_reports.push(coverage_report.clone());
+    Ok(())
+  }
+
+  fn done(&mut self, _coverage_root: &Path) {
+    let mut text = serde_json::to_string_pretty(&self.create_json()).unwrap();
+    text.push('\n');
+    write_report_output(self.output.as_ref(), &text).unwrap();
+  }
+}
+
 struct DetailedCoverageReporter {}
 
 impl DetailedCoverageReporter {
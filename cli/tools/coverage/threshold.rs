@@ -0,0 +1,75 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::reporter::CoverageStats;
+use super::CoverageReport;
+use crate::args::CoverageThresholds;
+use crate::colors;
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+
+/// Checks the coverage of the reports against the thresholds, printing each
+/// threshold which isn't met and returning an error if there are any.
+pub fn check_thresholds(
+  reports: &[CoverageReport],
+  thresholds: &CoverageThresholds,
+) -> Result<(), AnyError> {
+  if thresholds.is_empty() {
+    return Ok(());
+  }
+
+  let mut failures = Vec::new();
+  let mut totals = CoverageStats::default();
+  for report in reports {
+    totals.add_report(report);
+    if let Some(threshold) = thresholds.per_file {
+      let mut file_stats = CoverageStats::default();
+      file_stats.add_report(report);
+      let actual = file_stats.line_percent();
+      if actual < threshold as f64 {
+        failures.push(format!(
+          "Line coverage of {actual:.1}% is below the per file threshold of {threshold}%: {}",
+          display_url(report),
+        ));
+      }
+    }
+  }
+
+  for (kind, maybe_threshold, actual) in [
+    ("Line", thresholds.lines, totals.line_percent()),
+    ("Branch", thresholds.branches, totals.branch_percent()),
+    ("Function", thresholds.functions, totals.function_percent()),
+  ] {
+    if let Some(threshold) = maybe_threshold {
+      if actual < threshold as f64 {
+        failures.push(format!(
+          "{kind} coverage of {actual:.1}% is below the threshold of {threshold}%",
+        ));
+      }
+    }
+  }
+
+  if failures.is_empty() {
+    return Ok(());
+  }
+  for failure in &failures {
+    eprintln!("{} {}", colors::red("threshold"), failure);
+  }
+  Err(generic_error(format!(
+    "Coverage is below {} {}",
+    failures.len(),
+    if failures.len() == 1 {
+      "threshold"
+    } else {
+      "thresholds"
+    }
+  )))
+}
+
+fn display_url(report: &CoverageReport) -> String {
+  report
+    .url
+    .to_file_path()
+    .ok()
+    .map(|path| path.display().to_string())
+    .unwrap_or_else(|| report.url.to_string())
+}
@@ -1,7 +1,10 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 //! This module provides file formatting utilities using
-//! [`dprint-plugin-typescript`](https://github.com/dprint/dprint-plugin-typescript).
+//! [`dprint-plugin-typescript`](https://github.com/dprint/dprint-plugin-typescript),
+//! [`malva`](https://github.com/g-plane/malva),
+//! [`markup_fmt`](https://github.com/g-plane/markup_fmt), and
+//! [`pretty_yaml`](https://github.com/g-plane/pretty_yaml).
 //!
 //! At the moment it is only consumed using CLI but in
 //! the future it can be easily extended to provide
@@ -13,16 +16,18 @@ use crate::args::FmtFlags;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
 use crate::args::ProseWrap;
+use crate::args::UnstableFmtOptions;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::util::diff::diff;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::FileCollector;
+use crate::util::git_diff::ChangedLines;
+use crate::util::git_diff::LineRange;
 use crate::util::path::get_extension;
 use crate::util::text_encoding;
 use deno_ast::ParsedSource;
-use deno_config::glob::FilePatterns;
 use deno_core::anyhow::anyhow;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
@@ -34,6 +39,7 @@ use deno_core::unsync::spawn_blocking;
 use log::debug;
 use log::info;
 use log::warn;
+use std::borrow::Cow;
 use std::fs;
 use std::io::stdin;
 use std::io::stdout;
@@ -47,9 +53,16 @@ use std::sync::Arc;
 
 use crate::cache::IncrementalCache;
 
+mod ranges;
+
+pub use ranges::restrict_to_line_ranges;
+
 /// Format JavaScript/TypeScript files.
 pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
   if fmt_flags.is_stdin() {
+    if fmt_flags.changed_since.is_some() {
+      bail!("--changed-since can't be used when formatting standard input.");
+    }
     let cli_options = CliOptions::from_flags(flags)?;
     let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
     return format_stdin(
@@ -72,14 +85,13 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
           let factory = CliFactory::from_flags(flags).await?;
           let cli_options = factory.cli_options();
           let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
-          let files =
-            collect_fmt_files(fmt_options.files.clone()).and_then(|files| {
-              if files.is_empty() {
-                Err(generic_error("No target files found."))
-              } else {
-                Ok(files)
-              }
-            })?;
+          let files = collect_fmt_files(&fmt_options).and_then(|files| {
+            if files.is_empty() {
+              Err(generic_error("No target files found."))
+            } else {
+              Ok(files)
+            }
+          })?;
           let _ = watcher_communicator.watch_paths(files.clone());
           let refmt_files = if let Some(paths) = changed_paths {
             if fmt_options.check {
@@ -117,14 +129,13 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
     let factory = CliFactory::from_flags(flags).await?;
     let cli_options = factory.cli_options();
     let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
-    let files =
-      collect_fmt_files(fmt_options.files.clone()).and_then(|files| {
-        if files.is_empty() {
-          Err(generic_error("No target files found."))
-        } else {
-          Ok(files)
-        }
-      })?;
+    let files = collect_fmt_files(&fmt_options).and_then(|files| {
+      if files.is_empty() {
+        Err(generic_error("No target files found."))
+      } else {
+        Ok(files)
+      }
+    })?;
     format_files(factory, fmt_options, files).await?;
   }
 
@@ -138,28 +149,92 @@ async fn format_files(
 ) -> Result<(), AnyError> {
   let caches = factory.caches()?;
   let check = fmt_options.check;
+  let (paths, maybe_changed_lines) = match &fmt_options.changed_since {
+    Some(git_ref) => {
+      let changed_lines =
+        ChangedLines::from_git(factory.cli_options().initial_cwd(), git_ref)?;
+      let paths = paths
+        .into_iter()
+        .filter(|path| get_changed_line_ranges(&changed_lines, path).is_some())
+        .collect::<Vec<_>>();
+      (paths, Some(Arc::new(changed_lines)))
+    }
+    None => (paths, None),
+  };
   let incremental_cache = Arc::new(IncrementalCache::new(
     caches.fmt_incremental_cache_db(),
     &fmt_options.options,
     &paths,
   ));
   if check {
-    check_source_files(paths, fmt_options.options, incremental_cache.clone())
-      .await?;
+    check_source_files(
+      paths,
+      fmt_options.options,
+      fmt_options.unstable,
+      maybe_changed_lines,
+      incremental_cache.clone(),
+    )
+    .await?;
   } else {
-    format_source_files(paths, fmt_options.options, incremental_cache.clone())
-      .await?;
+    format_source_files(
+      paths,
+      fmt_options.options,
+      fmt_options.unstable,
+      maybe_changed_lines,
+      incremental_cache.clone(),
+    )
+    .await?;
   }
   incremental_cache.wait_completion().await;
   Ok(())
 }
 
-fn collect_fmt_files(files: FilePatterns) -> Result<Vec<PathBuf>, AnyError> {
-  FileCollector::new(|path, _| is_supported_ext_fmt(path))
-    .ignore_git_folder()
-    .ignore_node_modules()
-    .ignore_vendor_folder()
-    .collect_file_patterns(files)
+fn get_changed_line_ranges<'a>(
+  changed_lines: &'a ChangedLines,
+  file_path: &Path,
+) -> Option<&'a [LineRange]> {
+  canonicalize_path(file_path)
+    .ok()
+    .and_then(|path| changed_lines.get(&path))
+}
+
+/// Discards the formatting changes outside of the changed lines of the file,
+/// when only changed lines should be formatted.
+fn restrict_to_changed_lines(
+  file_path: &Path,
+  file_text: &str,
+  formatted_text: Option<String>,
+  maybe_changed_lines: Option<&ChangedLines>,
+  fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
+) -> Option<String> {
+  let formatted_text = formatted_text?;
+  match maybe_changed_lines {
+    Some(changed_lines) => {
+      let ranges = get_changed_line_ranges(changed_lines, file_path)?;
+      restrict_to_line_ranges(
+        file_path,
+        file_text,
+        &formatted_text,
+        ranges,
+        fmt_options,
+        unstable_options,
+      )
+    }
+    None => Some(formatted_text),
+  }
+}
+
+fn collect_fmt_files(
+  fmt_options: &FmtOptions,
+) -> Result<Vec<PathBuf>, AnyError> {
+  FileCollector::new(|path, _| {
+    is_supported_ext_fmt(path, &fmt_options.unstable)
+  })
+  .ignore_git_folder()
+  .ignore_node_modules()
+  .ignore_vendor_folder()
+  .collect_file_patterns(fmt_options.files.clone())
 }
 
 /// Formats markdown (using <https://github.com/dprint/dprint-plugin-markdown>) and its code blocks
@@ -232,11 +307,125 @@ pub fn format_json(
   dprint_plugin_json::format_text(file_path, file_text, &config)
 }
 
-/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, or IPYNB file.
+/// Formats CSS, SCSS, Sass, and Less using <https://github.com/g-plane/malva>.
+pub fn format_css(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted_str = malva::format_text(
+    file_text,
+    malva::detect_syntax(file_path).unwrap_or(malva::Syntax::Css),
+    &get_resolved_malva_config(fmt_options),
+  )
+  .map_err(AnyError::from)?;
+
+  Ok(if formatted_str == file_text {
+    None
+  } else {
+    Some(formatted_str)
+  })
+}
+
+/// Formats HTML using <https://github.com/g-plane/markup_fmt>. Scripts and
+/// styles embedded in the document are formatted with the formatter for
+/// their language.
+pub fn format_html(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
+) -> Result<Option<String>, AnyError> {
+  let formatted_str = markup_fmt::format_text(
+    file_text,
+    markup_fmt::Language::Html,
+    &get_resolved_markup_fmt_config(fmt_options),
+    |embedded_path, text, print_width| {
+      let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "deno_fmt_stdin".to_string());
+      // It's important to tell the formatter the file extension of the
+      // embedded code, so it's parsed with the right syntax.
+      let ext = embedded_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_default();
+      let fake_filename = PathBuf::from(format!("{}.{}", file_name, ext));
+      let mut embedded_options = fmt_options.clone();
+      embedded_options.line_width = Some(print_width as u32);
+      let maybe_formatted = match ext.as_str() {
+        "css" | "scss" | "sass" | "less" => {
+          format_css(&fake_filename, text, &embedded_options)?
+        }
+        _ => format_file(
+          &fake_filename,
+          text,
+          &embedded_options,
+          unstable_options,
+        )?,
+      };
+      Ok::<_, AnyError>(match maybe_formatted {
+        Some(formatted) => Cow::Owned(formatted),
+        None => Cow::Borrowed(text),
+      })
+    },
+  )
+  .map_err(|error| match error {
+    markup_fmt::FormatError::Syntax(error) => AnyError::from(error),
+    markup_fmt::FormatError::External(errors) => errors
+      .into_iter()
+      .next()
+      .unwrap_or_else(|| generic_error("Failed formatting embedded code")),
+  })?;
+
+  Ok(if formatted_str == file_text {
+    None
+  } else {
+    Some(formatted_str)
+  })
+}
+
+/// Formats YAML using <https://github.com/g-plane/pretty_yaml>.
+pub fn format_yaml(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  // YAML only has line comments, so support the same file level ignore
+  // comment as the other languages in the leading comment block
+  let ignore_file = file_text
+    .lines()
+    .take_while(|line| line.starts_with('#'))
+    .any(|line| {
+      line
+        .trim_start_matches('#')
+        .trim()
+        .starts_with("deno-fmt-ignore-file")
+    });
+  if ignore_file {
+    return Ok(None);
+  }
+
+  let formatted_str =
+    pretty_yaml::format_text(file_text, &get_resolved_yaml_config(fmt_options))
+      .map_err(AnyError::from)?;
+
+  Ok(if formatted_str == file_text {
+    None
+  } else {
+    Some(formatted_str)
+  })
+}
+
+/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, IPYNB, CSS, SCSS, SASS,
+/// LESS, HTML, YML, or YAML file. CSS, SCSS, SASS, LESS, HTML, YML, and YAML
+/// files are left as they are unless their formatting is enabled in the
+/// unstable options.
 pub fn format_file(
   file_path: &Path,
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
 ) -> Result<Option<String>, AnyError> {
   let ext = get_extension(file_path).unwrap_or_default();
 
@@ -245,10 +434,31 @@ pub fn format_file(
       format_markdown(file_text, fmt_options)
     }
     "json" | "jsonc" => format_json(file_path, file_text, fmt_options),
+    "css" | "scss" | "sass" | "less" => {
+      if unstable_options.css {
+        format_css(file_path, file_text, fmt_options)
+      } else {
+        Ok(None)
+      }
+    }
+    "html" | "htm" => {
+      if unstable_options.html {
+        format_html(file_path, file_text, fmt_options, unstable_options)
+      } else {
+        Ok(None)
+      }
+    }
+    "yml" | "yaml" => {
+      if unstable_options.yaml {
+        format_yaml(file_text, fmt_options)
+      } else {
+        Ok(None)
+      }
+    }
     "ipynb" => dprint_plugin_jupyter::format_text(
       file_text,
       |file_path: &Path, file_text: String| {
-        format_file(file_path, &file_text, fmt_options)
+        format_file(file_path, &file_text, fmt_options, unstable_options)
       },
     ),
     _ => {
@@ -271,6 +481,8 @@ pub fn format_parsed_source(
 async fn check_source_files(
   paths: Vec<PathBuf>,
   fmt_options: FmtOptionsConfig,
+  unstable_options: UnstableFmtOptions,
+  maybe_changed_lines: Option<Arc<ChangedLines>>,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let not_formatted_files_count = Arc::new(AtomicUsize::new(0));
@@ -291,7 +503,19 @@ async fn check_source_files(
         return Ok(());
       }
 
-      match format_file(&file_path, &file_text, &fmt_options) {
+      let format_result =
+        format_file(&file_path, &file_text, &fmt_options, &unstable_options)
+          .map(|formatted| {
+            restrict_to_changed_lines(
+              &file_path,
+              &file_text,
+              formatted,
+              maybe_changed_lines.as_deref(),
+              &fmt_options,
+              &unstable_options,
+            )
+          });
+      match format_result {
         Ok(Some(formatted_text)) => {
           not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
           let _g = output_lock.lock();
@@ -305,8 +529,11 @@ async fn check_source_files(
           // the file is the same since we don't bother checking for stable
           // formatting here. Additionally, ensure this is done during check
           // so that CIs that cache the DENO_DIR will get the benefit of
-          // incremental formatting
-          incremental_cache.update_file(&file_path, &file_text);
+          // incremental formatting. When only changed lines are checked, the
+          // rest of the file might not be formatted.
+          if maybe_changed_lines.is_none() {
+            incremental_cache.update_file(&file_path, &file_text);
+          }
         }
         Err(e) => {
           not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
@@ -352,6 +579,8 @@ async fn check_source_files(
 async fn format_source_files(
   paths: Vec<PathBuf>,
   fmt_options: FmtOptionsConfig,
+  unstable_options: UnstableFmtOptions,
+  maybe_changed_lines: Option<Arc<ChangedLines>>,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let formatted_files_count = Arc::new(AtomicUsize::new(0));
@@ -370,14 +599,31 @@ async fn format_source_files(
         return Ok(());
       }
 
-      match format_ensure_stable(
+      let format_result = format_ensure_stable(
         &file_path,
         &file_contents.text,
         &fmt_options,
-        format_file,
-      ) {
+        |file_path, file_text, fmt_options| {
+          format_file(file_path, file_text, fmt_options, &unstable_options)
+        },
+      )
+      .map(|formatted| {
+        restrict_to_changed_lines(
+          &file_path,
+          &file_contents.text,
+          formatted,
+          maybe_changed_lines.as_deref(),
+          &fmt_options,
+          &unstable_options,
+        )
+      });
+      match format_result {
         Ok(Some(formatted_text)) => {
-          incremental_cache.update_file(&file_path, &formatted_text);
+          // when only changed lines are formatted, the rest of the file
+          // might not be formatted
+          if maybe_changed_lines.is_none() {
+            incremental_cache.update_file(&file_path, &formatted_text);
+          }
           write_file_contents(
             &file_path,
             FileContents {
@@ -390,7 +636,9 @@ async fn format_source_files(
           info!("{}", file_path.to_string_lossy());
         }
         Ok(None) => {
-          incremental_cache.update_file(&file_path, &file_contents.text);
+          if maybe_changed_lines.is_none() {
+            incremental_cache.update_file(&file_path, &file_contents.text);
+          }
         }
         Err(e) => {
           let _g = output_lock.lock();
@@ -491,7 +739,12 @@ fn format_stdin(fmt_options: FmtOptions, ext: &str) -> Result<(), AnyError> {
     bail!("Failed to read from stdin");
   }
   let file_path = PathBuf::from(format!("_stdin.{ext}"));
-  let formatted_text = format_file(&file_path, &source, &fmt_options.options)?;
+  let formatted_text = format_file(
+    &file_path,
+    &source,
+    &fmt_options.options,
+    &fmt_options.unstable,
+  )?;
   if fmt_options.check {
     if formatted_text.is_some() {
       println!("Not formatted stdin");
@@ -599,6 +852,71 @@ fn get_resolved_json_config(
   builder.build()
 }
 
+fn get_resolved_malva_config(
+  options: &FmtOptionsConfig,
+) -> malva::config::FormatOptions {
+  use malva::config::*;
+
+  let layout = LayoutOptions {
+    print_width: options.line_width.unwrap_or(80) as usize,
+    use_tabs: options.use_tabs.unwrap_or_default(),
+    indent_width: options.indent_width.unwrap_or(2) as usize,
+    line_break: LineBreak::Lf,
+  };
+  let language = LanguageOptions {
+    quotes: if options.single_quote.unwrap_or_default() {
+      Quotes::PreferSingle
+    } else {
+      Quotes::PreferDouble
+    },
+    ..Default::default()
+  };
+
+  FormatOptions { layout, language }
+}
+
+fn get_resolved_markup_fmt_config(
+  options: &FmtOptionsConfig,
+) -> markup_fmt::config::FormatOptions {
+  use markup_fmt::config::*;
+
+  let layout = LayoutOptions {
+    print_width: options.line_width.unwrap_or(80) as usize,
+    use_tabs: options.use_tabs.unwrap_or_default(),
+    indent_width: options.indent_width.unwrap_or(2) as usize,
+    line_break: LineBreak::Lf,
+  };
+
+  FormatOptions {
+    layout,
+    language: LanguageOptions::default(),
+  }
+}
+
+fn get_resolved_yaml_config(
+  options: &FmtOptionsConfig,
+) -> pretty_yaml::config::FormatOptions {
+  use pretty_yaml::config::*;
+
+  // YAML doesn't allow tabs for indentation
+  let layout = LayoutOptions {
+    print_width: options.line_width.unwrap_or(80) as usize,
+    use_tabs: false,
+    indent_width: options.indent_width.unwrap_or(2) as usize,
+    line_break: LineBreak::Lf,
+  };
+  let language = LanguageOptions {
+    quotes: if options.single_quote.unwrap_or_default() {
+      Quotes::PreferSingle
+    } else {
+      Quotes::PreferDouble
+    },
+    ..Default::default()
+  };
+
+  FormatOptions { layout, language }
+}
+
 struct FileContents {
   text: String,
   had_bom: bool,
@@ -681,10 +999,16 @@ where
 
 /// This function is similar to is_supported_ext but adds additional extensions
 /// supported by `deno fmt`.
-fn is_supported_ext_fmt(path: &Path) -> bool {
-  get_extension(path).is_some_and(|ext| {
-    matches!(
-      ext.as_str(),
+fn is_supported_ext_fmt(
+  path: &Path,
+  unstable_options: &UnstableFmtOptions,
+) -> bool {
+  get_extension(path).is_some_and(|ext| match ext.as_str() {
+    "css" | "scss" | "sass" | "less" => unstable_options.css,
+    "html" | "htm" => unstable_options.html,
+    "yml" | "yaml" => unstable_options.yaml,
+    ext => matches!(
+      ext,
       "ts"
         | "tsx"
         | "js"
@@ -702,7 +1026,7 @@ fn is_supported_ext_fmt(path: &Path) -> bool {
         | "mdown"
         | "markdown"
         | "ipynb"
-    )
+    ),
   })
 }
 
@@ -712,30 +1036,65 @@ mod test {
 
   #[test]
   fn test_is_supported_ext_fmt() {
-    assert!(!is_supported_ext_fmt(Path::new("tests/subdir/redirects")));
-    assert!(is_supported_ext_fmt(Path::new("README.md")));
-    assert!(is_supported_ext_fmt(Path::new("readme.MD")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mkd")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mkdn")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mdwn")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mdown")));
-    assert!(is_supported_ext_fmt(Path::new("readme.markdown")));
-    assert!(is_supported_ext_fmt(Path::new("lib/typescript.d.ts")));
-    assert!(is_supported_ext_fmt(Path::new("testdata/run/001_hello.js")));
-    assert!(is_supported_ext_fmt(Path::new("testdata/run/002_hello.ts")));
-    assert!(is_supported_ext_fmt(Path::new("foo.jsx")));
-    assert!(is_supported_ext_fmt(Path::new("foo.tsx")));
-    assert!(is_supported_ext_fmt(Path::new("foo.TS")));
-    assert!(is_supported_ext_fmt(Path::new("foo.TSX")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JS")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JSX")));
-    assert!(is_supported_ext_fmt(Path::new("foo.mjs")));
-    assert!(!is_supported_ext_fmt(Path::new("foo.mjsx")));
-    assert!(is_supported_ext_fmt(Path::new("foo.jsonc")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JSONC")));
-    assert!(is_supported_ext_fmt(Path::new("foo.json")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JsON")));
-    assert!(is_supported_ext_fmt(Path::new("foo.ipynb")));
+    let stable = UnstableFmtOptions::default();
+    assert!(!is_supported_ext_fmt(
+      Path::new("tests/subdir/redirects"),
+      &stable
+    ));
+    assert!(is_supported_ext_fmt(Path::new("README.md"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.MD"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mkd"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mkdn"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mdwn"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mdown"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.markdown"), &stable));
+    assert!(is_supported_ext_fmt(
+      Path::new("lib/typescript.d.ts"),
+      &stable
+    ));
+    assert!(is_supported_ext_fmt(
+      Path::new("testdata/run/001_hello.js"),
+      &stable
+    ));
+    assert!(is_supported_ext_fmt(
+      Path::new("testdata/run/002_hello.ts"),
+      &stable
+    ));
+    assert!(is_supported_ext_fmt(Path::new("foo.jsx"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.tsx"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.TS"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.TSX"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JS"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JSX"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.mjs"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.mjsx"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.jsonc"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JSONC"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.json"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JsON"), &stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.ipynb"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.css"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.scss"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.sass"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.less"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.html"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.HTML"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.yml"), &stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.yaml"), &stable));
+
+    let unstable = UnstableFmtOptions {
+      css: true,
+      html: true,
+      yaml: true,
+    };
+    assert!(is_supported_ext_fmt(Path::new("foo.css"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.scss"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.sass"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.less"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.html"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.HTML"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.yml"), &unstable));
+    assert!(is_supported_ext_fmt(Path::new("foo.yaml"), &unstable));
   }
 
   #[test]
@@ -0,0 +1,162 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Support for only formatting some lines of a file, which allows adopting
+//! the formatter gradually in an existing code base.
+
+use crate::args::FmtOptionsConfig;
+use crate::args::UnstableFmtOptions;
+use crate::util::git_diff::LineRange;
+use dissimilar::Chunk;
+use log::debug;
+use std::path::Path;
+
+/// Takes the changes between the original and the formatted text which touch
+/// the provided lines of the original text, and discards the others.
+///
+/// Only applying some of the changes can produce text that no longer parses,
+/// so the result is formatted again and the original text is kept when that
+/// fails.
+///
+/// Returns `None` when none of the changes are in the ranges.
+pub fn restrict_to_line_ranges(
+  file_path: &Path,
+  original: &str,
+  formatted: &str,
+  ranges: &[LineRange],
+  fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
+) -> Option<String> {
+  struct PendingEdit<'a> {
+    start_line: usize,
+    removed: Vec<&'a str>,
+    inserted: Vec<&'a str>,
+  }
+
+  fn flush<'a>(
+    result: &mut String,
+    edit: Option<PendingEdit<'a>>,
+    end_line: usize,
+    ranges: &[LineRange],
+  ) {
+    let Some(edit) = edit else {
+      return;
+    };
+    let in_range = ranges.iter().any(|range| {
+      // an empty range is where lines were removed, which changes the
+      // lines before and after it by joining them
+      let (start, end) = if range.is_empty() {
+        (range.start.saturating_sub(1), range.end + 1)
+      } else {
+        (range.start, range.end)
+      };
+      start <= end_line && edit.start_line < end
+    });
+    let texts = if in_range {
+      edit.inserted
+    } else {
+      edit.removed
+    };
+    for text in texts {
+      result.push_str(text);
+    }
+  }
+
+  let mut result = String::with_capacity(formatted.len());
+  let mut line_index = 0;
+  let mut pending: Option<PendingEdit> = None;
+  for chunk in dissimilar::diff(original, formatted) {
+    match chunk {
+      Chunk::Equal(text) => {
+        flush(&mut result, pending.take(), line_index, ranges);
+        result.push_str(text);
+        line_index += text.matches('\n').count();
+      }
+      Chunk::Delete(text) => {
+        let edit = pending.get_or_insert_with(|| PendingEdit {
+          start_line: line_index,
+          removed: Vec::new(),
+          inserted: Vec::new(),
+        });
+        edit.removed.push(text);
+        line_index += text.matches('\n').count();
+      }
+      Chunk::Insert(text) => {
+        let edit = pending.get_or_insert_with(|| PendingEdit {
+          start_line: line_index,
+          removed: Vec::new(),
+          inserted: Vec::new(),
+        });
+        edit.inserted.push(text);
+      }
+    }
+  }
+  flush(&mut result, pending.take(), line_index, ranges);
+
+  if result == original {
+    return None;
+  }
+  if let Err(err) =
+    super::format_file(file_path, &result, fmt_options, unstable_options)
+  {
+    debug!(
+      "Discarded the formatting of the lines of {}: {:#}",
+      file_path.display(),
+      err
+    );
+    return None;
+  }
+  Some(result)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn restricts_changes_to_ranges() {
+    let original = "const a=1\nconst b=2\nconst c=3\n";
+    let formatted = "const a = 1;\nconst b = 2;\nconst c = 3;\n";
+    let restrict = |ranges: &[LineRange]| {
+      restrict_to_line_ranges(
+        Path::new("mod.ts"),
+        original,
+        formatted,
+        ranges,
+        &Default::default(),
+        &Default::default(),
+      )
+    };
+    assert_eq!(
+      restrict(&[1..2]).unwrap(),
+      "const a=1\nconst b = 2;\nconst c=3\n"
+    );
+    assert_eq!(
+      restrict(&[0..1, 2..3]).unwrap(),
+      "const a = 1;\nconst b=2\nconst c = 3;\n"
+    );
+    assert_eq!(restrict(&[0..usize::MAX]).unwrap(), formatted);
+    assert_eq!(
+      restrict(&[1..1]).unwrap(),
+      "const a = 1;\nconst b = 2;\nconst c=3\n"
+    );
+    assert_eq!(restrict(&[3..4]), None);
+  }
+
+  #[test]
+  fn keeps_original_when_restricted_changes_do_not_parse() {
+    // only removing the closing parenthesis would leave the opening one
+    let original = "const a = (1\n);\n";
+    let formatted = "const a = 1;\n";
+    assert_eq!(
+      restrict_to_line_ranges(
+        Path::new("mod.ts"),
+        original,
+        formatted,
+        &[1..2],
+        &Default::default(),
+        &Default::default(),
+      ),
+      None
+    );
+  }
+}
@@ -4,8 +4,11 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
 
 use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_core::serde_json;
@@ -20,23 +23,43 @@ use deno_npm::resolution::NpmResolutionSnapshot;
 use deno_npm::NpmPackageId;
 use deno_npm::NpmResolutionPackage;
 use deno_runtime::colors;
+use deno_runtime::permissions::PermissionsOptions;
 use deno_semver::npm::NpmPackageNvReference;
 use deno_semver::npm::NpmPackageReqReference;
 use deno_semver::package::PackageNv;
 
 use crate::args::Flags;
 use crate::args::InfoFlags;
+use crate::args::UnstableConfig;
 use crate::display;
 use crate::factory::CliFactory;
 use crate::graph_util::graph_lock_or_exit;
 use crate::npm::CliNpmResolver;
 use crate::npm::ManagedCliNpmResolver;
+use crate::standalone::inspect_standalone_binary;
+use crate::standalone::is_standalone_binary;
+use crate::standalone::StandaloneBinaryInfo;
 use crate::util::checksum;
 
 pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
   let factory = CliFactory::from_flags(flags).await?;
   let cli_options = factory.cli_options();
   if let Some(specifier) = info_flags.file {
+    let maybe_exe_path = cli_options.initial_cwd().join(&specifier);
+    if maybe_exe_path.is_file() && is_standalone_binary(&maybe_exe_path) {
+      return print_standalone_binary_info(
+        &maybe_exe_path,
+        info_flags.json,
+        info_flags.extract.as_deref(),
+      )
+      .await;
+    }
+    if info_flags.extract.is_some() {
+      bail!(
+        "--extract can only be used with an executable created by 'deno compile'."
+      );
+    }
+
     let module_graph_builder = factory.module_graph_builder().await?;
     let npm_resolver = factory.npm_resolver().await?;
     let maybe_lockfile = factory.maybe_lockfile();
@@ -163,6 +186,212 @@ fn print_cache_info(
   }
 }
 
+async fn print_standalone_binary_info(
+  exe_path: &Path,
+  json: bool,
+  maybe_extract_dir: Option<&Path>,
+) -> Result<(), AnyError> {
+  // the binary was checked to be a standalone binary
+  let info = inspect_standalone_binary(exe_path, maybe_extract_dir)
+    .await?
+    .unwrap();
+  if json {
+    display::write_json_to_stdout(&info)?;
+  } else {
+    let mut output = String::new();
+    write_standalone_binary_info(&info, &mut output)?;
+    display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+  }
+  if let Some(extract_dir) = maybe_extract_dir {
+    log::info!(
+      "{} {} modules to {}",
+      colors::green("Extracted"),
+      info.modules.len(),
+      extract_dir.display()
+    );
+  }
+  Ok(())
+}
+
+fn write_standalone_binary_info<TWrite: Write>(
+  info: &StandaloneBinaryInfo,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  writeln!(
+    writer,
+    "{} {}",
+    colors::bold("entrypoint:"),
+    info.entrypoint
+  )?;
+  writeln!(
+    writer,
+    "{} {}",
+    colors::bold("deno version:"),
+    info.deno_version
+  )?;
+  let permissions = permission_flags(&info.permissions);
+  writeln!(
+    writer,
+    "{} {}",
+    colors::bold("permissions:"),
+    if permissions.is_empty() {
+      "none".to_string()
+    } else {
+      permissions.join(" ")
+    }
+  )?;
+  if !info.argv.is_empty() {
+    writeln!(writer, "{} {}", colors::bold("args:"), info.argv.join(" "))?;
+  }
+  if !info.v8_flags.is_empty() {
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("v8 flags:"),
+      info.v8_flags.join(" ")
+    )?;
+  }
+  let unstable_flags = unstable_flags(&info.unstable_config);
+  if !unstable_flags.is_empty() {
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("unstable:"),
+      unstable_flags.join(" ")
+    )?;
+  }
+  if let Some(location) = &info.location {
+    writeln!(writer, "{} {}", colors::bold("location:"), location)?;
+  }
+  if info.compressed || info.obfuscated {
+    let encodings = [
+      (info.compressed, "compressed"),
+      (info.obfuscated, "obfuscated"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect::<Vec<_>>();
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("payload:"),
+      encodings.join(", ")
+    )?;
+  }
+
+  writeln!(writer)?;
+  writeln!(
+    writer,
+    "{} {} {}",
+    colors::bold("modules:"),
+    info.modules.len(),
+    maybe_size_to_text(Some(info.modules.iter().map(|m| m.size).sum()))
+  )?;
+  for module in &info.modules {
+    writeln!(
+      writer,
+      "  {} {}",
+      module.specifier,
+      maybe_size_to_text(Some(module.size))
+    )?;
+  }
+  if !info.npm_packages.is_empty() || info.npm_size > 0 {
+    writeln!(writer)?;
+    writeln!(
+      writer,
+      "{} {} {}",
+      colors::bold("npm packages:"),
+      info.npm_packages.len(),
+      maybe_size_to_text(Some(info.npm_size))
+    )?;
+    for package in &info.npm_packages {
+      writeln!(
+        writer,
+        "  npm:{} {}",
+        package.id,
+        maybe_size_to_text(package.size)
+      )?;
+    }
+  }
+  if !info.included_dirs.is_empty() {
+    writeln!(writer)?;
+    writeln!(writer, "{}", colors::bold("included directories:"))?;
+    for dir in &info.included_dirs {
+      writeln!(
+        writer,
+        "  {} {}",
+        dir.path.display(),
+        maybe_size_to_text(Some(dir.size))
+      )?;
+    }
+  }
+  Ok(())
+}
+
+/// Gets the flags which grant or deny the permissions.
+fn permission_flags(permissions: &PermissionsOptions) -> Vec<String> {
+  fn paths_to_strings(paths: &Option<Vec<PathBuf>>) -> Option<Vec<String>> {
+    paths.as_ref().map(|paths| {
+      paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect()
+    })
+  }
+
+  let mut flags = Vec::new();
+  if permissions.allow_hrtime {
+    flags.push("--allow-hrtime".to_string());
+  }
+  if permissions.deny_hrtime {
+    flags.push("--deny-hrtime".to_string());
+  }
+  let lists = [
+    ("allow-env", permissions.allow_env.clone()),
+    ("deny-env", permissions.deny_env.clone()),
+    ("allow-net", permissions.allow_net.clone()),
+    ("deny-net", permissions.deny_net.clone()),
+    ("allow-ffi", paths_to_strings(&permissions.allow_ffi)),
+    ("deny-ffi", paths_to_strings(&permissions.deny_ffi)),
+    ("allow-read", paths_to_strings(&permissions.allow_read)),
+    ("deny-read", paths_to_strings(&permissions.deny_read)),
+    ("allow-run", permissions.allow_run.clone()),
+    ("deny-run", permissions.deny_run.clone()),
+    ("allow-sys", permissions.allow_sys.clone()),
+    ("deny-sys", permissions.deny_sys.clone()),
+    ("allow-write", paths_to_strings(&permissions.allow_write)),
+    ("deny-write", paths_to_strings(&permissions.deny_write)),
+  ];
+  for (name, maybe_list) in lists {
+    match maybe_list {
+      Some(list) if list.is_empty() => flags.push(format!("--{name}")),
+      Some(list) => flags.push(format!("--{name}={}", list.join(","))),
+      None => {}
+    }
+  }
+  flags
+}
+
+fn unstable_flags(unstable_config: &UnstableConfig) -> Vec<String> {
+  let mut flags = Vec::new();
+  if unstable_config.legacy_flag_enabled {
+    flags.push("--unstable".to_string());
+  }
+  if unstable_config.bare_node_builtins {
+    flags.push("--unstable-bare-node-builtins".to_string());
+  }
+  if unstable_config.byonm {
+    flags.push("--unstable-byonm".to_string());
+  }
+  if unstable_config.sloppy_imports {
+    flags.push("--unstable-sloppy-imports".to_string());
+  }
+  for feature in &unstable_config.features {
+    flags.push(format!("--unstable-{feature}"));
+  }
+  flags
+}
+
 fn add_npm_packages_to_json(
   json: &mut serde_json::Value,
   npm_resolver: &dyn CliNpmResolver,
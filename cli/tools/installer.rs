@@ -1,20 +1,31 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::deno_registry_url;
+use crate::args::flags_from_vec;
+use crate::args::npm_registry_default_url;
 use crate::args::resolve_no_prompt;
 use crate::args::CaData;
+use crate::args::DenoSubcommand;
 use crate::args::Flags;
 use crate::args::InstallFlags;
+use crate::args::InstalledScriptsAction;
+use crate::args::InstalledScriptsFlags;
 use crate::args::TypeCheckMode;
+use crate::colors;
 use crate::factory::CliFactory;
 use crate::http_util::HttpClient;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 
 use deno_config::ConfigFlag;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
+use deno_core::serde_json;
 use deno_core::url::Url;
+use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
 use log::Level;
 use once_cell::sync::Lazy;
@@ -25,6 +36,7 @@ use std::fs;
 use std::fs::File;
 use std::io;
 use std::io::Write;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -127,6 +139,16 @@ fn get_installer_root() -> Result<PathBuf, io::Error> {
   Ok(home_path)
 }
 
+fn get_installation_dir(root: Option<&str>) -> Result<PathBuf, AnyError> {
+  let root = if let Some(root) = root {
+    let cwd = std::env::current_dir().context("Unable to get CWD")?;
+    canonicalize_path_maybe_not_exists(&cwd.join(root))?
+  } else {
+    get_installer_root()?
+  };
+  Ok(root.join("bin"))
+}
+
 pub async fn infer_name_from_url(url: &Url) -> Option<String> {
   // If there's an absolute url with no path, eg. https://my-cli.com
   // perform a request, and see if it redirects another file instead.
@@ -184,13 +206,7 @@ pub async fn infer_name_from_url(url: &Url) -> Option<String> {
 }
 
 pub fn uninstall(name: String, root: Option<String>) -> Result<(), AnyError> {
-  let cwd = std::env::current_dir().context("Unable to get CWD")?;
-  let root = if let Some(root) = root {
-    canonicalize_path_maybe_not_exists(&cwd.join(root))?
-  } else {
-    get_installer_root()?
-  };
-  let installation_dir = root.join("bin");
+  let installation_dir = get_installation_dir(root.as_deref())?;
 
   // ensure directory exists
   if let Ok(metadata) = fs::metadata(&installation_dir) {
@@ -312,12 +328,7 @@ async fn resolve_shim_data(
   install_flags: &InstallFlags,
 ) -> Result<ShimData, AnyError> {
   let cwd = std::env::current_dir().context("Unable to get CWD")?;
-  let root = if let Some(root) = &install_flags.root {
-    canonicalize_path_maybe_not_exists(&cwd.join(root))?
-  } else {
-    get_installer_root()?
-  };
-  let installation_dir = root.join("bin");
+  let installation_dir = get_installation_dir(install_flags.root.as_deref())?;
 
   // Check if module_url is remote
   let module_url = resolve_url_or_path(&install_flags.module_url, &cwd)?;
@@ -495,6 +506,406 @@ fn is_in_path(dir: &Path) -> bool {
   false
 }
 
+pub async fn installed_scripts_command(
+  installed_scripts_flags: InstalledScriptsFlags,
+) -> Result<(), AnyError> {
+  let InstalledScriptsFlags { action, root } = installed_scripts_flags;
+  let installation_dir = get_installation_dir(root.as_deref())?;
+  match action {
+    InstalledScriptsAction::List => list_installed_scripts(&installation_dir),
+    InstalledScriptsAction::Upgrade(names) => {
+      upgrade_installed_scripts(&installation_dir, root, names).await
+    }
+  }
+}
+
+/// A script installed by `deno install`, which is read from its shim.
+struct InstalledScript {
+  name: String,
+  module_url: String,
+  /// The arguments of `deno run` before the module url.
+  run_args: Vec<String>,
+  /// The arguments passed to the script.
+  script_args: Vec<String>,
+  flags: Flags,
+}
+
+/// Reads the installed script with the name from the shim for the shell,
+/// which is also written on Windows and is simpler to parse than the batch
+/// file. Returns `None` for executables not installed by `deno install`.
+fn read_installed_script(
+  installation_dir: &Path,
+  name: &str,
+) -> Result<Option<InstalledScript>, AnyError> {
+  let text = match fs::read_to_string(installation_dir.join(name)) {
+    Ok(text) => text,
+    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+    Err(err) => return Err(err.into()),
+  };
+  let Some(command) = text
+    .lines()
+    .skip_while(|line| *line != "# generated by deno install")
+    .nth(1)
+  else {
+    return Ok(None);
+  };
+  let command = command.strip_prefix("exec ").unwrap_or(command);
+  let mut args = split_shell_words(command)
+    .filter(|args| args.len() > 2 && args[0] == "deno" && args[1] == "run")
+    .ok_or_else(|| anyhow!("Failed reading the shim of {name}."))?;
+  if args.last().map(|arg| arg.as_str()) == Some("$@") {
+    args.pop();
+  }
+
+  let mut flags = flags_from_vec(args.clone()).map_err(|err| {
+    anyhow!("Failed parsing the flags in the shim of {name}: {err}")
+  })?;
+  let DenoSubcommand::Run(run_flags) = &flags.subcommand else {
+    bail!("Failed parsing the flags in the shim of {name}.");
+  };
+  let module_url = run_flags.script.clone();
+  let script_args = std::mem::take(&mut flags.argv);
+  let run_args = args[2..args.len() - script_args.len() - 1].to_vec();
+  Ok(Some(InstalledScript {
+    name: name.to_string(),
+    module_url,
+    run_args,
+    script_args,
+    flags,
+  }))
+}
+
+/// Splits a command written to a shim into its arguments, undoing the
+/// quoting of the arguments.
+fn split_shell_words(text: &str) -> Option<Vec<String>> {
+  let mut words = Vec::new();
+  let mut current: Option<String> = None;
+  let mut chars = text.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      ' ' | '\t' => words.extend(current.take()),
+      '\'' => {
+        let word = current.get_or_insert_with(String::new);
+        loop {
+          match chars.next()? {
+            '\'' => break,
+            c => word.push(c),
+          }
+        }
+      }
+      '"' => {
+        let word = current.get_or_insert_with(String::new);
+        loop {
+          match chars.next()? {
+            '"' => break,
+            // backslashes only escape these characters in double quotes
+            '\\' if matches!(chars.peek(), Some('$' | '`' | '"' | '\\')) => {
+              word.push(chars.next()?);
+            }
+            c => word.push(c),
+          }
+        }
+      }
+      '\\' => current.get_or_insert_with(String::new).push(chars.next()?),
+      c => current.get_or_insert_with(String::new).push(c),
+    }
+  }
+  words.extend(current);
+  Some(words)
+}
+
+fn installed_scripts(
+  installation_dir: &Path,
+) -> Result<Vec<InstalledScript>, AnyError> {
+  let read_dir = match fs::read_dir(installation_dir) {
+    Ok(read_dir) => read_dir,
+    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(err) => return Err(err.into()),
+  };
+  let mut names = Vec::new();
+  for entry in read_dir {
+    let entry = entry?;
+    let name = entry.file_name().to_string_lossy().to_string();
+    // this skips the hidden files and the batch files on Windows
+    if entry.file_type()?.is_file() && validate_name(&name).is_ok() {
+      names.push(name);
+    }
+  }
+  names.sort();
+
+  let mut scripts = Vec::with_capacity(names.len());
+  for name in names {
+    match read_installed_script(installation_dir, &name) {
+      Ok(Some(script)) => scripts.push(script),
+      Ok(None) => {}
+      Err(err) => log::warn!("{} {:#}", colors::yellow("Warning"), err),
+    }
+  }
+  Ok(scripts)
+}
+
+fn list_installed_scripts(installation_dir: &Path) -> Result<(), AnyError> {
+  let scripts = installed_scripts(installation_dir)?;
+  if scripts.is_empty() {
+    log::info!("No scripts are installed in {}", installation_dir.display());
+    return Ok(());
+  }
+  for script in scripts {
+    println!("{}", colors::green(&script.name));
+    println!("  url: {}", script.module_url);
+    if let Some(range) = module_url_version_range(&script.module_url) {
+      println!("  version: {}", &script.module_url[range]);
+    }
+    // --no-config is added when not installing with a config file
+    let flags = script
+      .run_args
+      .iter()
+      .filter(|arg| *arg != "--no-config")
+      .cloned()
+      .collect::<Vec<_>>();
+    if !flags.is_empty() {
+      println!("  flags: {}", flags.join(" "));
+    }
+    if !script.script_args.is_empty() {
+      println!("  args: {}", script.script_args.join(" "));
+    }
+  }
+  Ok(())
+}
+
+async fn upgrade_installed_scripts(
+  installation_dir: &Path,
+  root: Option<String>,
+  names: Vec<String>,
+) -> Result<(), AnyError> {
+  let scripts = if names.is_empty() {
+    installed_scripts(installation_dir)?
+  } else {
+    let mut scripts = Vec::with_capacity(names.len());
+    for name in names {
+      match read_installed_script(installation_dir, &name)? {
+        Some(script) => scripts.push(script),
+        None => bail!("No installation found for {name}"),
+      }
+    }
+    scripts
+  };
+  if scripts.is_empty() {
+    log::info!("No scripts are installed in {}", installation_dir.display());
+    return Ok(());
+  }
+
+  let mut failed_count = 0;
+  for script in scripts {
+    let name = script.name.clone();
+    if let Err(err) = upgrade_installed_script(script, root.clone()).await {
+      log::error!(
+        "{} Failed upgrading {}: {:#}",
+        colors::red_bold("error:"),
+        name,
+        err
+      );
+      failed_count += 1;
+    }
+  }
+  if failed_count > 0 {
+    bail!(
+      "Failed upgrading {} {}.",
+      failed_count,
+      if failed_count == 1 {
+        "script"
+      } else {
+        "scripts"
+      }
+    );
+  }
+  Ok(())
+}
+
+async fn upgrade_installed_script(
+  script: InstalledScript,
+  root: Option<String>,
+) -> Result<(), AnyError> {
+  let InstalledScript {
+    name,
+    module_url,
+    script_args,
+    mut flags,
+    ..
+  } = script;
+  let new_module_url = match module_url_version_range(&module_url) {
+    Some(range) => {
+      let latest_url = resolve_latest_module_url(&module_url, range).await?;
+      if latest_url == module_url {
+        log::info!("{} is up to date ({})", name, module_url);
+        return Ok(());
+      }
+      log::info!(
+        "{} {} from {} to {}",
+        colors::green("Upgrading"),
+        name,
+        module_url,
+        latest_url
+      );
+      latest_url
+    }
+    None => {
+      // without a version, the module is reloaded to get its latest version
+      log::info!(
+        "{} {} ({})",
+        colors::green("Reinstalling"),
+        name,
+        module_url
+      );
+      flags.reload = true;
+      module_url
+    }
+  };
+
+  // the lockfile would keep the previous versions, so an empty one is
+  // used instead, which only replaces the installed one on success
+  let temp_dir = tempfile::TempDir::new()?;
+  if flags.lock.is_some() {
+    let lock_path = temp_dir.path().join("lock.json");
+    fs::write(&lock_path, "{}")
+      .with_context(|| format!("error writing {}", lock_path.display()))?;
+    flags.lock = Some(lock_path);
+  }
+  // --seed is also passed to v8, which would add it to the v8 flags again
+  if flags.seed.is_some() {
+    flags
+      .v8_flags
+      .retain(|flag| !flag.starts_with("--random-seed="));
+  }
+  let install_flags = InstallFlags {
+    module_url: new_module_url,
+    args: script_args,
+    name: Some(name),
+    root,
+    force: true,
+  };
+  flags.subcommand = DenoSubcommand::Install(install_flags.clone());
+  install_command(flags, install_flags).await
+}
+
+/// Gets the range of the version in a module url, which is the version
+/// requirement of an npm or jsr specifier or the version of a
+/// `name@version` part of the path of a remote url.
+fn module_url_version_range(module_url: &str) -> Option<Range<usize>> {
+  if let Some(specifier) = module_url
+    .strip_prefix("npm:")
+    .or_else(|| module_url.strip_prefix("jsr:"))
+  {
+    let start = module_url.len() - specifier.trim_start_matches('/').len();
+    let name_and_rest = &module_url[start..];
+    // skip over the scope of the name
+    let name_start = match name_and_rest.strip_prefix('@') {
+      Some(rest) => name_and_rest.len() - rest.len() + rest.find('/')? + 1,
+      None => 0,
+    };
+    let at_index =
+      start + name_start + name_and_rest[name_start..].find('@')?;
+    if module_url[start + name_start..at_index].contains('/') {
+      // the @ is in the sub path
+      return None;
+    }
+    let version_start = at_index + 1;
+    let version_end = module_url[version_start..]
+      .find('/')
+      .map(|index| version_start + index)
+      .unwrap_or(module_url.len());
+    return Some(version_start..version_end);
+  }
+
+  let url = Url::parse(module_url).ok()?;
+  if !matches!(url.scheme(), "http" | "https") {
+    return None;
+  }
+  let path_start = module_url.find("://")? + 3;
+  let path_start = path_start + module_url[path_start..].find('/')?;
+  let path_end = module_url[path_start..]
+    .find(['?', '#'])
+    .map(|index| path_start + index)
+    .unwrap_or(module_url.len());
+  let mut segment_start = path_start + 1;
+  for segment in module_url[path_start + 1..path_end].split('/') {
+    match segment.find('@') {
+      Some(at_index) if at_index > 0 && at_index + 1 < segment.len() => {
+        return Some(
+          segment_start + at_index + 1..segment_start + segment.len(),
+        );
+      }
+      _ => segment_start += segment.len() + 1,
+    }
+  }
+  None
+}
+
+/// Resolves the module url with the latest version, which is the latest
+/// version of an npm or jsr package or where the url without a version
+/// redirects.
+async fn resolve_latest_module_url(
+  module_url: &str,
+  version_range: Range<usize>,
+) -> Result<String, AnyError> {
+  let client = HttpClient::new(None, None);
+  let maybe_latest =
+    if let Ok(req_ref) = NpmPackageReqReference::from_str(module_url) {
+      let name = &req_ref.req().name;
+      let package_url = npm_registry_default_url().join(name)?;
+      let package_info: serde_json::Value =
+        serde_json::from_str(&client.download_text(package_url).await?)?;
+      let Some(latest) = package_info["dist-tags"]["latest"].as_str() else {
+        bail!("Could not find the latest version of npm package '{name}'.");
+      };
+      Some(latest.to_string())
+    } else if let Ok(req_ref) = JsrPackageReqReference::from_str(module_url) {
+      let name = &req_ref.req().name;
+      let meta_url = deno_registry_url().join(&format!("{name}/meta.json"))?;
+      let package_meta: serde_json::Value =
+        serde_json::from_str(&client.download_text(meta_url).await?)?;
+      let Some(latest) = package_meta["latest"].as_str() else {
+        bail!("Could not find the latest version of jsr package '{name}'.");
+      };
+      Some(latest.to_string())
+    } else {
+      None
+    };
+  if let Some(latest) = maybe_latest {
+    // keep the operator of the version requirement
+    let version = &module_url[version_range.clone()];
+    let operator = if version.starts_with(['^', '~']) {
+      &version[..1]
+    } else {
+      ""
+    };
+    return Ok(format!(
+      "{}{}{}{}",
+      &module_url[..version_range.start],
+      operator,
+      latest,
+      &module_url[version_range.end..]
+    ));
+  }
+
+  // remove the @ along with the version
+  let unversioned_url = format!(
+    "{}{}",
+    &module_url[..version_range.start - 1],
+    &module_url[version_range.end..]
+  );
+  let response = client.get_redirected_response(&unversioned_url).await?;
+  let latest_url = response.url().to_string();
+  if latest_url == unversioned_url
+    || module_url_version_range(&latest_url).is_none()
+  {
+    bail!(
+      "Could not find the latest version of {module_url} because {unversioned_url} does not redirect to a versioned url."
+    );
+  }
+  Ok(latest_url)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1232,6 +1643,97 @@ mod tests {
     assert!(content.contains(&expected_string));
   }
 
+  #[tokio::test]
+  async fn read_installed_scripts() {
+    let temp_dir = TempDir::new();
+    let bin_dir = temp_dir.path().join("bin");
+    std::fs::create_dir(&bin_dir).unwrap();
+    let shim_data = resolve_shim_data(
+      &Flags {
+        allow_net: Some(vec![]),
+        allow_read: Some(vec![]),
+        type_check_mode: TypeCheckMode::None,
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      },
+      &InstallFlags {
+        module_url: "http://localhost:4545/echo_server.ts".to_string(),
+        args: vec!["--foobar".to_string(), "it's a test".to_string()],
+        name: Some("echo_test".to_string()),
+        root: Some(temp_dir.path().to_string()),
+        force: false,
+      },
+    )
+    .await
+    .unwrap();
+    generate_executable_file(&shim_data).unwrap();
+    // executables not installed by deno are ignored
+    std::fs::write(bin_dir.join("other"), "#!/bin/sh\necho other\n").unwrap();
+
+    let scripts = installed_scripts(bin_dir.as_path()).unwrap();
+    assert_eq!(scripts.len(), 1);
+    let script = &scripts[0];
+    assert_eq!(script.name, "echo_test");
+    assert_eq!(script.module_url, "http://localhost:4545/echo_server.ts");
+    assert_eq!(
+      script.run_args,
+      vec!["--allow-read", "--allow-net", "--quiet", "--no-config"]
+    );
+    assert_eq!(script.script_args, vec!["--foobar", "it's a test"]);
+    assert_eq!(script.flags.allow_net, Some(vec![]));
+    assert_eq!(script.flags.allow_read, Some(vec![]));
+  }
+
+  #[test]
+  fn split_shim_words() {
+    assert_eq!(
+      split_shell_words(
+        r#"exec deno 'run' "--seed" 1 'it'\''s' "a \"b\" C:\d" "$@""#
+      )
+      .unwrap(),
+      vec![
+        "exec",
+        "deno",
+        "run",
+        "--seed",
+        "1",
+        "it's",
+        "a \"b\" C:\\d",
+        "$@"
+      ]
+    );
+    assert_eq!(split_shell_words("deno 'run"), None);
+  }
+
+  #[test]
+  fn module_url_versions() {
+    fn version(module_url: &str) -> Option<&str> {
+      module_url_version_range(module_url).map(|range| &module_url[range])
+    }
+
+    assert_eq!(version("npm:cowsay@1.5.0"), Some("1.5.0"));
+    assert_eq!(version("npm:/cowsay@^1.5.0/cowthink"), Some("^1.5.0"));
+    assert_eq!(version("npm:@denotest/bin@~1.0.0/cli"), Some("~1.0.0"));
+    assert_eq!(version("npm:cowsay"), None);
+    assert_eq!(version("npm:@denotest/bin/cli@1"), None);
+    assert_eq!(
+      version("jsr:@std/http@^0.214.0/file_server"),
+      Some("^0.214.0")
+    );
+    assert_eq!(version("jsr:@std/http"), None);
+    assert_eq!(
+      version("https://deno.land/std@0.200.0/http/file_server.ts"),
+      Some("0.200.0")
+    );
+    assert_eq!(
+      version("https://deno.land/x/foo@v1.2.3/mod.ts?test=a@b"),
+      Some("v1.2.3")
+    );
+    assert_eq!(version("https://deno.land/std/http/file_server.ts"), None);
+    assert_eq!(version("https://example.com/@abc/mod.ts"), None);
+    assert_eq!(version("file:///dir/abc@1.0.0/mod.ts"), None);
+  }
+
   #[test]
   fn uninstall_basic() {
     let temp_dir = TempDir::new();
@@ -0,0 +1,289 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The history of the code executed by the kernel, which is kept in a file
+//! so `history_request` can also return the code of previous sessions.
+//!
+//! The file is shared by all the kernels, so it's locked while it's read or
+//! written.
+
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use fs3::FileExt;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The number of entries which are kept in the history file.
+const MAX_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct HistoryEntry {
+  session: u64,
+  /// The execution count, which starts at 1. The entry with line 0 of a
+  /// session marks its start, so other kernels don't use the same session.
+  line: u64,
+  input: String,
+}
+
+impl HistoryEntry {
+  fn to_line(&self) -> String {
+    format!("{}\n", serde_json::to_string(self).unwrap())
+  }
+}
+
+pub struct KernelHistory {
+  file_path: PathBuf,
+  session: u64,
+  entries: Vec<HistoryEntry>,
+  errored_on_save: bool,
+}
+
+impl KernelHistory {
+  /// Loads the history from the file, starting a new session after the
+  /// sessions which are in it.
+  pub fn load(file_path: PathBuf) -> Self {
+    let (session, entries, errored_on_save) = match start_session(&file_path) {
+      Ok((session, entries)) => (session, entries, false),
+      Err(err) => {
+        eprintln!("Unable to load history file: {err}");
+        (1, Vec::new(), true)
+      }
+    };
+    Self {
+      file_path,
+      session,
+      entries,
+      errored_on_save,
+    }
+  }
+
+  pub fn add(&mut self, line: u64, input: &str) {
+    let entry = HistoryEntry {
+      session: self.session,
+      line,
+      input: input.to_string(),
+    };
+    if let Err(err) = self.append_to_file(&entry) {
+      if !self.errored_on_save {
+        self.errored_on_save = true;
+        eprintln!("Unable to save history file: {err}");
+      }
+    }
+    self.entries.push(entry);
+  }
+
+  fn append_to_file(&self, entry: &HistoryEntry) -> std::io::Result<()> {
+    let mut file = open_locked(&self.file_path)?;
+    file.write_all(entry.to_line().as_bytes())
+  }
+
+  /// Gets the history which is returned for the content of a
+  /// `history_request`.
+  pub fn request(&self, content: &serde_json::Value) -> Vec<serde_json::Value> {
+    let n = content["n"].as_u64().map(|n| n as usize);
+    let entries = match content["hist_access_type"].as_str().unwrap_or("") {
+      "tail" => tail(self.entries.iter(), n.unwrap_or(10)),
+      "range" => {
+        // the current session is 0 and negative numbers count back from it
+        let session = match content["session"].as_i64().unwrap_or(0) {
+          session if session > 0 => session as u64,
+          session => self.session.saturating_sub(session.unsigned_abs()),
+        };
+        let start = content["start"].as_u64().unwrap_or(0);
+        let stop = content["stop"].as_u64().unwrap_or(u64::MAX);
+        self
+          .entries
+          .iter()
+          .filter(|entry| {
+            entry.session == session && entry.line >= start && entry.line < stop
+          })
+          .collect()
+      }
+      "search" => {
+        let Ok(pattern) =
+          glob::Pattern::new(content["pattern"].as_str().unwrap_or("*"))
+        else {
+          return Vec::new();
+        };
+        let mut entries = self
+          .entries
+          .iter()
+          .filter(|entry| pattern.matches(&entry.input))
+          .collect::<Vec<_>>();
+        if content["unique"].as_bool().unwrap_or(false) {
+          // keep the latest of the entries with the same input
+          let mut seen = std::collections::HashSet::new();
+          entries.reverse();
+          entries.retain(|entry| seen.insert(&entry.input));
+          entries.reverse();
+        }
+        match n {
+          Some(n) => tail(entries.into_iter(), n),
+          None => entries,
+        }
+      }
+      _ => Vec::new(),
+    };
+    let output = content["output"].as_bool().unwrap_or(false);
+    entries
+      .into_iter()
+      .map(|entry| {
+        if output {
+          // outputs aren't stored
+          json!([entry.session, entry.line, [entry.input, null]])
+        } else {
+          json!([entry.session, entry.line, entry.input])
+        }
+      })
+      .collect()
+  }
+}
+
+/// Opens the history file for reading and appending, waiting until no other
+/// kernel has it locked. The lock is released when the file is closed.
+fn open_locked(file_path: &Path) -> std::io::Result<std::fs::File> {
+  if let Some(parent) = file_path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  let file = std::fs::OpenOptions::new()
+    .read(true)
+    .append(true)
+    .create(true)
+    .open(file_path)?;
+  file.lock_exclusive()?;
+  Ok(file)
+}
+
+/// Reads the entries of the history file and records the start of a new
+/// session after the sessions in it, returning the session and the entries.
+fn start_session(
+  file_path: &Path,
+) -> std::io::Result<(u64, Vec<HistoryEntry>)> {
+  let mut file = open_locked(file_path)?;
+  let mut text = String::new();
+  file.read_to_string(&mut text)?;
+  let mut entries = text
+    .lines()
+    .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+    .collect::<Vec<_>>();
+  let session =
+    entries.iter().map(|entry| entry.session).max().unwrap_or(0) + 1;
+  let session_start = HistoryEntry {
+    session,
+    line: 0,
+    input: String::new(),
+  };
+  if entries.len() >= MAX_ENTRIES {
+    entries.drain(..entries.len() + 1 - MAX_ENTRIES);
+    entries.push(session_start);
+    let text = entries
+      .iter()
+      .map(HistoryEntry::to_line)
+      .collect::<String>();
+    // the file is opened for appending, so this writes from the start
+    file.set_len(0)?;
+    file.write_all(text.as_bytes())?;
+  } else {
+    file.write_all(session_start.to_line().as_bytes())?;
+  }
+  entries.retain(|entry| entry.line > 0);
+  Ok((session, entries))
+}
+
+fn tail<'a>(
+  entries: impl Iterator<Item = &'a HistoryEntry>,
+  n: usize,
+) -> Vec<&'a HistoryEntry> {
+  let entries = entries.collect::<Vec<_>>();
+  let start = entries.len().saturating_sub(n);
+  entries[start..].to_vec()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use test_util::TempDir;
+
+  #[test]
+  fn history_is_kept_across_sessions() {
+    let temp_dir = TempDir::new();
+    let file_path = temp_dir.path().join("history.jsonl").to_path_buf();
+
+    let mut history = KernelHistory::load(file_path.clone());
+    history.add(1, "const a = 1;");
+    history.add(2, "a + 1");
+
+    let mut history = KernelHistory::load(file_path);
+    history.add(1, "a + 2");
+
+    assert_eq!(
+      history.request(&json!({ "hist_access_type": "tail", "n": 2 })),
+      vec![json!([1, 2, "a + 1"]), json!([2, 1, "a + 2"])],
+    );
+    assert_eq!(
+      history.request(&json!({
+        "hist_access_type": "range",
+        "session": -1,
+        "start": 1,
+        "stop": 2,
+      })),
+      vec![json!([1, 1, "const a = 1;"])],
+    );
+    assert_eq!(
+      history.request(&json!({
+        "hist_access_type": "range",
+        "session": 0,
+        "output": true,
+      })),
+      vec![json!([2, 1, ["a + 2", null]])],
+    );
+    assert_eq!(
+      history.request(&json!({
+        "hist_access_type": "search",
+        "pattern": "a + *",
+      })),
+      vec![json!([1, 2, "a + 1"]), json!([2, 1, "a + 2"])],
+    );
+  }
+
+  #[test]
+  fn sessions_are_not_shared() {
+    let temp_dir = TempDir::new();
+    let file_path = temp_dir.path().join("history.jsonl").to_path_buf();
+
+    // kernels which are started before either ran any code
+    let mut first = KernelHistory::load(file_path.clone());
+    let mut second = KernelHistory::load(file_path.clone());
+    first.add(1, "a");
+    second.add(1, "b");
+
+    let history = KernelHistory::load(file_path);
+    assert_eq!(
+      history.request(&json!({ "hist_access_type": "tail", "n": 10 })),
+      vec![json!([1, 1, "a"]), json!([2, 1, "b"])],
+    );
+  }
+
+  #[test]
+  fn search_unique() {
+    let temp_dir = TempDir::new();
+    let mut history =
+      KernelHistory::load(temp_dir.path().join("history.jsonl").to_path_buf());
+    history.add(1, "a");
+    history.add(2, "b");
+    history.add(3, "a");
+
+    assert_eq!(
+      history.request(&json!({
+        "hist_access_type": "search",
+        "pattern": "*",
+        "unique": true,
+      })),
+      vec![json!([1, 2, "b"]), json!([1, 3, "a"])],
+    );
+  }
+}
@@ -172,6 +172,14 @@ impl JupyterMessage {
     self.content["comm_id"].as_str().unwrap_or("")
   }
 
+  pub(crate) fn content(&self) -> &serde_json::Value {
+    &self.content
+  }
+
+  pub(crate) fn buffers(&self) -> &[Bytes] {
+    &self.buffers
+  }
+
   // Creates a new child message of this message. ZMQ identities are not transferred.
   pub(crate) fn new_message(&self, msg_type: &str) -> JupyterMessage {
     let mut header = self.header.clone();
@@ -26,6 +26,7 @@ use super::test::reporters::PrettyTestReporter;
 use super::test::TestEvent;
 use super::test::TestEventSender;
 
+mod history;
 mod install;
 pub(crate) mod jupyter_msg;
 pub(crate) mod server;
@@ -58,6 +59,9 @@ pub async fn kernel(
 
   let factory = CliFactory::from_flags(flags).await?;
   let cli_options = factory.cli_options();
+  let history = history::KernelHistory::load(
+    factory.deno_dir()?.jupyter_history_file_path(),
+  );
   let main_module =
     resolve_url_or_path("./$deno$jupyter.ts", cli_options.initial_cwd())
       .unwrap();
@@ -136,7 +140,7 @@ pub async fn kernel(
     )
   }));
 
-  server::JupyterServer::start(spec, stdio_rx, repl_session).await?;
+  server::JupyterServer::start(spec, stdio_rx, repl_session, history).await?;
 
   Ok(())
 }
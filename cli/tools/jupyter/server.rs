@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 use crate::cdp;
 use crate::tools::repl;
+use bytes::Bytes;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::serde_json;
@@ -21,6 +22,7 @@ use tokio::sync::Mutex;
 use zeromq::SocketRecv;
 use zeromq::SocketSend;
 
+use super::history::KernelHistory;
 use super::jupyter_msg::Connection;
 use super::jupyter_msg::JupyterMessage;
 use super::ConnectionSpec;
@@ -32,11 +34,14 @@ pub enum StdioMsg {
 
 pub struct JupyterServer {
   execution_count: usize,
-  last_execution_request: Rc<RefCell<Option<JupyterMessage>>>,
+  /// The last execute request or comm message, which the messages sent
+  /// while running the code it triggered, such as the output, belong to.
+  last_parent_message: Rc<RefCell<Option<JupyterMessage>>>,
   // This is Arc<Mutex<>>, so we don't hold RefCell borrows across await
   // points.
   iopub_socket: Arc<Mutex<Connection<zeromq::PubSocket>>>,
   repl_session: repl::ReplSession,
+  history: KernelHistory,
 }
 
 impl JupyterServer {
@@ -44,6 +49,7 @@ impl JupyterServer {
     spec: ConnectionSpec,
     mut stdio_rx: mpsc::UnboundedReceiver<StdioMsg>,
     mut repl_session: repl::ReplSession,
+    history: KernelHistory,
   ) -> Result<(), AnyError> {
     let mut heartbeat =
       bind_socket::<zeromq::RepSocket>(&spec, spec.hb_port).await?;
@@ -56,14 +62,14 @@ impl JupyterServer {
     let iopub_socket =
       bind_socket::<zeromq::PubSocket>(&spec, spec.iopub_port).await?;
     let iopub_socket = Arc::new(Mutex::new(iopub_socket));
-    let last_execution_request = Rc::new(RefCell::new(None));
+    let last_parent_message = Rc::new(RefCell::new(None));
 
     // Store `iopub_socket` in the op state so it's accessible to the runtime API.
     {
       let op_state_rc = repl_session.worker.js_runtime.op_state();
       let mut op_state = op_state_rc.borrow_mut();
       op_state.put(iopub_socket.clone());
-      op_state.put(last_execution_request.clone());
+      op_state.put(last_parent_message.clone());
     }
 
     let cancel_handle = CancelHandle::new_rc();
@@ -72,8 +78,9 @@ impl JupyterServer {
     let mut server = Self {
       execution_count: 0,
       iopub_socket: iopub_socket.clone(),
-      last_execution_request: last_execution_request.clone(),
+      last_parent_message: last_parent_message.clone(),
       repl_session,
+      history,
     };
 
     let handle1 = deno_core::unsync::spawn(async move {
@@ -100,7 +107,7 @@ impl JupyterServer {
       while let Some(stdio_msg) = stdio_rx.recv().await {
         Self::handle_stdio_msg(
           iopub_socket.clone(),
-          last_execution_request.clone(),
+          last_parent_message.clone(),
           stdio_msg,
         )
         .await;
@@ -119,17 +126,17 @@ impl JupyterServer {
 
   async fn handle_stdio_msg<S: zeromq::SocketSend>(
     iopub_socket: Arc<Mutex<Connection<S>>>,
-    last_execution_request: Rc<RefCell<Option<JupyterMessage>>>,
+    last_parent_message: Rc<RefCell<Option<JupyterMessage>>>,
     stdio_msg: StdioMsg,
   ) {
-    let maybe_exec_result = last_execution_request.borrow().clone();
-    if let Some(exec_request) = maybe_exec_result {
+    let maybe_parent_message = last_parent_message.borrow().clone();
+    if let Some(parent_message) = maybe_parent_message {
       let (name, text) = match stdio_msg {
         StdioMsg::Stdout(text) => ("stdout", text),
         StdioMsg::Stderr(text) => ("stderr", text),
       };
 
-      let result = exec_request
+      let result = parent_message
         .new_message("stream")
         .with_content(json!({
             "name": name,
@@ -207,6 +214,13 @@ impl JupyterServer {
       .send(&mut *self.iopub_socket.lock().await)
       .await?;
 
+    if matches!(
+      msg.message_type(),
+      "execute_request" | "comm_open" | "comm_msg" | "comm_close"
+    ) {
+      *self.last_parent_message.borrow_mut() = Some(msg.clone());
+    }
+
     match msg.message_type() {
       "kernel_info_request" => {
         msg
@@ -228,14 +242,70 @@ impl JupyterServer {
           .await?;
       }
       "comm_open" => {
+        let content = msg.content();
+        let has_target = call_comm_manager(
+          &mut self.repl_session,
+          "handleOpen",
+          vec![
+            content["comm_id"].clone(),
+            content["target_name"].clone(),
+            content["data"].clone(),
+            buffers_to_json(msg.buffers()),
+          ],
+        )
+        .await?;
+        // close comms which were opened to an unknown target
+        if has_target != Some(serde_json::Value::Bool(true)) {
+          msg
+            .comm_close_message()
+            .send(&mut *self.iopub_socket.lock().await)
+            .await?;
+        }
+      }
+      "comm_msg" => {
+        call_comm_manager(
+          &mut self.repl_session,
+          "handleMessage",
+          vec![
+            json!(msg.comm_id()),
+            msg.content()["data"].clone(),
+            buffers_to_json(msg.buffers()),
+          ],
+        )
+        .await?;
+      }
+      "comm_close" => {
+        call_comm_manager(
+          &mut self.repl_session,
+          "handleClose",
+          vec![
+            json!(msg.comm_id()),
+            msg.content()["data"].clone(),
+            buffers_to_json(msg.buffers()),
+          ],
+        )
+        .await?;
+      }
+      "comm_info_request" => {
+        let comms = call_comm_manager(
+          &mut self.repl_session,
+          "info",
+          vec![msg.content()["target_name"].clone()],
+        )
+        .await?;
         msg
-          .comm_close_message()
-          .send(&mut *self.iopub_socket.lock().await)
+          .new_reply()
+          .with_content(json!({
+            "status": "ok",
+            "comms": comms.unwrap_or_else(|| json!({})),
+          }))
+          .send(connection)
           .await?;
       }
       "complete_request" => {
         let user_code = msg.code();
-        let cursor_pos = msg.cursor_pos();
+        let cursor_pos =
+          code_points_to_byte_offset(user_code, msg.cursor_pos());
 
         let lsp_completions = self
           .repl_session
@@ -264,8 +334,8 @@ impl JupyterServer {
             .with_content(json!({
               "status": "ok",
               "matches": matches,
-              "cursor_start": cursor_start,
-              "cursor_end": cursor_end,
+              "cursor_start": byte_offset_to_code_points(user_code, cursor_start),
+              "cursor_end": byte_offset_to_code_points(user_code, cursor_end),
               "metadata": {},
             }))
             .send(connection)
@@ -312,16 +382,64 @@ impl JupyterServer {
             .with_content(json!({
               "status": "ok",
               "matches": completions,
-              "cursor_start": cursor_start,
-              "cursor_end": cursor_pos,
+              "cursor_start": byte_offset_to_code_points(user_code, cursor_start),
+              "cursor_end": byte_offset_to_code_points(user_code, cursor_pos),
               "metadata": {},
             }))
             .send(connection)
             .await?;
         }
       }
-      "comm_msg" | "comm_info_request" | "history_request" => {
-        // We don't handle these messages
+      "inspect_request" => {
+        let user_code = msg.code();
+        let cursor_pos =
+          code_points_to_byte_offset(user_code, msg.cursor_pos());
+
+        let maybe_docs = match self
+          .repl_session
+          .language_server
+          .hover(user_code, cursor_pos)
+          .await
+        {
+          Some(docs) => Some(docs),
+          None => {
+            let expr = get_expr_from_line_at_pos(user_code, cursor_pos);
+            get_expression_description(&mut self.repl_session, expr).await
+          }
+        };
+
+        let content = match maybe_docs {
+          Some(docs) => json!({
+            "status": "ok",
+            "found": true,
+            "data": {
+              "text/plain": docs,
+              "text/markdown": docs,
+            },
+            "metadata": {},
+          }),
+          None => json!({
+            "status": "ok",
+            "found": false,
+            "data": {},
+            "metadata": {},
+          }),
+        };
+        msg
+          .new_reply()
+          .with_content(content)
+          .send(connection)
+          .await?;
+      }
+      "history_request" => {
+        msg
+          .new_reply()
+          .with_content(json!({
+            "status": "ok",
+            "history": self.history.request(msg.content()),
+          }))
+          .send(connection)
+          .await?;
       }
       _ => {
         eprintln!("Unrecognized shell message type: {}", msg.message_type());
@@ -342,7 +460,9 @@ impl JupyterServer {
     connection: &mut Connection<zeromq::RouterSocket>,
   ) -> Result<(), AnyError> {
     self.execution_count += 1;
-    *self.last_execution_request.borrow_mut() = Some(msg.clone());
+    if msg.content()["store_history"].as_bool().unwrap_or(true) {
+      self.history.add(self.execution_count as u64, msg.code());
+    }
 
     msg
       .new_message("execute_input")
@@ -388,6 +508,13 @@ impl JupyterServer {
     } = evaluate_response.value;
 
     if exception_details.is_none() {
+      // let the language server know about the declarations of the cell
+      self
+        .repl_session
+        .language_server
+        .commit_text(&evaluate_response.ts_code)
+        .await;
+
       publish_result(&mut self.repl_session, &result, self.execution_count)
         .await?;
 
@@ -570,6 +697,72 @@ async fn publish_result(
   Ok(None)
 }
 
+/// Calls a method of the comm manager in `40_jupyter.js` and returns its
+/// result, or `None` when it threw.
+async fn call_comm_manager(
+  session: &mut repl::ReplSession,
+  method: &str,
+  args: Vec<serde_json::Value>,
+) -> Result<Option<serde_json::Value>, AnyError> {
+  let arguments = args
+    .into_iter()
+    .map(|value| cdp::CallArgument {
+      value: Some(value),
+      unserializable_value: None,
+      object_id: None,
+    })
+    .collect::<Vec<_>>();
+
+  let response = session
+    .post_message_with_event_loop(
+      "Runtime.callFunctionOn",
+      Some(json!({
+        "functionDeclaration": format!(r#"async function (...args) {{
+          return await Deno[Deno.internal].jupyter.comms.{method}(...args);
+    }}"#),
+        "arguments": arguments,
+        "executionContextId": session.context_id,
+        "awaitPromise": true,
+        "returnByValue": true,
+      })),
+    )
+    .await?;
+
+  let response: cdp::CallFunctionOnResponse = serde_json::from_value(response)?;
+
+  if let Some(exception_details) = &response.exception_details {
+    eprintln!("Exception encountered: {}", exception_details.text);
+    return Ok(None);
+  }
+
+  Ok(response.result.value)
+}
+
+/// Converts the buffers of a message to arrays of bytes, since they can't be
+/// passed as binary through the inspector protocol.
+fn buffers_to_json(buffers: &[Bytes]) -> serde_json::Value {
+  buffers.iter().map(|buffer| buffer.as_ref()).collect()
+}
+
+/// Converts a cursor position of the Jupyter protocol, which is in unicode
+/// code points, to a byte offset in the text.
+fn code_points_to_byte_offset(text: &str, cursor_pos: usize) -> usize {
+  text
+    .char_indices()
+    .nth(cursor_pos)
+    .map(|(index, _)| index)
+    .unwrap_or(text.len())
+}
+
+/// Converts a byte offset in the text to a cursor position of the Jupyter
+/// protocol, which is in unicode code points.
+fn byte_offset_to_code_points(text: &str, byte_offset: usize) -> usize {
+  text
+    .get(..byte_offset)
+    .map(|text| text.chars().count())
+    .unwrap_or_else(|| text.chars().count())
+}
+
 // TODO(bartlomieju): dedup with repl::editor
 fn get_expr_from_line_at_pos(line: &str, cursor_pos: usize) -> &str {
   let start = line[..cursor_pos].rfind(is_word_boundary).unwrap_or(0);
@@ -682,6 +875,24 @@ async fn get_object_expr_properties(
   )
 }
 
+/// Describes the value of an expression, which is used for the inspection
+/// of values that the language server doesn't know about.
+async fn get_expression_description(
+  session: &mut repl::ReplSession,
+  expr: &str,
+) -> Option<String> {
+  if expr.is_empty() {
+    return None;
+  }
+  let evaluate_response = evaluate_expression(session, expr).await?;
+  let result = evaluate_response.result;
+  let description = match result.description {
+    Some(description) => description,
+    None => result.value?.to_string(),
+  };
+  Some(format!("{expr}: {} = {description}", result.kind))
+}
+
 // TODO(bartlomieju): dedup with repl::editor
 async fn evaluate_expression(
   session: &mut repl::ReplSession,
@@ -719,3 +930,23 @@ async fn evaluate_expression(
     Some(evaluate_response)
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn converts_cursor_positions() {
+    let code = "const ü = 'ö'; ü.len";
+    let cursor_pos = code.chars().count();
+    let byte_offset = code_points_to_byte_offset(code, cursor_pos);
+    assert_eq!(byte_offset, code.len());
+    assert_eq!(get_expr_from_line_at_pos(code, byte_offset), "ü.len");
+    assert_eq!(byte_offset_to_code_points(code, byte_offset), cursor_pos);
+
+    assert_eq!(code_points_to_byte_offset(code, 7), 8);
+    assert_eq!(byte_offset_to_code_points(code, 8), 7);
+    // positions past the end are clamped
+    assert_eq!(code_points_to_byte_offset(code, 100), code.len());
+  }
+}
@@ -0,0 +1,270 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Automatic fixes for the subset of lint diagnostics which can be resolved
+//! without changing the behaviour of the program.
+
+use deno_ast::swc::ast;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::StartSourcePos;
+use deno_lint::diagnostic::LintDiagnostic;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// A replacement of a range of the source text, in byte indexes.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFixEdit {
+  pub start: usize,
+  pub end: usize,
+  pub new_text: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFix {
+  pub description: String,
+  pub edits: Vec<LintFixEdit>,
+}
+
+/// Gets the fix for each of the provided diagnostics. The returned vector
+/// has the same length as `diagnostics` and contains `None` for the
+/// diagnostics that don't have a safe fix.
+pub fn get_lint_fixes(
+  parsed_source: &ParsedSource,
+  diagnostics: &[LintDiagnostic],
+) -> Vec<Option<LintFix>> {
+  if !diagnostics.iter().any(|d| is_fixable_code(&d.code)) {
+    return vec![None; diagnostics.len()];
+  }
+
+  let text = parsed_source.text_info().text_str();
+  let mut collector = FixableNodeCollector {
+    start_pos: parsed_source.text_info().range().start,
+    let_decls: Vec::new(),
+    type_anns: Vec::new(),
+  };
+  parsed_source.module().visit_with(&mut collector);
+
+  let prefer_const_starts = diagnostics
+    .iter()
+    .filter(|d| d.code == "prefer-const")
+    .map(|d| d.range.start.byte_index)
+    .collect::<HashSet<_>>();
+
+  diagnostics
+    .iter()
+    .map(|d| {
+      let start = d.range.start.byte_index;
+      let end = d.range.end.byte_index;
+      match d.code.as_str() {
+        "no-extra-semi" => {
+          if text.get(start..end) != Some(";") {
+            return None;
+          }
+          Some(LintFix {
+            description: "Remove extra semicolon".to_string(),
+            edits: vec![LintFixEdit {
+              start,
+              end,
+              new_text: String::new(),
+            }],
+          })
+        }
+        "prefer-const" => {
+          let decl = collector
+            .let_decls
+            .iter()
+            .find(|decl| decl.ident_starts.contains(&start))?;
+          // every binding needs to be reported, otherwise changing the
+          // keyword would make a reassigned variable constant
+          if !decl
+            .ident_starts
+            .iter()
+            .all(|start| prefer_const_starts.contains(start))
+          {
+            return None;
+          }
+          let keyword_end = decl.keyword_start + 3;
+          if text.get(decl.keyword_start..keyword_end) != Some("let") {
+            return None;
+          }
+          Some(LintFix {
+            description: "Use `const` instead of `let`".to_string(),
+            edits: vec![LintFixEdit {
+              start: decl.keyword_start,
+              end: keyword_end,
+              new_text: "const".to_string(),
+            }],
+          })
+        }
+        "no-inferrable-types" => {
+          let (ann_start, ann_end) = collector
+            .type_anns
+            .iter()
+            .filter(|(ann_start, ann_end)| *ann_start < end && start < *ann_end)
+            .min_by_key(|(ann_start, ann_end)| ann_end - ann_start)
+            .copied()?;
+          if !text[ann_start..].starts_with(':') {
+            return None;
+          }
+          // also remove any whitespace between the binding and the colon
+          let start = text[..ann_start].trim_end().len();
+          Some(LintFix {
+            description: "Remove inferrable type annotation".to_string(),
+            edits: vec![LintFixEdit {
+              start,
+              end: ann_end,
+              new_text: String::new(),
+            }],
+          })
+        }
+        _ => None,
+      }
+    })
+    .collect()
+}
+
+/// Applies the edits of the provided fixes to the source text. Edits which
+/// overlap an already applied edit are skipped, so the result should be
+/// linted again in order to pick up the remaining fixes.
+///
+/// Returns `None` when the fixes don't change the text.
+pub fn apply_lint_fixes<'a>(
+  source_code: &str,
+  fixes: impl Iterator<Item = &'a LintFix>,
+) -> Option<String> {
+  let mut edits = fixes.flat_map(|f| f.edits.iter()).collect::<Vec<_>>();
+  edits.sort_by_key(|e| (e.start, e.end));
+  edits.dedup();
+
+  let mut text = String::with_capacity(source_code.len());
+  let mut last_end = 0;
+  let mut changed = false;
+  for edit in edits {
+    if edit.start < last_end || edit.end > source_code.len() {
+      continue;
+    }
+    text.push_str(&source_code[last_end..edit.start]);
+    text.push_str(&edit.new_text);
+    last_end = edit.end;
+    changed = true;
+  }
+  text.push_str(&source_code[last_end..]);
+
+  if changed && text != source_code {
+    Some(text)
+  } else {
+    None
+  }
+}
+
+fn is_fixable_code(code: &str) -> bool {
+  matches!(
+    code,
+    "no-extra-semi" | "prefer-const" | "no-inferrable-types"
+  )
+}
+
+struct LetDecl {
+  keyword_start: usize,
+  ident_starts: Vec<usize>,
+}
+
+struct FixableNodeCollector {
+  start_pos: StartSourcePos,
+  let_decls: Vec<LetDecl>,
+  /// Ranges of the type annotations, including the leading colon.
+  type_anns: Vec<(usize, usize)>,
+}
+
+impl Visit for FixableNodeCollector {
+  fn visit_var_decl(&mut self, node: &ast::VarDecl) {
+    node.visit_children_with(self);
+
+    // only declarations where each declarator is a simple identifier with
+    // an initializer can be safely turned into a `const` declaration
+    if node.kind != ast::VarDeclKind::Let || node.declare {
+      return;
+    }
+    let mut ident_starts = Vec::with_capacity(node.decls.len());
+    for decl in &node.decls {
+      let ast::Pat::Ident(ident) = &decl.name else {
+        return;
+      };
+      if decl.init.is_none() {
+        return;
+      }
+      ident_starts.push(ident.id.range().start.as_byte_index(self.start_pos));
+    }
+    self.let_decls.push(LetDecl {
+      keyword_start: node.range().start.as_byte_index(self.start_pos),
+      ident_starts,
+    });
+  }
+
+  fn visit_binding_ident(&mut self, node: &ast::BindingIdent) {
+    node.visit_children_with(self);
+    if let Some(type_ann) = &node.type_ann {
+      self.add_type_ann(type_ann);
+    }
+  }
+
+  fn visit_class_prop(&mut self, node: &ast::ClassProp) {
+    node.visit_children_with(self);
+    if let Some(type_ann) = &node.type_ann {
+      self.add_type_ann(type_ann);
+    }
+  }
+}
+
+impl FixableNodeCollector {
+  fn add_type_ann(&mut self, type_ann: &ast::TsTypeAnn) {
+    let range = type_ann.range();
+    self.type_anns.push((
+      range.start.as_byte_index(self.start_pos),
+      range.end.as_byte_index(self.start_pos),
+    ));
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn edit(start: usize, end: usize, new_text: &str) -> LintFix {
+    LintFix {
+      description: String::new(),
+      edits: vec![LintFixEdit {
+        start,
+        end,
+        new_text: new_text.to_string(),
+      }],
+    }
+  }
+
+  #[test]
+  fn apply_fixes() {
+    let source = "let a = 1;;\nlet b: number = 2;\n";
+    let fixes = [edit(10, 11, ""), edit(0, 3, "const"), edit(0, 3, "const")];
+    assert_eq!(
+      apply_lint_fixes(source, fixes.iter()).unwrap(),
+      "const a = 1;\nlet b: number = 2;\n"
+    );
+  }
+
+  #[test]
+  fn apply_fixes_skips_overlapping_edits() {
+    let source = "let a = 1;";
+    let fixes = [edit(0, 5, "const b"), edit(4, 5, "c")];
+    assert_eq!(
+      apply_lint_fixes(source, fixes.iter()).unwrap(),
+      "const b = 1;"
+    );
+    assert_eq!(apply_lint_fixes(source, [].iter()), None);
+    assert_eq!(apply_lint_fixes(source, [edit(0, 3, "let")].iter()), None);
+  }
+}
@@ -3,6 +3,7 @@
 //! This module provides file linting utilities using
 //! [`deno_lint`](https://github.com/denoland/deno_lint).
 use crate::args::Flags;
+use crate::args::LintFixMode;
 use crate::args::LintFlags;
 use crate::args::LintOptions;
 use crate::args::LintReporterKind;
@@ -10,12 +11,15 @@ use crate::args::LintRulesConfig;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::tools::fmt::run_parallelized;
+use crate::util::diff::diff;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::FileCollector;
 use crate::util::path::is_script_ext;
 use crate::util::sync::AtomicFlag;
 use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParsedSource;
 use deno_config::glob::FilePatterns;
 use deno_core::anyhow::bail;
 use deno_core::error::generic_error;
@@ -32,6 +36,7 @@ use deno_runtime::fmt_errors::format_location;
 use log::debug;
 use log::info;
 use serde::Serialize;
+use std::borrow::Cow;
 use std::fs;
 use std::io::stdin;
 use std::io::Read;
@@ -42,13 +47,28 @@ use std::sync::Mutex;
 
 use crate::cache::IncrementalCache;
 
+mod fix;
+mod plugins;
+
+pub use fix::apply_lint_fixes;
+pub use fix::get_lint_fixes;
+pub use fix::LintFix;
+pub use fix::LintFixEdit;
+pub use plugins::LintPluginRunner;
+
 static STDIN_FILE_NAME: &str = "_stdin.ts";
 
+/// The maximum number of times a file is linted and fixed again, since
+/// applying a fix might reveal or unblock other fixes.
+const MAX_FIX_PASSES: usize = 10;
+
 fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
   match kind {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new()),
+    LintReporterKind::Github => Box::new(GithubLintReporter::new()),
   }
 }
 
@@ -107,10 +127,17 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
     let lint_options = cli_options.resolve_lint_options(lint_flags)?;
     let files = &lint_options.files;
     let success = if is_stdin {
+      if lint_options.fix_mode != LintFixMode::Disabled {
+        return Err(generic_error(
+          "Lint fix on standard input is not supported.",
+        ));
+      }
       let reporter_kind = lint_options.reporter_kind;
       let reporter_lock = Arc::new(Mutex::new(create_reporter(reporter_kind)));
       let lint_rules = get_config_rules_err_empty(lint_options.rules)?;
-      let r = lint_stdin(lint_rules);
+      let maybe_plugin_runner =
+        create_plugin_runner(&factory, lint_options.plugins).await?;
+      let r = lint_stdin(lint_rules, maybe_plugin_runner.as_ref());
       let success =
         handle_lint_result(STDIN_FILE_NAME, r, reporter_lock.clone());
       reporter_lock.lock().unwrap().close(1);
@@ -142,6 +169,10 @@ async fn lint_files(
 ) -> Result<bool, AnyError> {
   let caches = factory.caches()?;
   let lint_rules = get_config_rules_err_empty(lint_options.rules)?;
+  let maybe_plugin_runner =
+    create_plugin_runner(&factory, lint_options.plugins)
+      .await?
+      .map(Arc::new);
   let incremental_cache = Arc::new(IncrementalCache::new(
     caches.lint_incremental_cache_db(),
     // use a hash of the rule names in order to bust the cache
@@ -154,6 +185,7 @@ async fn lint_files(
     &paths,
   ));
   let target_files_len = paths.len();
+  let fix_mode = lint_options.fix_mode;
   let reporter_kind = lint_options.reporter_kind;
   let reporter_lock =
     Arc::new(Mutex::new(create_reporter(reporter_kind.clone())));
@@ -164,17 +196,54 @@ async fn lint_files(
     let lint_rules = lint_rules.clone();
     let reporter_lock = reporter_lock.clone();
     let incremental_cache = incremental_cache.clone();
+    let maybe_plugin_runner = maybe_plugin_runner.clone();
     move |file_path| {
       let file_text = fs::read_to_string(&file_path)?;
 
-      // don't bother rechecking this file if it didn't have any diagnostics before
-      if incremental_cache.is_file_same(&file_path, &file_text) {
+      // don't bother rechecking this file if it didn't have any diagnostics
+      // before, unless there are plugins since their code might have changed
+      if maybe_plugin_runner.is_none()
+        && incremental_cache.is_file_same(&file_path, &file_text)
+      {
         return Ok(());
       }
 
-      let r = lint_file(&file_path, file_text, lint_rules);
+      let maybe_plugin_runner = maybe_plugin_runner.as_deref();
+      let r = if fix_mode == LintFixMode::Disabled {
+        lint_file(&file_path, file_text, lint_rules, maybe_plugin_runner)
+      } else {
+        lint_file_with_fixes(
+          &file_path,
+          file_text.clone(),
+          lint_rules.clone(),
+          maybe_plugin_runner,
+        )
+        .and_then(|(file_diagnostics, fixed_text)| {
+          if fix_mode == LintFixMode::DryRun {
+            if fixed_text != file_text {
+              let _g = reporter_lock.lock().unwrap();
+              let diff = diff(&file_text, &fixed_text);
+              info!("");
+              info!("{} {}:", colors::bold("from"), file_path.display());
+              info!("{}", diff);
+            }
+            // nothing was written, so report the diagnostics of the file
+            // as it is on disk
+            return lint_file(
+              &file_path,
+              file_text.clone(),
+              lint_rules,
+              maybe_plugin_runner,
+            );
+          }
+          if fixed_text != file_text {
+            fs::write(&file_path, &fixed_text)?;
+          }
+          Ok((file_diagnostics, fixed_text))
+        })
+      };
       if let Ok((file_diagnostics, file_text)) = &r {
-        if file_diagnostics.is_empty() {
+        if file_diagnostics.is_empty() && fix_mode != LintFixMode::DryRun {
           // update the incremental cache if there were no diagnostics
           incremental_cache.update_file(&file_path, file_text)
         }
@@ -199,6 +268,16 @@ async fn lint_files(
   Ok(!has_error.is_raised())
 }
 
+async fn create_plugin_runner(
+  factory: &CliFactory,
+  plugins: Vec<ModuleSpecifier>,
+) -> Result<Option<LintPluginRunner>, AnyError> {
+  if plugins.is_empty() {
+    return Ok(None);
+  }
+  Ok(Some(LintPluginRunner::create(factory, plugins).await?))
+}
+
 fn collect_lint_files(files: FilePatterns) -> Result<Vec<PathBuf>, AnyError> {
   FileCollector::new(|path, _| is_script_ext(path))
     .ignore_git_folder()
@@ -262,26 +341,81 @@ fn lint_file(
   file_path: &Path,
   source_code: String,
   lint_rules: Vec<&'static dyn LintRule>,
+  maybe_plugin_runner: Option<&LintPluginRunner>,
 ) -> Result<(Vec<LintDiagnostic>, String), AnyError> {
   let filename = file_path.to_string_lossy().to_string();
   let media_type = MediaType::from_path(file_path);
 
   let linter = create_linter(lint_rules);
 
-  let (_, file_diagnostics) = linter.lint_file(LintFileOptions {
-    filename,
-    media_type,
-    source_code: source_code.clone(),
-  })?;
+  let (_, file_diagnostics) = lint_with_plugins(
+    &linter,
+    LintFileOptions {
+      filename,
+      media_type,
+      source_code: source_code.clone(),
+    },
+    maybe_plugin_runner,
+  )?;
 
   Ok((file_diagnostics, source_code))
 }
 
+fn lint_with_plugins(
+  linter: &Linter,
+  options: LintFileOptions,
+  maybe_plugin_runner: Option<&LintPluginRunner>,
+) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
+  let (parsed_source, mut file_diagnostics) = linter.lint_file(options)?;
+  if let Some(plugin_runner) = maybe_plugin_runner {
+    plugin_runner.lint(&parsed_source, &mut file_diagnostics)?;
+  }
+  Ok((parsed_source, file_diagnostics))
+}
+
+/// Lints the file and applies the available fixes until there are no more
+/// fixes to apply, returning the remaining diagnostics and the fixed text.
+fn lint_file_with_fixes(
+  file_path: &Path,
+  mut source_code: String,
+  lint_rules: Vec<&'static dyn LintRule>,
+  maybe_plugin_runner: Option<&LintPluginRunner>,
+) -> Result<(Vec<LintDiagnostic>, String), AnyError> {
+  let filename = file_path.to_string_lossy().to_string();
+  let media_type = MediaType::from_path(file_path);
+
+  let linter = create_linter(lint_rules);
+
+  let mut pass = 0;
+  loop {
+    let (parsed_source, file_diagnostics) = lint_with_plugins(
+      &linter,
+      LintFileOptions {
+        filename: filename.clone(),
+        media_type,
+        source_code: source_code.clone(),
+      },
+      maybe_plugin_runner,
+    )?;
+    pass += 1;
+    if pass > MAX_FIX_PASSES {
+      return Ok((file_diagnostics, source_code));
+    }
+
+    let fixes = get_lint_fixes(&parsed_source, &file_diagnostics);
+    match apply_lint_fixes(&source_code, fixes.iter().flatten()) {
+      Some(fixed_text) => source_code = fixed_text,
+      None => return Ok((file_diagnostics, source_code)),
+    }
+  }
+}
+
 /// Lint stdin and write result to stdout.
 /// Treats input as TypeScript.
 /// Compatible with `--json` flag.
 fn lint_stdin(
   lint_rules: Vec<&'static dyn LintRule>,
+  maybe_plugin_runner: Option<&LintPluginRunner>,
 ) -> Result<(Vec<LintDiagnostic>, String), AnyError> {
   let mut source_code = String::new();
   if stdin().read_to_string(&mut source_code).is_err() {
@@ -290,11 +424,15 @@ fn lint_stdin(
 
   let linter = create_linter(lint_rules);
 
-  let (_, file_diagnostics) = linter.lint_file(LintFileOptions {
-    filename: STDIN_FILE_NAME.to_string(),
-    source_code: source_code.clone(),
-    media_type: MediaType::TypeScript,
-  })?;
+  let (_, file_diagnostics) = lint_with_plugins(
+    &linter,
+    LintFileOptions {
+      filename: STDIN_FILE_NAME.to_string(),
+      source_code: source_code.clone(),
+      media_type: MediaType::TypeScript,
+    },
+    maybe_plugin_runner,
+  )?;
 
   Ok((file_diagnostics, source_code))
 }
@@ -529,6 +667,241 @@ impl LintReporter for JsonLintReporter {
   }
 }
 
+/// Reports the diagnostics in the Static Analysis Results Interchange Format
+/// (SARIF) 2.1.0, which is understood by code scanning tools.
+struct SarifLintReporter {
+  diagnostics: Vec<LintDiagnostic>,
+  errors: Vec<LintError>,
+  /// The URL of the current directory, which the artifact URIs are relative
+  /// to.
+  src_root: Option<ModuleSpecifier>,
+}
+
+impl SarifLintReporter {
+  fn new() -> SarifLintReporter {
+    SarifLintReporter {
+      diagnostics: Vec::new(),
+      errors: Vec::new(),
+      src_root: std::env::current_dir()
+        .ok()
+        .and_then(|cwd| ModuleSpecifier::from_directory_path(cwd).ok()),
+    }
+  }
+
+  fn artifact_location(&self, file_path: &str) -> serde_json::Value {
+    let Ok(url) = ModuleSpecifier::from_file_path(file_path) else {
+      return serde_json::json!({ "uri": file_path });
+    };
+    let maybe_relative = self
+      .src_root
+      .as_ref()
+      .and_then(|root| url.as_str().strip_prefix(root.as_str()));
+    match maybe_relative {
+      Some(relative) => serde_json::json!({
+        "uri": relative,
+        "uriBaseId": SARIF_SRC_ROOT,
+      }),
+      None => serde_json::json!({ "uri": url.to_string() }),
+    }
+  }
+}
+
+const SARIF_SRC_ROOT: &str = "%SRCROOT%";
+
+impl LintReporter for SarifLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic, _source_lines: Vec<&str>) {
+    self.diagnostics.push(d.clone());
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    self.errors.push(LintError {
+      file_path: file_path.to_string(),
+      message: err.to_string(),
+    });
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    sort_diagnostics(&mut self.diagnostics);
+
+    let mut rule_codes = self
+      .diagnostics
+      .iter()
+      .map(|d| d.code.as_str())
+      .collect::<Vec<_>>();
+    rule_codes.sort_unstable();
+    rule_codes.dedup();
+    let rules = rule_codes
+      .iter()
+      .map(|code| {
+        serde_json::json!({
+          "id": code,
+          "helpUri": format!("https://lint.deno.land/#{code}"),
+        })
+      })
+      .collect::<Vec<_>>();
+
+    let results = self
+      .diagnostics
+      .iter()
+      .map(|d| {
+        let mut message = d.message.clone();
+        if let Some(hint) = &d.hint {
+          message.push('\n');
+          message.push_str(hint);
+        }
+        serde_json::json!({
+          "ruleId": d.code,
+          "ruleIndex": rule_codes.binary_search(&d.code.as_str()).unwrap(),
+          "level": "error",
+          "message": { "text": message },
+          "locations": [{
+            "physicalLocation": {
+              "artifactLocation": self.artifact_location(&d.filename),
+              "region": {
+                "startLine": d.range.start.line_index + 1,
+                "startColumn": d.range.start.column_index + 1,
+                "endLine": d.range.end.line_index + 1,
+                "endColumn": d.range.end.column_index + 1,
+              },
+            },
+          }],
+        })
+      })
+      .collect::<Vec<_>>();
+
+    let notifications = self
+      .errors
+      .iter()
+      .map(|err| {
+        serde_json::json!({
+          "level": "error",
+          "message": { "text": err.message },
+          "locations": [{
+            "physicalLocation": {
+              "artifactLocation": self.artifact_location(&err.file_path),
+            },
+          }],
+        })
+      })
+      .collect::<Vec<_>>();
+
+    let mut original_uri_base_ids = serde_json::Map::new();
+    if let Some(src_root) = &self.src_root {
+      original_uri_base_ids.insert(
+        SARIF_SRC_ROOT.to_string(),
+        serde_json::json!({ "uri": src_root.to_string() }),
+      );
+    }
+    let sarif = serde_json::json!({
+      "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+      "version": "2.1.0",
+      "runs": [{
+        "tool": {
+          "driver": {
+            "name": "deno lint",
+            "informationUri": "https://lint.deno.land",
+            "version": crate::version::deno(),
+            "rules": rules,
+          },
+        },
+        // the columns of the diagnostics are character indexes rather than
+        // the default UTF-16 code units
+        "columnKind": "unicodeCodePoints",
+        "originalUriBaseIds": original_uri_base_ids,
+        "invocations": [{
+          "executionSuccessful": self.errors.is_empty(),
+          "toolExecutionNotifications": notifications,
+        }],
+        "results": results,
+      }],
+    });
+    println!("{}", serde_json::to_string_pretty(&sarif).unwrap());
+  }
+}
+
+/// Reports the diagnostics as workflow commands that GitHub Actions shows as
+/// annotations on the changed files.
+struct GithubLintReporter {
+  lint_count: u32,
+  cwd: Option<PathBuf>,
+}
+
+impl GithubLintReporter {
+  fn new() -> GithubLintReporter {
+    GithubLintReporter {
+      lint_count: 0,
+      cwd: std::env::current_dir().ok(),
+    }
+  }
+
+  /// Annotations are matched against paths relative to the repository.
+  fn relative_path<'a>(&self, file_path: &'a str) -> Cow<'a, str> {
+    self
+      .cwd
+      .as_ref()
+      .and_then(|cwd| Path::new(file_path).strip_prefix(cwd).ok())
+      .map(|path| Cow::Owned(path.to_string_lossy().replace('\\', "/")))
+      .unwrap_or(Cow::Borrowed(file_path))
+  }
+}
+
+impl LintReporter for GithubLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic, _source_lines: Vec<&str>) {
+    self.lint_count += 1;
+
+    let mut message = d.message.clone();
+    if let Some(hint) = &d.hint {
+      message.push('\n');
+      message.push_str(hint);
+    }
+    println!(
+      "::error file={},line={},endLine={},col={},endColumn={},title={}::{}",
+      escape_github_property(&self.relative_path(&d.filename)),
+      d.range.start.line_index + 1,
+      d.range.end.line_index + 1,
+      d.range.start.column_index + 1,
+      d.range.end.column_index + 1,
+      escape_github_property(&d.code),
+      escape_github_data(&message),
+    );
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    println!(
+      "::error file={}::{}",
+      escape_github_property(&self.relative_path(file_path)),
+      escape_github_data(&format!("Error linting: {err}")),
+    );
+  }
+
+  fn close(&mut self, check_count: usize) {
+    match self.lint_count {
+      1 => info!("Found 1 problem"),
+      n if n > 1 => info!("Found {} problems", self.lint_count),
+      _ => (),
+    }
+
+    match check_count {
+      n if n <= 1 => info!("Checked {} file", n),
+      n if n > 1 => info!("Checked {} files", n),
+      _ => unreachable!(),
+    }
+  }
+}
+
+fn escape_github_data(value: &str) -> String {
+  value
+    .replace('%', "%25")
+    .replace('\r', "%0D")
+    .replace('\n', "%0A")
+}
+
+fn escape_github_property(value: &str) -> String {
+  escape_github_data(value)
+    .replace(':', "%3A")
+    .replace(',', "%2C")
+}
+
 fn sort_diagnostics(diagnostics: &mut [LintDiagnostic]) {
   // Sort so that we guarantee a deterministic output which is useful for tests
   diagnostics.sort_by(|a, b| {
@@ -603,4 +976,16 @@ mod test {
     recommended_rule_names.sort();
     assert_eq!(rule_names, recommended_rule_names);
   }
+
+  #[test]
+  fn github_annotation_escaping() {
+    assert_eq!(
+      escape_github_data("100% broken\r\nhint: a, b"),
+      "100%25 broken%0D%0Ahint: a, b"
+    );
+    assert_eq!(
+      escape_github_property("C:\\src\\a,b.ts"),
+      "C%3A\\src\\a%2Cb.ts"
+    );
+  }
 }
@@ -0,0 +1,118 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Runs the rules of lint plugins against the serialized AST of a file.
+//
+// A plugin is a module with a default export of the following shape:
+//
+//   export default {
+//     name: "my-plugin",
+//     rules: {
+//       "my-rule": {
+//         create(context) {
+//           return {
+//             ImportDeclaration(node) {
+//               context.report({ node, message: "..." });
+//             },
+//           };
+//         },
+//       },
+//     },
+//   };
+//
+// This script evaluates to a function which is called with the namespaces of
+// the plugin modules and returns the codes of their rules along with the
+// function which is called for each file.
+(() => {
+  const encoder = new TextEncoder();
+  const decoder = new TextDecoder();
+
+  function validatePlugin(namespace) {
+    const plugin = namespace.default;
+    if (
+      plugin == null || typeof plugin.name !== "string" ||
+      plugin.rules == null || typeof plugin.rules !== "object"
+    ) {
+      throw new TypeError(
+        'A lint plugin must have a default export with a "name" and "rules".',
+      );
+    }
+    return plugin;
+  }
+
+  function visit(node, parent, listeners) {
+    if (Array.isArray(node)) {
+      for (const child of node) {
+        visit(child, parent, listeners);
+      }
+      return;
+    }
+    if (node === null || typeof node !== "object") {
+      return;
+    }
+    const type = typeof node.type === "string" ? node.type : undefined;
+    if (type !== undefined) {
+      for (const listener of listeners[type] ?? []) {
+        listener(node, parent);
+      }
+    }
+    for (const key in node) {
+      if (key !== "span" && key !== "type") {
+        visit(node[key], type !== undefined ? node : parent, listeners);
+      }
+    }
+    if (type !== undefined) {
+      for (const listener of listeners[`${type}:exit`] ?? []) {
+        listener(node, parent);
+      }
+    }
+  }
+
+  return function createLintPluginRunner(namespaces) {
+    const rules = [];
+    for (const namespace of namespaces) {
+      const plugin = validatePlugin(namespace);
+      for (const [ruleName, rule] of Object.entries(plugin.rules)) {
+        rules.push({ code: `${plugin.name}/${ruleName}`, rule });
+      }
+    }
+
+    function run(fileName, sourceText, programJson, spanOffset) {
+      const program = JSON.parse(programJson);
+      const sourceBytes = encoder.encode(sourceText);
+      const diagnostics = [];
+      const listeners = {};
+      for (const { code, rule } of rules) {
+        const context = {
+          id: code,
+          fileName,
+          sourceText,
+          getSourceText(node) {
+            return decoder.decode(
+              sourceBytes.subarray(
+                node.span.start - spanOffset,
+                node.span.end - spanOffset,
+              ),
+            );
+          },
+          report({ node, message, hint }) {
+            diagnostics.push({
+              code,
+              message: String(message),
+              hint: hint == null ? null : String(hint),
+              start: node.span.start - spanOffset,
+              end: node.span.end - spanOffset,
+            });
+          },
+        };
+        const ruleListeners = rule.create(context) ?? {};
+        for (const [type, listener] of Object.entries(ruleListeners)) {
+          (listeners[type] ??= []).push(listener);
+        }
+      }
+      visit(program, null, listeners);
+      return diagnostics;
+    }
+
+    return { ruleCodes: rules.map(({ code }) => code), run };
+  };
+})();
@@ -0,0 +1,331 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Support for lint rules defined in JavaScript or TypeScript modules, which
+//! are configured with `lint.plugins` in the config file.
+
+use crate::factory::CliFactory;
+use crate::worker::CliMainWorkerFactory;
+use deno_ast::swc::ast::Program;
+use deno_ast::swc::common::Spanned;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use deno_core::anyhow::anyhow;
+use deno_core::error::AnyError;
+use deno_core::error::JsError;
+use deno_core::located_script_name;
+use deno_core::serde_json;
+use deno_core::serde_v8;
+use deno_core::unsync::spawn_blocking;
+use deno_core::v8;
+use deno_core::ModuleSpecifier;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::Position;
+use deno_lint::diagnostic::Range;
+use deno_runtime::permissions::Permissions;
+use deno_runtime::permissions::PermissionsContainer;
+use deno_runtime::tokio_util::create_and_run_current_thread;
+use deno_runtime::worker::MainWorker;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+#[derive(Debug, Deserialize)]
+struct PluginDiagnostic {
+  code: String,
+  message: String,
+  hint: Option<String>,
+  /// Byte index in the source text.
+  start: usize,
+  /// Byte index in the source text.
+  end: usize,
+}
+
+type PluginResponse = Result<Vec<PluginDiagnostic>, AnyError>;
+
+struct PluginRequest {
+  file_name: String,
+  source_text: String,
+  program_json: String,
+  span_offset: u32,
+  response_tx: std::sync::mpsc::Sender<PluginResponse>,
+}
+
+/// Runs the rules of the lint plugins. The plugins are loaded in a worker
+/// that lives on its own thread, so the runner can be shared between the
+/// threads that lint files.
+#[derive(Debug)]
+pub struct LintPluginRunner {
+  sender: mpsc::UnboundedSender<PluginRequest>,
+  /// The codes of the rules the plugins define.
+  rule_codes: HashSet<String>,
+}
+
+impl LintPluginRunner {
+  pub async fn create(
+    factory: &CliFactory,
+    plugins: Vec<ModuleSpecifier>,
+  ) -> Result<Self, AnyError> {
+    let worker_factory =
+      Arc::new(factory.create_cli_main_worker_factory().await?);
+    let (sender, mut receiver) = mpsc::unbounded_channel::<PluginRequest>();
+    let (ready_tx, ready_rx) =
+      oneshot::channel::<Result<HashSet<String>, AnyError>>();
+    spawn_blocking(move || {
+      create_and_run_current_thread(async move {
+        let mut host = match PluginHost::load(&worker_factory, plugins).await {
+          Ok(host) => {
+            let _ = ready_tx.send(Ok(host.rule_codes.clone()));
+            host
+          }
+          Err(err) => {
+            let _ = ready_tx.send(Err(err));
+            return;
+          }
+        };
+        while let Some(request) = receiver.recv().await {
+          let response = host.run(&request);
+          let _ = request.response_tx.send(response);
+        }
+      })
+    });
+    let rule_codes = ready_rx.await??;
+    Ok(Self { sender, rule_codes })
+  }
+
+  /// Runs the plugin rules against the parsed source and adds their
+  /// diagnostics to the diagnostics of the built-in rules. Blocks the current
+  /// thread until the plugins have finished.
+  pub fn lint(
+    &self,
+    parsed_source: &ParsedSource,
+    diagnostics: &mut Vec<LintDiagnostic>,
+  ) -> Result<(), AnyError> {
+    let text_info = parsed_source.text_info();
+    let program = parsed_source.program();
+    let program_start =
+      program.range().start.as_byte_index(text_info.range().start);
+    let file_name = parsed_source.specifier().to_string();
+    let (response_tx, response_rx) = std::sync::mpsc::channel();
+    self
+      .sender
+      .send(PluginRequest {
+        file_name: file_name.clone(),
+        source_text: text_info.text_str().to_string(),
+        program_json: serde_json::to_string(&*program)?,
+        span_offset: program.span().lo.0 - program_start as u32,
+        response_tx,
+      })
+      .map_err(|_| anyhow!("The lint plugins are no longer running."))?;
+    let plugin_diagnostics = response_rx.recv()??;
+
+    // the built-in rules don't know about the codes of the plugin rules, so
+    // ignore comments for them would be reported as unknown or unused
+    diagnostics.retain(|d| {
+      !(matches!(
+        d.code.as_str(),
+        "ban-unknown-rule-code" | "ban-unused-ignore"
+      ) && quoted_code(&d.message)
+        .map(|code| self.rule_codes.contains(code))
+        .unwrap_or(false))
+    });
+
+    let ignore_directives = IgnoreDirectives::new(parsed_source);
+    let text_len = text_info.text_str().len();
+    for d in plugin_diagnostics {
+      let start = d.start.min(text_len);
+      let end = d.end.clamp(start, text_len);
+      let range = Range {
+        start: as_position(text_info, start),
+        end: as_position(text_info, end),
+      };
+      if ignore_directives.is_ignored(&d.code, range.start.line_index) {
+        continue;
+      }
+      diagnostics.push(LintDiagnostic {
+        range,
+        filename: file_name.clone(),
+        message: d.message,
+        code: d.code,
+        hint: d.hint,
+      });
+    }
+    Ok(())
+  }
+}
+
+/// Gets the rule code which the messages of the `ban-unknown-rule-code` and
+/// `ban-unused-ignore` rules quote.
+fn quoted_code(message: &str) -> Option<&str> {
+  let start = message.find('"')? + 1;
+  let end = start + message[start..].find('"')?;
+  Some(&message[start..end])
+}
+
+fn as_position(text_info: &SourceTextInfo, byte_index: usize) -> Position {
+  let loc =
+    text_info.line_and_column_index(text_info.range().start + byte_index);
+  Position {
+    line_index: loc.line_index,
+    column_index: loc.column_index,
+    byte_index,
+  }
+}
+
+/// The `deno-lint-ignore` and `deno-lint-ignore-file` comments of a file,
+/// which the linter only applies to the built-in rules. Like the linter,
+/// `deno-lint-ignore-file` is only honored before the first statement.
+struct IgnoreDirectives {
+  ignore_file: bool,
+  file_codes: HashSet<String>,
+  /// Codes that are ignored, keyed by line index.
+  line_codes: HashMap<usize, HashSet<String>>,
+}
+
+impl IgnoreDirectives {
+  fn new(parsed_source: &ParsedSource) -> Self {
+    let mut directives = Self {
+      ignore_file: false,
+      file_codes: HashSet::new(),
+      line_codes: HashMap::new(),
+    };
+    let program = parsed_source.program();
+    let code_start = match program.as_ref() {
+      Program::Module(module) => module.body.first().map(|item| item.start()),
+      Program::Script(script) => script.body.first().map(|stmt| stmt.start()),
+    };
+    for comment in parsed_source.comments().get_vec().iter() {
+      let text = comment.text.trim();
+      if let Some(codes) = text.strip_prefix("deno-lint-ignore-file") {
+        if code_start
+          .map(|start| comment.end() > start)
+          .unwrap_or(false)
+        {
+          continue;
+        }
+        if codes.trim().is_empty() {
+          directives.ignore_file = true;
+        }
+        directives
+          .file_codes
+          .extend(codes.split_whitespace().map(|c| c.to_string()));
+      } else if let Some(codes) = text.strip_prefix("deno-lint-ignore") {
+        let line_index = parsed_source
+          .text_info()
+          .line_and_column_index(comment.end())
+          .line_index;
+        directives
+          .line_codes
+          .entry(line_index + 1)
+          .or_default()
+          .extend(codes.split_whitespace().map(|c| c.to_string()));
+      }
+    }
+    directives
+  }
+
+  fn is_ignored(&self, code: &str, line_index: usize) -> bool {
+    self.ignore_file
+      || self.file_codes.contains(code)
+      || self
+        .line_codes
+        .get(&line_index)
+        .map(|codes| codes.contains(code))
+        .unwrap_or(false)
+  }
+}
+
+struct PluginHost {
+  worker: MainWorker,
+  rule_codes: HashSet<String>,
+  run_fn: v8::Global<v8::Function>,
+}
+
+impl PluginHost {
+  async fn load(
+    worker_factory: &CliMainWorkerFactory,
+    plugins: Vec<ModuleSpecifier>,
+  ) -> Result<Self, AnyError> {
+    // plugins only analyze the AST they're given, so they get no permissions
+    let permissions = PermissionsContainer::new(Permissions::from_options(
+      &Default::default(),
+    )?);
+    // the worker's main module is never loaded, so that every plugin is
+    // loaded the same way as a side module
+    let main_module =
+      ModuleSpecifier::parse("internal:///lint_plugin_runner.js").unwrap();
+    let mut worker = worker_factory
+      .create_main_worker(main_module, permissions)
+      .await?
+      .into_main_worker();
+    let mut namespaces = Vec::with_capacity(plugins.len());
+    for plugin in &plugins {
+      let id = worker.preload_side_module(plugin).await?;
+      worker.evaluate_module(id).await?;
+      namespaces.push(worker.js_runtime.get_module_namespace(id)?);
+    }
+
+    let create_fn = worker.js_runtime.execute_script_static(
+      located_script_name!(),
+      include_str!("plugin_runner.js"),
+    )?;
+    let (rule_codes, run_fn) = {
+      let scope = &mut worker.js_runtime.handle_scope();
+      let tc_scope = &mut v8::TryCatch::new(scope);
+      let create_fn = v8::Local::new(tc_scope, create_fn);
+      let create_fn = v8::Local::<v8::Function>::try_from(create_fn)?;
+      let namespaces = namespaces
+        .iter()
+        .map(|namespace| v8::Local::new(tc_scope, namespace).into())
+        .collect::<Vec<v8::Local<v8::Value>>>();
+      let args = [v8::Array::new_with_elements(tc_scope, &namespaces).into()];
+      let undefined = v8::undefined(tc_scope).into();
+      let Some(runner) = create_fn.call(tc_scope, undefined, &args) else {
+        let exception = tc_scope
+          .exception()
+          .unwrap_or_else(|| v8::undefined(tc_scope).into());
+        return Err(JsError::from_v8_exception(tc_scope, exception).into());
+      };
+      let runner = v8::Local::<v8::Object>::try_from(runner)?;
+      let key = v8::String::new(tc_scope, "ruleCodes").unwrap();
+      let rule_codes = runner.get(tc_scope, key.into()).unwrap();
+      let rule_codes: HashSet<String> =
+        serde_v8::from_v8(tc_scope, rule_codes)?;
+      let key = v8::String::new(tc_scope, "run").unwrap();
+      let run_fn = runner.get(tc_scope, key.into()).unwrap();
+      let run_fn = v8::Local::<v8::Function>::try_from(run_fn)?;
+      (rule_codes, v8::Global::new(tc_scope, run_fn))
+    };
+
+    Ok(Self {
+      worker,
+      rule_codes,
+      run_fn,
+    })
+  }
+
+  fn run(&mut self, request: &PluginRequest) -> PluginResponse {
+    let scope = &mut self.worker.js_runtime.handle_scope();
+    let tc_scope = &mut v8::TryCatch::new(scope);
+    let args = [
+      serde_v8::to_v8(tc_scope, &request.file_name)?,
+      serde_v8::to_v8(tc_scope, &request.source_text)?,
+      serde_v8::to_v8(tc_scope, &request.program_json)?,
+      serde_v8::to_v8(tc_scope, request.span_offset)?,
+    ];
+    let run_fn = v8::Local::new(tc_scope, &self.run_fn);
+    let undefined = v8::undefined(tc_scope).into();
+    match run_fn.call(tc_scope, undefined, &args) {
+      Some(value) => Ok(serde_v8::from_v8(tc_scope, value)?),
+      None => {
+        let exception = tc_scope
+          .exception()
+          .unwrap_or_else(|| v8::undefined(tc_scope).into());
+        Err(JsError::from_v8_exception(tc_scope, exception).into())
+      }
+    }
+  }
+}
@@ -41,6 +41,87 @@ use std::sync::Arc;
 use super::channel::RustylineSyncMessageSender;
 use super::session::REPL_INTERNALS_NAME;
 
+/// The commands which can be entered in the REPL instead of code, along
+/// with their descriptions for `.help`.
+pub const REPL_COMMANDS: &[(&str, &str)] = &[
+  (
+    ".clear",
+    "Clear the screen and the inputs recorded for .save",
+  ),
+  (".editor", "Enter editor mode to write multiple lines"),
+  (".exit", "Exit the REPL"),
+  (".help", "Print this list of commands"),
+  (".load <file>", "Evaluate a file in the REPL"),
+  (
+    ".save <file>",
+    "Save the inputs which were evaluated successfully to a file",
+  ),
+  (":type <expr>", "Print the TypeScript type of an expression"),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReplCommand<'a> {
+  Clear,
+  Editor,
+  Exit,
+  Help,
+  Load(&'a str),
+  Save(&'a str),
+  Type(&'a str),
+  Unknown(&'a str),
+}
+
+impl<'a> ReplCommand<'a> {
+  /// Parses the command of an input, which is `None` for code.
+  pub fn parse(input: &'a str) -> Option<Self> {
+    let input = input.trim();
+    if let Some(expr) = input.strip_prefix(":type") {
+      if expr.is_empty() || expr.starts_with(char::is_whitespace) {
+        return Some(ReplCommand::Type(expr.trim()));
+      }
+    }
+    // numbers like `.5` are code
+    let text = input.strip_prefix('.')?;
+    if !text.starts_with(|c: char| c.is_ascii_alphabetic()) {
+      return None;
+    }
+    let (name, arg) = match text.split_once(char::is_whitespace) {
+      Some((name, arg)) => (name, arg.trim()),
+      None => (text, ""),
+    };
+    Some(match name {
+      "clear" => ReplCommand::Clear,
+      "editor" => ReplCommand::Editor,
+      "exit" => ReplCommand::Exit,
+      "help" => ReplCommand::Help,
+      "load" => ReplCommand::Load(arg),
+      "save" => ReplCommand::Save(arg),
+      _ => ReplCommand::Unknown(name),
+    })
+  }
+}
+
+/// Gets the commands which start with the text before the cursor.
+fn get_command_completions(line: &str, pos: usize) -> Option<Vec<String>> {
+  let text = &line[..pos];
+  if !(text.starts_with('.') || text.starts_with(':'))
+    || text.contains(char::is_whitespace)
+  {
+    return None;
+  }
+  let candidates = REPL_COMMANDS
+    .iter()
+    .map(|(usage, _)| usage.split(' ').next().unwrap())
+    .filter(|name| name.starts_with(text))
+    .map(|name| name.to_string())
+    .collect::<Vec<_>>();
+  if candidates.is_empty() {
+    None
+  } else {
+    Some(candidates)
+  }
+}
+
 // Provides helpers to the editor like validation for multi-line edits, completion candidates for
 // tab completion.
 #[derive(Helper, Hinter)]
@@ -190,6 +271,10 @@ impl Completer for EditorHelper {
     pos: usize,
     _ctx: &Context<'_>,
   ) -> Result<(usize, Vec<String>), ReadlineError> {
+    if let Some(candidates) = get_command_completions(line, pos) {
+      return Ok((0, candidates));
+    }
+
     let lsp_completions = self.sync_sender.lsp_completions(line, pos);
     if !lsp_completions.is_empty() {
       // assumes all lsp completions have the same start position
@@ -239,6 +324,9 @@ impl Validator for EditorHelper {
     &self,
     ctx: &mut ValidationContext,
   ) -> Result<ValidationResult, ReadlineError> {
+    if ReplCommand::parse(ctx.input()).is_some() {
+      return Ok(ValidationResult::Valid(None));
+    }
     Ok(validate(ctx.input()))
   }
 }
@@ -477,8 +565,12 @@ impl ReplEditor {
     })
   }
 
-  pub fn readline(&self) -> Result<String, ReadlineError> {
-    self.inner.lock().readline("> ")
+  pub fn readline(&self, prompt: &str) -> Result<String, ReadlineError> {
+    self.inner.lock().readline(prompt)
+  }
+
+  pub fn clear_screen(&self) {
+    let _ = self.inner.lock().clear_screen();
   }
 
   pub fn update_history(&self, entry: String) {
@@ -563,7 +655,9 @@ impl ConditionalEventHandler for TabEventHandler {
 mod test {
   use rustyline::validate::ValidationResult;
 
+  use super::get_command_completions;
   use super::validate;
+  use super::ReplCommand;
 
   #[test]
   fn validate_only_one_forward_slash_per_line() {
@@ -578,4 +672,39 @@ let left = test( arr.slice( 0 , arr.length/2 ) )"#;
     let code = r#"/testing/;"#;
     assert!(matches!(validate(code), ValidationResult::Valid(_)));
   }
+
+  #[test]
+  fn parse_repl_command() {
+    assert_eq!(ReplCommand::parse(".help"), Some(ReplCommand::Help));
+    assert_eq!(
+      ReplCommand::parse(" .save  session.ts "),
+      Some(ReplCommand::Save("session.ts"))
+    );
+    assert_eq!(ReplCommand::parse(".load"), Some(ReplCommand::Load("")));
+    assert_eq!(
+      ReplCommand::parse(":type a.b"),
+      Some(ReplCommand::Type("a.b"))
+    );
+    assert_eq!(
+      ReplCommand::parse(".foo"),
+      Some(ReplCommand::Unknown("foo"))
+    );
+    assert_eq!(ReplCommand::parse(".5 + 1"), None);
+    assert_eq!(ReplCommand::parse(":typed"), None);
+    assert_eq!(ReplCommand::parse("a.save"), None);
+  }
+
+  #[test]
+  fn command_completions() {
+    assert_eq!(
+      get_command_completions(".s", 2),
+      Some(vec![".save".to_string()])
+    );
+    assert_eq!(
+      get_command_completions(".e", 2),
+      Some(vec![".editor".to_string(), ".exit".to_string()])
+    );
+    assert_eq!(get_command_completions(".save a", 7), None);
+    assert_eq!(get_command_completions("a.s", 3), None);
+  }
 }
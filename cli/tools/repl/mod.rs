@@ -25,7 +25,9 @@ use channel::RustylineSyncMessage;
 use channel::RustylineSyncMessageHandler;
 use channel::RustylineSyncResponse;
 use editor::EditorHelper;
+use editor::ReplCommand;
 use editor::ReplEditor;
+use editor::REPL_COMMANDS;
 pub use session::EvaluationOutput;
 pub use session::ReplSession;
 pub use session::REPL_INTERNALS_NAME;
@@ -37,6 +39,8 @@ struct Repl {
   session: ReplSession,
   editor: ReplEditor,
   message_handler: RustylineSyncMessageHandler,
+  /// The inputs which were evaluated successfully, which `.save` writes.
+  evaluated_inputs: Vec<String>,
 }
 
 impl Repl {
@@ -46,13 +50,18 @@ impl Repl {
         &mut self.session,
         &mut self.message_handler,
         self.editor.clone(),
+        "> ",
       )
       .await;
       match line {
         Ok(line) => {
           self.editor.set_should_exit_on_interrupt(false);
           self.editor.update_history(line.clone());
-          let output = self.session.evaluate_line_and_get_output(&line).await;
+          let output = match ReplCommand::parse(&line) {
+            Some(ReplCommand::Exit) => break,
+            Some(command) => self.run_command(command).await,
+            None => self.evaluate(&line).await,
+          };
 
           // We check for close and break here instead of making it a loop condition to get
           // consistent behavior in when the user evaluates a call to close().
@@ -82,14 +91,119 @@ impl Repl {
 
     Ok(())
   }
+
+  async fn evaluate(&mut self, input: &str) -> EvaluationOutput {
+    let output = self.session.evaluate_line_and_get_output(input).await;
+    if matches!(output, EvaluationOutput::Value(_)) {
+      self.evaluated_inputs.push(input.to_string());
+    }
+    output
+  }
+
+  async fn run_command(
+    &mut self,
+    command: ReplCommand<'_>,
+  ) -> EvaluationOutput {
+    match command {
+      ReplCommand::Clear => {
+        self.editor.clear_screen();
+        self.evaluated_inputs.clear();
+        EvaluationOutput::Value("Cleared the inputs recorded for .save".into())
+      }
+      ReplCommand::Editor => match self.read_editor_input().await {
+        Some(input) => {
+          self.editor.update_history(input.clone());
+          self.evaluate(&input).await
+        }
+        None => EvaluationOutput::Value(String::new()),
+      },
+      ReplCommand::Exit => unreachable!(),
+      ReplCommand::Help => {
+        let width = REPL_COMMANDS
+          .iter()
+          .map(|(usage, _)| usage.len())
+          .max()
+          .unwrap_or(0);
+        EvaluationOutput::Value(
+          REPL_COMMANDS
+            .iter()
+            .map(|(usage, description)| {
+              format!("{usage:width$}  {description}")
+            })
+            .chain(["".to_string(), "Press ctrl+d to exit.".to_string()])
+            .collect::<Vec<_>>()
+            .join("\n"),
+        )
+      }
+      ReplCommand::Load(path) => {
+        if path.is_empty() {
+          return command_error("Usage: .load <file>");
+        }
+        match std::fs::read_to_string(path) {
+          Ok(source) => self.evaluate(&source).await,
+          Err(err) => command_error(&format!("Failed to read '{path}': {err}")),
+        }
+      }
+      ReplCommand::Save(path) => {
+        if path.is_empty() {
+          return command_error("Usage: .save <file>");
+        }
+        let mut text = self.evaluated_inputs.join("\n");
+        text.push('\n');
+        match std::fs::write(path, text) {
+          Ok(()) => EvaluationOutput::Value(format!(
+            "Saved {} input(s) to '{path}'",
+            self.evaluated_inputs.len()
+          )),
+          Err(err) => command_error(&format!("Failed to save '{path}': {err}")),
+        }
+      }
+      ReplCommand::Type(expr) => {
+        self.session.get_expression_type_output(expr).await
+      }
+      ReplCommand::Unknown(name) => command_error(&format!(
+        "Unknown command '.{name}'. Type .help for the list of commands."
+      )),
+    }
+  }
+
+  /// Reads lines until ctrl+d is pressed on an empty line, returning `None`
+  /// when it's cancelled with ctrl+c.
+  async fn read_editor_input(&mut self) -> Option<String> {
+    println!("// Entering editor mode (ctrl+d to evaluate, ctrl+c to cancel)");
+    let mut lines = Vec::new();
+    loop {
+      let line = read_line_and_poll(
+        &mut self.session,
+        &mut self.message_handler,
+        self.editor.clone(),
+        "",
+      )
+      .await;
+      match line {
+        Ok(line) => lines.push(line),
+        Err(ReadlineError::Eof) => return Some(lines.join("\n")),
+        Err(ReadlineError::Interrupted) => return None,
+        Err(err) => {
+          println!("Error: {err:?}");
+          return None;
+        }
+      }
+    }
+  }
+}
+
+fn command_error(message: &str) -> EvaluationOutput {
+  EvaluationOutput::Error(format!("{} {}", colors::red("error:"), message))
 }
 
 async fn read_line_and_poll(
   repl_session: &mut ReplSession,
   message_handler: &mut RustylineSyncMessageHandler,
   editor: ReplEditor,
+  prompt: &'static str,
 ) -> Result<String, ReadlineError> {
-  let mut line_fut = spawn_blocking(move || editor.readline());
+  let mut line_fut = spawn_blocking(move || editor.readline(prompt));
   let mut poll_worker = true;
   let notifications_rc = repl_session.notifications.clone();
   let mut notifications = notifications_rc.lock().await;
@@ -204,6 +318,7 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
     session,
     editor,
     message_handler: rustyline_channel.1,
+    evaluated_inputs: Vec::new(),
   };
 
   if let Some(eval_files) = repl_flags.eval_files {
@@ -239,6 +354,7 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
   if !cli_options.is_quiet() {
     println!("Deno {}", crate::version::deno());
     println!("exit using ctrl+d, ctrl+c, or close()");
+    println!("type .help for the REPL commands");
     if repl_flags.is_default_command {
       println!(
         "{}",
@@ -132,6 +132,16 @@ globalThis.clear = console.clear.bind(console);
   )
 }
 
+/// Wraps a line which starts like an object literal in parens, so it's
+/// evaluated as an expression instead of a block.
+fn wrap_object_literal(line: &str) -> Option<String> {
+  if line.trim_start().starts_with('{') && !line.trim_end().ends_with(';') {
+    Some(format!("({line})"))
+  } else {
+    None
+  }
+}
+
 pub enum EvaluationOutput {
   Value(String),
   Error(String),
@@ -184,6 +194,8 @@ pub struct ReplSession {
   /// This is only optional because it's temporarily taken when evaluating.
   test_event_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<TestEvent>>,
   jsx: ReplJsxState,
+  /// Whether the input is type checked before it's evaluated (`--check`).
+  type_check: bool,
 }
 
 impl ReplSession {
@@ -260,6 +272,7 @@ impl ReplSession {
         frag_factory: "React.Fragment".to_string(),
         import_source: None,
       },
+      type_check: cli_options.type_check_mode().is_true(),
     };
 
     // inject prelude
@@ -332,6 +345,11 @@ impl ReplSession {
       session: &mut ReplSession,
       line: &str,
     ) -> Result<EvaluationOutput, AnyError> {
+      if session.type_check {
+        if let Some(errors) = session.type_check_line(line).await {
+          return Ok(EvaluationOutput::Error(errors));
+        }
+      }
       match session.evaluate_line_with_object_wrapping(line).await {
         Ok(evaluate_response) => {
           let cdp::EvaluateResponse {
@@ -394,6 +412,57 @@ impl ReplSession {
     result_to_evaluation_output(result)
   }
 
+  /// Type checks the line against the previously evaluated code, returning
+  /// the formatted errors when there are any.
+  async fn type_check_line(&mut self, line: &str) -> Option<String> {
+    let diagnostics = self.language_server.type_check(line).await;
+    if diagnostics.is_empty() {
+      return None;
+    }
+    // check the line the way it's evaluated when it's an object literal
+    if let Some(wrapped_line) = wrap_object_literal(line) {
+      if self
+        .language_server
+        .type_check(&wrapped_line)
+        .await
+        .is_empty()
+      {
+        return None;
+      }
+    }
+    Some(
+      diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n"),
+    )
+  }
+
+  /// Gets the inferred TypeScript type of an expression, which is shown by
+  /// the `:type` command.
+  pub async fn get_expression_type_output(
+    &mut self,
+    expr: &str,
+  ) -> EvaluationOutput {
+    let expr = expr.trim().trim_end_matches(';');
+    if expr.is_empty() {
+      return EvaluationOutput::Error(format!(
+        "{} Usage: :type <expression>",
+        colors::red("error:")
+      ));
+    }
+    match self.language_server.expression_type(expr).await {
+      Some(ts_type) => {
+        EvaluationOutput::Value(colors::cyan(ts_type).to_string())
+      }
+      None => EvaluationOutput::Error(format!(
+        "{} Could not get the type of the expression.",
+        colors::red("error:")
+      )),
+    }
+  }
+
   pub async fn evaluate_line_with_object_wrapping(
     &mut self,
     line: &str,
@@ -401,13 +470,8 @@ impl ReplSession {
     // Expressions like { "foo": "bar" } are interpreted as block expressions at the
     // statement level rather than an object literal so we interpret it as an expression statement
     // to match the behavior found in a typical prompt including browser developer tools.
-    let wrapped_line = if line.trim_start().starts_with('{')
-      && !line.trim_end().ends_with(';')
-    {
-      format!("({})", &line)
-    } else {
-      line.to_string()
-    };
+    let wrapped_line =
+      wrap_object_literal(line).unwrap_or_else(|| line.to_string());
 
     let evaluate_response = self.evaluate_ts_expression(&wrapped_line).await;
 
@@ -0,0 +1,1042 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use crate::args::Flags;
+use crate::args::TaskDefinition;
+use crate::args::TaskFlags;
+use crate::cache::FastInsecureHasher;
+use crate::cache::TaskCache;
+use crate::colors;
+use crate::factory::CliFactory;
+use crate::npm::CliNpmResolver;
+use crate::npm::InnerCliNpmResolverRef;
+use crate::npm::ManagedCliNpmResolver;
+use crate::util::fs::canonicalize_path;
+use crate::util::fs::FileCollector;
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPatternSet;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::futures;
+use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::FutureExt;
+use deno_core::futures::StreamExt;
+use deno_runtime::deno_node::NodeResolver;
+use deno_semver::package::PackageNv;
+use deno_task_shell::parser::SequentialList;
+use deno_task_shell::ExecuteResult;
+use deno_task_shell::ShellCommand;
+use deno_task_shell::ShellCommandContext;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellPipeWriter;
+use deno_task_shell::ShellState;
+use indexmap::IndexMap;
+use lazy_regex::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use tokio::task::LocalSet;
+
+mod workspace;
+
+pub async fn execute_script(
+  flags: Flags,
+  task_flags: TaskFlags,
+) -> Result<i32, AnyError> {
+  let factory = CliFactory::from_flags(flags).await?;
+  let cli_options = factory.cli_options();
+  let tasks_config = cli_options.resolve_tasks_config()?;
+  let maybe_package_json = cli_options.maybe_package_json();
+  let package_json_scripts = maybe_package_json
+    .as_ref()
+    .and_then(|p| p.scripts.clone())
+    .unwrap_or_default();
+
+  let task_name = match &task_flags.task {
+    Some(task) => task,
+    None => {
+      print_available_tasks(&tasks_config, &package_json_scripts);
+      return Ok(1);
+    }
+  };
+
+  // with --parallel the arguments are the other tasks to run
+  let (task_names, argv) = if task_flags.parallel {
+    let mut task_names = vec![task_name.clone()];
+    task_names.extend(cli_options.argv().iter().cloned());
+    (task_names, &[] as &[String])
+  } else {
+    (vec![task_name.clone()], cli_options.argv().as_slice())
+  };
+
+  if task_flags.recursive {
+    let task_cache = Rc::new(TaskCache::new(factory.caches()?.task_cache_db()));
+    return workspace::run_workspace_tasks(
+      cli_options,
+      &task_names,
+      argv,
+      task_flags.filter.as_deref(),
+      task_cache,
+    )
+    .await;
+  }
+
+  if task_flags.parallel || tasks_config.contains_key(task_name) {
+    for task_name in &task_names {
+      if !tasks_config.contains_key(task_name) {
+        eprintln!("Task not found: {task_name}");
+        print_available_tasks(&tasks_config, &package_json_scripts);
+        return Ok(1);
+      }
+    }
+    let config_file_url = cli_options.maybe_config_file_specifier().unwrap();
+    let config_file_path = if config_file_url.scheme() == "file" {
+      config_file_url.to_file_path().unwrap()
+    } else {
+      bail!("Only local configuration files are supported")
+    };
+    let cwd = match task_flags.cwd {
+      Some(path) => canonicalize_path(&PathBuf::from(path))?,
+      None => config_file_path.parent().unwrap().to_owned(),
+    };
+    let task_cache = Rc::new(TaskCache::new(factory.caches()?.task_cache_db()));
+    run_deno_tasks(
+      &tasks_config,
+      &task_names,
+      argv,
+      &cwd,
+      &config_file_path,
+      task_cache,
+    )
+    .await
+  } else if package_json_scripts.contains_key(task_name) {
+    let package_json_deps_provider = factory.package_json_deps_provider();
+    let npm_resolver = factory.npm_resolver().await?;
+    let node_resolver = factory.node_resolver().await?;
+
+    if let Some(package_deps) = package_json_deps_provider.deps() {
+      for (key, value) in package_deps {
+        if let Err(err) = value {
+          log::info!(
+            "{} Ignoring dependency '{}' in package.json because its version requirement failed to parse: {:#}",
+            colors::yellow("Warning"),
+            key,
+            err,
+          );
+        }
+      }
+    }
+
+    // ensure the npm packages are installed if using a node_modules
+    // directory and managed resolver
+    if cli_options.has_node_modules_dir() {
+      if let Some(npm_resolver) = npm_resolver.as_managed() {
+        npm_resolver.ensure_top_level_package_json_install().await?;
+        npm_resolver.resolve_pending().await?;
+      }
+    }
+
+    let cwd = match task_flags.cwd {
+      Some(path) => canonicalize_path(&PathBuf::from(path))?,
+      None => maybe_package_json
+        .as_ref()
+        .unwrap()
+        .path
+        .parent()
+        .unwrap()
+        .to_owned(),
+    };
+
+    // At this point we already checked if the task name exists in package.json.
+    // We can therefore check for "pre" and "post" scripts too, since we're only
+    // dealing with package.json here and not deno.json
+    let task_names = vec![
+      format!("pre{}", task_name),
+      task_name.clone(),
+      format!("post{}", task_name),
+    ];
+    for task_name in task_names {
+      if let Some(script) = package_json_scripts.get(&task_name) {
+        let script = get_script_with_args(script, cli_options.argv());
+        output_task(&task_name, &script);
+        let seq_list = deno_task_shell::parser::parse(&script)
+          .with_context(|| format!("Error parsing script '{task_name}'."))?;
+        let npx_commands = match npm_resolver.as_inner() {
+          InnerCliNpmResolverRef::Managed(npm_resolver) => {
+            resolve_npm_commands(npm_resolver, node_resolver)?
+          }
+          InnerCliNpmResolverRef::Byonm(npm_resolver) => {
+            let node_modules_dir =
+              npm_resolver.root_node_modules_path().unwrap();
+            resolve_npm_commands_from_bin_dir(node_modules_dir)?
+          }
+        };
+        let env_vars = match npm_resolver.root_node_modules_path() {
+          Some(dir_path) => collect_env_vars_with_node_modules_dir(dir_path),
+          None => collect_env_vars(),
+        };
+        let local = LocalSet::new();
+        let future =
+          deno_task_shell::execute(seq_list, env_vars, &cwd, npx_commands);
+        let exit_code = local.run_until(future).await;
+        if exit_code > 0 {
+          return Ok(exit_code);
+        }
+      }
+    }
+
+    Ok(0)
+  } else {
+    eprintln!("Task not found: {task_name}");
+    print_available_tasks(&tasks_config, &package_json_scripts);
+    Ok(1)
+  }
+}
+
+fn get_script_with_args(script: &str, argv: &[String]) -> String {
+  let additional_args = argv
+    .iter()
+    // surround all the additional arguments in double quotes
+    // and sanitize any command substitution
+    .map(|a| format!("\"{}\"", a.replace('"', "\\\"").replace('$', "\\$")))
+    .collect::<Vec<_>>()
+    .join(" ");
+  let script = format!("{script} {additional_args}");
+  script.trim().to_owned()
+}
+
+/// Runs the tasks along with the tasks they depend on. A task is started once
+/// all of its dependencies succeeded, so tasks that don't depend on each other
+/// run at the same time. After a task fails, no more tasks are started.
+async fn run_deno_tasks(
+  tasks_config: &IndexMap<String, TaskDefinition>,
+  task_names: &[String],
+  argv: &[String],
+  cwd: &Path,
+  config_file_path: &Path,
+  task_cache: Rc<TaskCache>,
+) -> Result<i32, AnyError> {
+  let sorted_task_names = sort_tasks_by_dependencies(tasks_config, task_names)?;
+  let prefix_output = has_concurrent_tasks(tasks_config, &sorted_task_names);
+
+  // parse all the scripts up front so that a syntax error doesn't surface
+  // after other tasks already ran
+  let mut scripts = HashMap::with_capacity(sorted_task_names.len());
+  for task_name in &sorted_task_names {
+    let definition = &tasks_config[task_name.as_str()];
+    let command = &definition.command;
+    if command.is_empty() {
+      continue;
+    }
+    // only the tasks provided on the command line receive the arguments
+    let script = if task_names.contains(task_name) {
+      get_script_with_args(command, argv)
+    } else {
+      command.clone()
+    };
+    let seq_list = deno_task_shell::parser::parse(&script)
+      .with_context(|| format!("Error parsing script '{task_name}'."))?;
+    let maybe_cache_info =
+      TaskCacheInfo::new(config_file_path, task_name, definition)?;
+    scripts.insert(task_name.clone(), (script, seq_list, maybe_cache_info));
+  }
+
+  let local = LocalSet::new();
+  let future = async move {
+    let mut pending = sorted_task_names;
+    let mut completed = HashSet::with_capacity(pending.len());
+    let mut running = FuturesUnordered::new();
+    let mut exit_code = 0;
+    loop {
+      if exit_code == 0 {
+        pending.retain(|task_name| {
+          let is_ready = tasks_config[task_name.as_str()]
+            .depends_on
+            .iter()
+            .all(|dependency| completed.contains(dependency));
+          if is_ready {
+            let task_name = task_name.clone();
+            let maybe_script = scripts.remove(&task_name);
+            let cwd = cwd.to_path_buf();
+            let task_cache = task_cache.clone();
+            running.push(
+              async move {
+                let exit_code = match maybe_script {
+                  Some((script, seq_list, maybe_cache_info)) => {
+                    run_task_with_cache(
+                      &task_name,
+                      &script,
+                      seq_list,
+                      &cwd,
+                      prefix_output,
+                      maybe_cache_info
+                        .as_ref()
+                        .map(|info| (info, &*task_cache)),
+                    )
+                    .await
+                  }
+                  None => 0,
+                };
+                (task_name, exit_code)
+              }
+              .boxed_local(),
+            );
+          }
+          !is_ready
+        });
+      }
+      let Some((task_name, task_exit_code)) = running.next().await else {
+        break;
+      };
+      if task_exit_code != 0 && exit_code == 0 {
+        exit_code = task_exit_code;
+      }
+      completed.insert(task_name);
+    }
+    exit_code
+  };
+  Ok(local.run_until(future).await)
+}
+
+/// Gets the tasks and their transitive dependencies, with each task after
+/// the tasks it depends on.
+fn sort_tasks_by_dependencies(
+  tasks_config: &IndexMap<String, TaskDefinition>,
+  task_names: &[String],
+) -> Result<Vec<String>, AnyError> {
+  fn visit(
+    task_name: &str,
+    tasks_config: &IndexMap<String, TaskDefinition>,
+    path: &mut Vec<String>,
+    result: &mut Vec<String>,
+  ) -> Result<(), AnyError> {
+    if result.iter().any(|name| name == task_name) {
+      return Ok(());
+    }
+    if let Some(index) = path.iter().position(|name| name == task_name) {
+      let mut cycle = path[index..].to_vec();
+      cycle.push(task_name.to_string());
+      bail!("Task dependency cycle detected: {}", cycle.join(" -> "));
+    }
+    let Some(definition) = tasks_config.get(task_name) else {
+      bail!("Task not found: {task_name}");
+    };
+    path.push(task_name.to_string());
+    for dependency in &definition.depends_on {
+      visit(dependency, tasks_config, path, result)?;
+    }
+    path.pop();
+    result.push(task_name.to_string());
+    Ok(())
+  }
+
+  let mut result = Vec::new();
+  for task_name in task_names {
+    visit(task_name, tasks_config, &mut Vec::new(), &mut result)?;
+  }
+  Ok(result)
+}
+
+/// Gets if any of the sorted tasks could run at the same time, which is when
+/// they aren't a single chain of dependencies.
+fn has_concurrent_tasks(
+  tasks_config: &IndexMap<String, TaskDefinition>,
+  sorted_task_names: &[String],
+) -> bool {
+  let mut depths: HashMap<&str, usize> = HashMap::new();
+  for task_name in sorted_task_names {
+    let depth = tasks_config[task_name.as_str()]
+      .depends_on
+      .iter()
+      .map(|dependency| depths[dependency.as_str()] + 1)
+      .max()
+      .unwrap_or(0);
+    if depths.values().any(|other_depth| *other_depth == depth) {
+      return true;
+    }
+    depths.insert(task_name.as_str(), depth);
+  }
+  false
+}
+
+/// Runs the task unless it has inputs which didn't change since its last
+/// successful run and its outputs exist.
+async fn run_task_with_cache(
+  task_name: &str,
+  script: &str,
+  seq_list: SequentialList,
+  cwd: &Path,
+  prefix_output: bool,
+  maybe_cache: Option<(&TaskCacheInfo, &TaskCache)>,
+) -> i32 {
+  let env_vars = collect_env_vars();
+  let maybe_inputs_hash = maybe_cache.and_then(|(cache_info, _)| {
+    match cache_info.hash_inputs(script, &env_vars) {
+      Ok(hash) => Some(hash),
+      Err(err) => {
+        log::debug!("Failed hashing the inputs of task '{task_name}': {err:#}");
+        None
+      }
+    }
+  });
+  if let (Some((cache_info, task_cache)), Some(inputs_hash)) =
+    (maybe_cache, maybe_inputs_hash)
+  {
+    if task_cache.get_inputs_hash(&cache_info.key) == Some(inputs_hash)
+      && cache_info.outputs_exist()
+    {
+      log::info!(
+        "{} {} {}",
+        colors::green("Task"),
+        colors::cyan(&task_name),
+        colors::gray("(up to date)"),
+      );
+      return 0;
+    }
+  }
+
+  output_task(task_name, script);
+  let exit_code =
+    run_task(task_name, seq_list, env_vars, cwd, prefix_output).await;
+  if exit_code == 0 {
+    if let (Some((cache_info, task_cache)), Some(inputs_hash)) =
+      (maybe_cache, maybe_inputs_hash)
+    {
+      task_cache.set_inputs_hash(&cache_info.key, inputs_hash);
+    }
+  }
+  exit_code
+}
+
+/// The inputs and outputs of a task, used to skip running it when it's up
+/// to date.
+struct TaskCacheInfo {
+  key: String,
+  inputs: FilePatterns,
+  outputs: Vec<FilePatterns>,
+}
+
+impl TaskCacheInfo {
+  fn new(
+    config_file_path: &Path,
+    task_name: &str,
+    definition: &TaskDefinition,
+  ) -> Result<Option<Self>, AnyError> {
+    if definition.inputs.is_empty() {
+      return Ok(None);
+    }
+    let config_dir = config_file_path.parent().unwrap();
+    let file_patterns =
+      |patterns: &[String]| -> Result<FilePatterns, AnyError> {
+        Ok(FilePatterns {
+          base: config_dir.to_path_buf(),
+          include: Some(PathOrPatternSet::from_relative_path_or_patterns(
+            config_dir, patterns,
+          )?),
+          exclude: Default::default(),
+        })
+      };
+    let inputs = file_patterns(&definition.inputs)
+      .with_context(|| format!("Invalid inputs of task '{task_name}'."))?;
+    let outputs = definition
+      .outputs
+      .iter()
+      .map(|output| file_patterns(std::slice::from_ref(output)))
+      .collect::<Result<Vec<_>, _>>()
+      .with_context(|| format!("Invalid outputs of task '{task_name}'."))?;
+    Ok(Some(Self {
+      key: format!("{}#{}", config_file_path.display(), task_name),
+      inputs,
+      outputs,
+    }))
+  }
+
+  /// Hashes the script, the environment variables and the input files.
+  fn hash_inputs(
+    &self,
+    script: &str,
+    env_vars: &HashMap<String, String>,
+  ) -> Result<u64, AnyError> {
+    // variables which are changed by shells without affecting tasks
+    const IGNORED_ENV_VARS: [&str; 4] = ["_", "OLDPWD", "PWD", "SHLVL"];
+
+    let mut hasher = FastInsecureHasher::new();
+    hasher.write_hashable(script);
+    let mut env_vars = env_vars
+      .iter()
+      .filter(|(name, _)| !IGNORED_ENV_VARS.contains(&name.as_str()))
+      .collect::<Vec<_>>();
+    env_vars.sort();
+    hasher.write_hashable(env_vars);
+    let mut input_paths = collect_task_files(self.inputs.clone())?;
+    input_paths.sort();
+    for path in input_paths {
+      hasher.write_hashable(&path);
+      hasher.write(&std::fs::read(&path)?);
+    }
+    Ok(hasher.finish())
+  }
+
+  fn outputs_exist(&self) -> bool {
+    self.outputs.iter().all(|output| {
+      collect_task_files(output.clone())
+        .map(|paths| !paths.is_empty())
+        .unwrap_or(false)
+    })
+  }
+}
+
+fn collect_task_files(
+  file_patterns: FilePatterns,
+) -> Result<Vec<PathBuf>, AnyError> {
+  FileCollector::new(|_, _| true)
+    .ignore_git_folder()
+    .ignore_node_modules()
+    .collect_file_patterns(file_patterns)
+}
+
+async fn run_task(
+  task_name: &str,
+  seq_list: SequentialList,
+  env_vars: HashMap<String, String>,
+  cwd: &Path,
+  prefix_output: bool,
+) -> i32 {
+  if !prefix_output {
+    return deno_task_shell::execute(
+      seq_list,
+      env_vars,
+      cwd,
+      Default::default(),
+    )
+    .await;
+  }
+
+  let prefix = format!("[{}] ", colors::cyan(task_name));
+  let (stdout_reader, stdout_writer) = deno_task_shell::pipe();
+  let (stderr_reader, stderr_writer) = deno_task_shell::pipe();
+  let stdout_handle = {
+    let prefix = prefix.clone();
+    tokio::task::spawn_blocking(move || {
+      write_prefixed_lines(stdout_reader, &prefix, &mut std::io::stdout())
+    })
+  };
+  let stderr_handle = tokio::task::spawn_blocking(move || {
+    write_prefixed_lines(stderr_reader, &prefix, &mut std::io::stderr())
+  });
+  let state = ShellState::new(env_vars, cwd, Default::default());
+  // the writers are dropped once the task is done, which ends the readers
+  let exit_code = deno_task_shell::execute_with_pipes(
+    seq_list,
+    state,
+    ShellPipeReader::stdin(),
+    stdout_writer,
+    stderr_writer,
+  )
+  .await;
+  let _ = stdout_handle.await;
+  let _ = stderr_handle.await;
+  exit_code
+}
+
+/// Copies the output of a task to the writer, prefixing each line so the
+/// output of tasks which run at the same time can be told apart.
+fn write_prefixed_lines(
+  reader: ShellPipeReader,
+  prefix: &str,
+  writer: &mut dyn Write,
+) -> Result<(), AnyError> {
+  let mut writer = PrefixedLineWriter {
+    prefix: prefix.as_bytes(),
+    line: Vec::new(),
+    inner: writer,
+  };
+  reader.pipe_to(&mut writer)?;
+  writer.finish()?;
+  Ok(())
+}
+
+struct PrefixedLineWriter<'a> {
+  prefix: &'a [u8],
+  line: Vec<u8>,
+  inner: &'a mut dyn Write,
+}
+
+impl<'a> PrefixedLineWriter<'a> {
+  fn write_line(&mut self) -> std::io::Result<()> {
+    // write each line at once so lines of other tasks don't interleave
+    let mut output = Vec::with_capacity(self.prefix.len() + self.line.len());
+    output.extend_from_slice(self.prefix);
+    output.append(&mut self.line);
+    self.inner.write_all(&output)?;
+    self.inner.flush()
+  }
+
+  /// Writes the last line when the output didn't end with a newline.
+  fn finish(&mut self) -> std::io::Result<()> {
+    if self.line.is_empty() {
+      return Ok(());
+    }
+    self.line.push(b'\n');
+    self.write_line()
+  }
+}
+
+impl<'a> Write for PrefixedLineWriter<'a> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    for byte in buf {
+      self.line.push(*byte);
+      if *byte == b'\n' {
+        self.write_line()?;
+      }
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+fn output_task(task_name: &str, script: &str) {
+  log::info!(
+    "{} {} {}",
+    colors::green("Task"),
+    colors::cyan(&task_name),
+    script,
+  );
+}
+
+fn collect_env_vars_with_node_modules_dir(
+  node_modules_dir_path: &Path,
+) -> HashMap<String, String> {
+  let mut env_vars = collect_env_vars();
+  prepend_to_path(
+    &mut env_vars,
+    node_modules_dir_path
+      .join(".bin")
+      .to_string_lossy()
+      .to_string(),
+  );
+  env_vars
+}
+
+fn prepend_to_path(env_vars: &mut HashMap<String, String>, value: String) {
+  match env_vars.get_mut("PATH") {
+    Some(path) => {
+      if path.is_empty() {
+        *path = value;
+      } else {
+        *path =
+          format!("{}{}{}", value, if cfg!(windows) { ";" } else { ":" }, path);
+      }
+    }
+    None => {
+      env_vars.insert("PATH".to_string(), value);
+    }
+  }
+}
+
+fn collect_env_vars() -> HashMap<String, String> {
+  // get the starting env vars (the PWD env var will be set by deno_task_shell)
+  let mut env_vars = std::env::vars().collect::<HashMap<String, String>>();
+  const INIT_CWD_NAME: &str = "INIT_CWD";
+  if !env_vars.contains_key(INIT_CWD_NAME) {
+    if let Ok(cwd) = std::env::current_dir() {
+      // if not set, set an INIT_CWD env var that has the cwd
+      env_vars
+        .insert(INIT_CWD_NAME.to_string(), cwd.to_string_lossy().to_string());
+    }
+  }
+  env_vars
+}
+
+fn print_available_tasks(
+  // order can be important, so these use an index map
+  tasks_config: &IndexMap<String, TaskDefinition>,
+  package_json_scripts: &IndexMap<String, String>,
+) {
+  eprintln!("{}", colors::green("Available tasks:"));
+
+  let mut had_task = false;
+  for (is_deno, key, command, depends_on) in tasks_config
+    .iter()
+    .map(|(key, definition)| {
+      (
+        true,
+        key,
+        &definition.command,
+        definition.depends_on.as_slice(),
+      )
+    })
+    .chain(
+      package_json_scripts
+        .iter()
+        .filter(|(key, _)| !tasks_config.contains_key(*key))
+        .map(|(key, value)| (false, key, value, &[] as &[String])),
+    )
+  {
+    eprintln!(
+      "- {}{}",
+      colors::cyan(key),
+      if is_deno {
+        "".to_string()
+      } else {
+        format!(" {}", colors::italic_gray("(package.json)"))
+      }
+    );
+    if !command.is_empty() {
+      eprintln!("    {command}");
+    }
+    if !depends_on.is_empty() {
+      eprintln!(
+        "    {} {}",
+        colors::gray("depends on:"),
+        depends_on.join(", ")
+      );
+    }
+    had_task = true;
+  }
+  if !had_task {
+    eprintln!("  {}", colors::red("No tasks found in configuration file"));
+  }
+}
+
+struct NpxCommand;
+
+impl ShellCommand for NpxCommand {
+  fn execute(
+    &self,
+    mut context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    if let Some(first_arg) = context.args.first().cloned() {
+      if let Some(command) = context.state.resolve_command(&first_arg) {
+        let context = ShellCommandContext {
+          args: context.args.iter().skip(1).cloned().collect::<Vec<_>>(),
+          ..context
+        };
+        command.execute(context)
+      } else {
+        let _ = context
+          .stderr
+          .write_line(&format!("npx: could not resolve command '{first_arg}'"));
+        Box::pin(futures::future::ready(ExecuteResult::from_exit_code(1)))
+      }
+    } else {
+      let _ = context.stderr.write_line("npx: missing command");
+      Box::pin(futures::future::ready(ExecuteResult::from_exit_code(1)))
+    }
+  }
+}
+
+#[derive(Clone)]
+struct NpmPackageBinCommand {
+  name: String,
+  npm_package: PackageNv,
+}
+
+impl ShellCommand for NpmPackageBinCommand {
+  fn execute(
+    &self,
+    context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    let mut args = vec![
+      "run".to_string(),
+      "-A".to_string(),
+      if self.npm_package.name == self.name {
+        format!("npm:{}", self.npm_package)
+      } else {
+        format!("npm:{}/{}", self.npm_package, self.name)
+      },
+    ];
+    args.extend(context.args);
+    let executable_command =
+      deno_task_shell::ExecutableCommand::new("deno".to_string());
+    executable_command.execute(ShellCommandContext { args, ..context })
+  }
+}
+
+/// Runs a module in the node_modules folder.
+#[derive(Clone)]
+struct NodeModulesFileRunCommand {
+  command_name: String,
+  path: PathBuf,
+}
+
+impl ShellCommand for NodeModulesFileRunCommand {
+  fn execute(
+    &self,
+    mut context: ShellCommandContext,
+  ) -> LocalBoxFuture<'static, ExecuteResult> {
+    let mut args = vec![
+      "run".to_string(),
+      "--ext=js".to_string(),
+      "-A".to_string(),
+      self.path.to_string_lossy().to_string(),
+    ];
+    args.extend(context.args);
+    let executable_command =
+      deno_task_shell::ExecutableCommand::new("deno".to_string());
+    // set this environment variable so that the launched process knows the npm command name
+    context
+      .state
+      .apply_env_var("DENO_INTERNAL_NPM_CMD_NAME", &self.command_name);
+    executable_command.execute(ShellCommandContext { args, ..context })
+  }
+}
+
+fn resolve_npm_commands_from_bin_dir(
+  node_modules_dir: &Path,
+) -> Result<HashMap<String, Rc<dyn ShellCommand>>, AnyError> {
+  let mut result = HashMap::<String, Rc<dyn ShellCommand>>::new();
+  let bin_dir = node_modules_dir.join(".bin");
+  log::debug!("Resolving commands in '{}'.", bin_dir.display());
+  match std::fs::read_dir(&bin_dir) {
+    Ok(entries) => {
+      for entry in entries {
+        let Ok(entry) = entry else {
+          continue;
+        };
+        if let Some(command) = resolve_bin_dir_entry_command(entry) {
+          result.insert(command.command_name.clone(), Rc::new(command));
+        }
+      }
+    }
+    Err(err) => {
+      log::debug!("Failed read_dir for '{}': {:#}", bin_dir.display(), err);
+    }
+  }
+  Ok(result)
+}
+
+fn resolve_bin_dir_entry_command(
+  entry: std::fs::DirEntry,
+) -> Option<NodeModulesFileRunCommand> {
+  if entry.path().extension().is_some() {
+    return None; // only look at files without extensions (even on Windows)
+  }
+  let file_type = entry.file_type().ok()?;
+  let path = if file_type.is_file() {
+    entry.path()
+  } else if file_type.is_symlink() {
+    entry.path().canonicalize().ok()?
+  } else {
+    return None;
+  };
+  let text = std::fs::read_to_string(&path).ok()?;
+  let command_name = entry.file_name().to_string_lossy().to_string();
+  if let Some(path) = resolve_execution_path_from_npx_shim(path, &text) {
+    log::debug!(
+      "Resolved npx command '{}' to '{}'.",
+      command_name,
+      path.display()
+    );
+    Some(NodeModulesFileRunCommand { command_name, path })
+  } else {
+    log::debug!("Failed resolving npx command '{}'.", command_name);
+    None
+  }
+}
+
+/// This is not ideal, but it works ok because it allows us to bypass
+/// the shebang and execute the script directly with Deno.
+fn resolve_execution_path_from_npx_shim(
+  file_path: PathBuf,
+  text: &str,
+) -> Option<PathBuf> {
+  static SCRIPT_PATH_RE: Lazy<Regex> =
+    lazy_regex::lazy_regex!(r#""\$basedir\/([^"]+)" "\$@""#);
+
+  if text.starts_with("#!/usr/bin/env node") {
+    // launch this file itself because it's a JS file
+    Some(file_path)
+  } else {
+    // Search for...
+    // > "$basedir/../next/dist/bin/next" "$@"
+    // ...which is what it will look like on Windows
+    SCRIPT_PATH_RE
+      .captures(text)
+      .and_then(|c| c.get(1))
+      .map(|relative_path| {
+        file_path.parent().unwrap().join(relative_path.as_str())
+      })
+  }
+}
+
+fn resolve_npm_commands(
+  npm_resolver: &ManagedCliNpmResolver,
+  node_resolver: &NodeResolver,
+) -> Result<HashMap<String, Rc<dyn ShellCommand>>, AnyError> {
+  let mut result = HashMap::new();
+  let snapshot = npm_resolver.snapshot();
+  for id in snapshot.top_level_packages() {
+    let package_folder = npm_resolver.resolve_pkg_folder_from_pkg_id(id)?;
+    let bin_commands =
+      node_resolver.resolve_binary_commands(&package_folder)?;
+    for bin_command in bin_commands {
+      result.insert(
+        bin_command.to_string(),
+        Rc::new(NpmPackageBinCommand {
+          name: bin_command,
+          npm_package: id.nv.clone(),
+        }) as Rc<dyn ShellCommand>,
+      );
+    }
+  }
+  if !result.contains_key("npx") {
+    result.insert("npx".to_string(), Rc::new(NpxCommand));
+  }
+  Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_prepend_to_path() {
+    let mut env_vars = HashMap::new();
+
+    prepend_to_path(&mut env_vars, "/example".to_string());
+    assert_eq!(
+      env_vars,
+      HashMap::from([("PATH".to_string(), "/example".to_string())])
+    );
+
+    prepend_to_path(&mut env_vars, "/example2".to_string());
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    assert_eq!(
+      env_vars,
+      HashMap::from([(
+        "PATH".to_string(),
+        format!("/example2{}/example", separator)
+      )])
+    );
+
+    env_vars.get_mut("PATH").unwrap().clear();
+    prepend_to_path(&mut env_vars, "/example".to_string());
+    assert_eq!(
+      env_vars,
+      HashMap::from([("PATH".to_string(), "/example".to_string())])
+    );
+  }
+
+  fn tasks_config(
+    tasks: &[(&str, &[&str])],
+  ) -> IndexMap<String, TaskDefinition> {
+    tasks
+      .iter()
+      .map(|(name, depends_on)| {
+        (
+          name.to_string(),
+          TaskDefinition {
+            command: format!("echo {name}"),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+          },
+        )
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_sort_tasks_by_dependencies() {
+    let config = tasks_config(&[
+      ("build", &["codegen", "assets"]),
+      ("codegen", &["fetch"]),
+      ("assets", &["fetch"]),
+      ("fetch", &[]),
+      ("lint", &[]),
+    ]);
+    assert_eq!(
+      sort_tasks_by_dependencies(&config, &["build".to_string()]).unwrap(),
+      vec!["fetch", "codegen", "assets", "build"]
+    );
+    assert_eq!(
+      sort_tasks_by_dependencies(
+        &config,
+        &["lint".to_string(), "codegen".to_string()]
+      )
+      .unwrap(),
+      vec!["lint", "fetch", "codegen"]
+    );
+
+    let config = tasks_config(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]);
+    assert_eq!(
+      sort_tasks_by_dependencies(&config, &["a".to_string()])
+        .unwrap_err()
+        .to_string(),
+      "Task dependency cycle detected: a -> b -> c -> a"
+    );
+  }
+
+  #[test]
+  fn test_has_concurrent_tasks() {
+    let config = tasks_config(&[
+      ("build", &["codegen"]),
+      ("codegen", &["fetch"]),
+      ("fetch", &[]),
+      ("lint", &[]),
+    ]);
+    let sorted = |names: &[&str]| {
+      let names = names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+      sort_tasks_by_dependencies(&config, &names).unwrap()
+    };
+    assert!(!has_concurrent_tasks(&config, &sorted(&["build"])));
+    assert!(has_concurrent_tasks(&config, &sorted(&["build", "lint"])));
+    assert!(!has_concurrent_tasks(&config, &sorted(&["lint"])));
+  }
+
+  #[test]
+  fn test_prefixed_line_writer() {
+    let mut output = Vec::new();
+    let mut writer = PrefixedLineWriter {
+      prefix: b"[a] ",
+      line: Vec::new(),
+      inner: &mut output,
+    };
+    writer.write_all(b"one\ntw").unwrap();
+    writer.write_all(b"o\nthree").unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      "[a] one\n[a] two\n[a] three\n"
+    );
+  }
+
+  #[test]
+  fn test_resolve_execution_path_from_npx_shim() {
+    // example shim on unix
+    let unix_shim = r#"#!/usr/bin/env node
+"use strict";
+console.log('Hi!');
+"#;
+    let path = PathBuf::from("/node_modules/.bin/example");
+    assert_eq!(
+      resolve_execution_path_from_npx_shim(path.clone(), unix_shim).unwrap(),
+      path
+    );
+    // example shim on windows
+    let windows_shim = r#"#!/bin/sh
+basedir=$(dirname "$(echo "$0" | sed -e 's,\\,/,g')")
+
+case `uname` in
+    *CYGWIN*|*MINGW*|*MSYS*) basedir=`cygpath -w "$basedir"`;;
+esac
+
+if [ -x "$basedir/node" ]; then
+  exec "$basedir/node"  "$basedir/../example/bin/example" "$@"
+else 
+  exec node  "$basedir/../example/bin/example" "$@"
+fi"#;
+    assert_eq!(
+      resolve_execution_path_from_npx_shim(path.clone(), windows_shim).unwrap(),
+      path.parent().unwrap().join("../example/bin/example")
+    );
+  }
+}
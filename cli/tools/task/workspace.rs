@@ -0,0 +1,350 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Running a task in every member of a workspace which defines it.
+
+use super::run_deno_tasks;
+use crate::args::resolve_tasks_config;
+use crate::args::CliOptions;
+use crate::cache::TaskCache;
+use crate::colors;
+use deno_config::WorkspaceMemberConfig;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+enum MemberTaskResult {
+  Ok,
+  Failed(i32),
+  /// A member it depends on failed.
+  Skipped,
+}
+
+/// Runs the tasks in every workspace member which defines them, after the
+/// members it depends on, and prints a summary of the results.
+pub async fn run_workspace_tasks(
+  cli_options: &CliOptions,
+  task_names: &[String],
+  argv: &[String],
+  maybe_filter: Option<&str>,
+  task_cache: Rc<TaskCache>,
+) -> Result<i32, AnyError> {
+  let Some(workspace_config) = cli_options.maybe_workspace_config() else {
+    bail!("The --recursive flag requires a workspace, which is configured with \"workspaces\" in the config file");
+  };
+  let members = &workspace_config.members;
+  let dependencies = members
+    .iter()
+    .map(|member| member_dependencies(member, members))
+    .collect::<Vec<_>>();
+  let sorted_indexes = sort_by_dependencies(&dependencies, |index| {
+    members[index].package_name.as_str()
+  })?;
+
+  let mut results = Vec::new();
+  // members which failed or were skipped
+  let mut unsuccessful = HashSet::new();
+  let mut exit_code = 0;
+  for index in sorted_indexes {
+    let member = &members[index];
+    if let Some(filter) = maybe_filter {
+      if !matches_filter(filter, &member.package_name)
+        && !matches_filter(filter, &member.member_name)
+      {
+        continue;
+      }
+    }
+    let tasks_config = resolve_tasks_config(&member.config_file)?;
+    let member_task_names = task_names
+      .iter()
+      .filter(|task_name| tasks_config.contains_key(*task_name))
+      .cloned()
+      .collect::<Vec<_>>();
+    if member_task_names.is_empty() {
+      continue;
+    }
+    if dependencies[index]
+      .iter()
+      .any(|dependency| unsuccessful.contains(dependency))
+    {
+      unsuccessful.insert(index);
+      results.push((&member.package_name, MemberTaskResult::Skipped));
+      continue;
+    }
+
+    log::info!(
+      "{} {}",
+      colors::green("Package"),
+      colors::cyan(&member.package_name)
+    );
+    let Ok(config_file_path) = member.config_file.specifier.to_file_path()
+    else {
+      bail!("Only local configuration files are supported")
+    };
+    let cwd = config_file_path.parent().unwrap();
+    let member_exit_code = match run_deno_tasks(
+      &tasks_config,
+      &member_task_names,
+      argv,
+      cwd,
+      &config_file_path,
+      task_cache.clone(),
+    )
+    .await
+    {
+      Ok(exit_code) => exit_code,
+      Err(err) => {
+        eprintln!("{}: {:#}", colors::red_bold("error"), err);
+        1
+      }
+    };
+    if member_exit_code == 0 {
+      results.push((&member.package_name, MemberTaskResult::Ok));
+    } else {
+      unsuccessful.insert(index);
+      results.push((
+        &member.package_name,
+        MemberTaskResult::Failed(member_exit_code),
+      ));
+      if exit_code == 0 {
+        exit_code = member_exit_code;
+      }
+    }
+  }
+
+  if results.is_empty() {
+    eprintln!(
+      "No workspace members define the task{} {}",
+      if task_names.len() == 1 { "" } else { "s" },
+      task_names.join(", ")
+    );
+    return Ok(1);
+  }
+
+  log::info!("");
+  log::info!("{}", colors::green("Summary:"));
+  for (package_name, result) in results {
+    match result {
+      MemberTaskResult::Ok => {
+        log::info!("  {}      {}", colors::green("ok"), package_name);
+      }
+      MemberTaskResult::Failed(exit_code) => {
+        log::info!(
+          "  {}  {} {}",
+          colors::red("failed"),
+          package_name,
+          colors::gray(format!("(exit code {exit_code})"))
+        );
+      }
+      MemberTaskResult::Skipped => {
+        log::info!(
+          "  {} {} {}",
+          colors::gray("skipped"),
+          package_name,
+          colors::gray("(a dependency failed)")
+        );
+      }
+    }
+  }
+  Ok(exit_code)
+}
+
+/// Gets the indexes of the other members which the member imports through
+/// the `imports` of its config file.
+fn member_dependencies(
+  member: &WorkspaceMemberConfig,
+  members: &[WorkspaceMemberConfig],
+) -> Vec<usize> {
+  let packages = members
+    .iter()
+    .map(|other| {
+      (
+        other.package_name.as_str(),
+        other.config_file.specifier.join("./").unwrap(),
+      )
+    })
+    .collect::<Vec<_>>();
+  let self_index = members
+    .iter()
+    .position(|other| other.package_name == member.package_name);
+  import_map_dependencies(
+    &member.config_file.to_import_map_value(),
+    &member.config_file.specifier,
+    &packages,
+  )
+  .into_iter()
+  .filter(|index| Some(*index) != self_index)
+  .collect()
+}
+
+/// Gets the indexes of the packages referenced by the `imports` of an import
+/// map, either by their name, a `jsr:` specifier or a path in their directory.
+fn import_map_dependencies(
+  import_map: &serde_json::Value,
+  base: &Url,
+  packages: &[(&str, Url)],
+) -> Vec<usize> {
+  let Some(imports) = import_map.get("imports").and_then(|i| i.as_object())
+  else {
+    return Vec::new();
+  };
+  let is_package_specifier = |specifier: &str, name: &str| {
+    specifier == name
+      || specifier
+        .strip_prefix(name)
+        .map(|rest| rest.starts_with('@') || rest.starts_with('/'))
+        .unwrap_or(false)
+  };
+  let mut result = Vec::new();
+  for (index, (name, dir_url)) in packages.iter().enumerate() {
+    let is_dependency = imports.iter().any(|(key, value)| {
+      if is_package_specifier(key, name) {
+        return true;
+      }
+      let Some(value) = value.as_str() else {
+        return false;
+      };
+      if let Some(jsr_specifier) = value.strip_prefix("jsr:") {
+        let jsr_specifier =
+          jsr_specifier.strip_prefix('/').unwrap_or(jsr_specifier);
+        return is_package_specifier(jsr_specifier, name);
+      }
+      base
+        .join(value)
+        .map(|url| url.as_str().starts_with(dir_url.as_str()))
+        .unwrap_or(false)
+    });
+    if is_dependency {
+      result.push(index);
+    }
+  }
+  result
+}
+
+/// Sorts the indexes of the items so that each one comes after its
+/// dependencies, otherwise keeping their order.
+fn sort_by_dependencies<'a>(
+  dependencies: &[Vec<usize>],
+  get_name: impl Fn(usize) -> &'a str,
+) -> Result<Vec<usize>, AnyError> {
+  fn visit<'a>(
+    index: usize,
+    dependencies: &[Vec<usize>],
+    get_name: &impl Fn(usize) -> &'a str,
+    path: &mut Vec<usize>,
+    result: &mut Vec<usize>,
+  ) -> Result<(), AnyError> {
+    if result.contains(&index) {
+      return Ok(());
+    }
+    if let Some(position) = path.iter().position(|i| *i == index) {
+      let mut cycle = path[position..]
+        .iter()
+        .map(|i| get_name(*i))
+        .collect::<Vec<_>>();
+      cycle.push(get_name(index));
+      bail!(
+        "Circular workspace member dependency detected: {}",
+        cycle.join(" -> ")
+      );
+    }
+    path.push(index);
+    for dependency in &dependencies[index] {
+      visit(*dependency, dependencies, get_name, path, result)?;
+    }
+    path.pop();
+    result.push(index);
+    Ok(())
+  }
+
+  let mut result = Vec::with_capacity(dependencies.len());
+  for index in 0..dependencies.len() {
+    visit(index, dependencies, &get_name, &mut Vec::new(), &mut result)?;
+  }
+  Ok(result)
+}
+
+/// Matches a name against a pattern where `*` matches any characters.
+fn matches_filter(pattern: &str, name: &str) -> bool {
+  let parts = pattern.split('*').collect::<Vec<_>>();
+  if parts.len() == 1 {
+    return pattern == name;
+  }
+  let Some(mut rest) = name.strip_prefix(parts[0]) else {
+    return false;
+  };
+  let last = parts[parts.len() - 1];
+  for part in &parts[1..parts.len() - 1] {
+    match rest.find(part) {
+      Some(index) => rest = &rest[index + part.len()..],
+      None => return false,
+    }
+  }
+  rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use deno_core::serde_json::json;
+
+  #[test]
+  fn test_import_map_dependencies() {
+    let base = Url::parse("file:///repo/app/deno.json").unwrap();
+    let packages = [
+      ("@scope/app", Url::parse("file:///repo/app/").unwrap()),
+      ("@scope/lib", Url::parse("file:///repo/lib/").unwrap()),
+      ("@scope/utils", Url::parse("file:///repo/utils/").unwrap()),
+      ("@scope/http", Url::parse("file:///repo/http/").unwrap()),
+      ("@scope/other", Url::parse("file:///repo/other/").unwrap()),
+    ];
+    let import_map = json!({
+      "imports": {
+        "@scope/lib": "./vendor/lib.ts",
+        "utils": "jsr:@scope/utils@^1.0.0",
+        "http/": "../http/",
+        "@scope/other-thing": "npm:other-thing",
+      }
+    });
+    assert_eq!(
+      import_map_dependencies(&import_map, &base, &packages),
+      vec![0, 1, 2, 3]
+    );
+    assert_eq!(
+      import_map_dependencies(&json!({}), &base, &packages),
+      Vec::<usize>::new()
+    );
+  }
+
+  #[test]
+  fn test_sort_by_dependencies() {
+    let names = ["a", "b", "c", "d"];
+    let dependencies = vec![vec![2], vec![], vec![1], vec![]];
+    assert_eq!(
+      sort_by_dependencies(&dependencies, |i| names[i]).unwrap(),
+      vec![1, 2, 0, 3]
+    );
+
+    let dependencies = vec![vec![1], vec![2], vec![0], vec![]];
+    assert_eq!(
+      sort_by_dependencies(&dependencies, |i| names[i])
+        .unwrap_err()
+        .to_string(),
+      "Circular workspace member dependency detected: a -> b -> c -> a"
+    );
+  }
+
+  #[test]
+  fn test_matches_filter() {
+    assert!(matches_filter("@scope/app", "@scope/app"));
+    assert!(!matches_filter("@scope/app", "@scope/apps"));
+    assert!(matches_filter("@scope/*", "@scope/app"));
+    assert!(!matches_filter("@scope/*", "@other/app"));
+    assert!(matches_filter("*app*", "@scope/my-app-server"));
+    assert!(matches_filter("@*/a*p", "@scope/app"));
+    assert!(!matches_filter("@*/a*p", "@scope/apps"));
+    assert!(matches_filter("*", "anything"));
+  }
+}
@@ -2055,8 +2055,13 @@ declare namespace Deno {
   export namespace jupyter {
     /** @category Jupyter */
     export interface DisplayOptions {
+      /** Whether the object is already a {@linkcode MediaBundle}, which is
+       * displayed as is. */
       raw?: boolean;
+      /** Whether to update the existing output with the `display_id`
+       * instead of creating a new output. */
       update?: boolean;
+      /** An identifier for the output, which allows it to be updated later. */
       display_id?: string;
     }
 
@@ -2111,11 +2116,28 @@ declare namespace Deno {
      * Mimics the behavior of IPython's `display(obj, raw=True)` function to allow
      * asynchronous displaying of objects in Jupyter.
      *
+     * Outputs which are displayed with a `display_id` can be updated later,
+     * for example to show progress:
+     *
+     * ```typescript
+     * await Deno.jupyter.display(Deno.jupyter.md`Loading...`, {
+     *   display_id: "progress",
+     * });
+     * // ...
+     * await Deno.jupyter.display(Deno.jupyter.md`Done!`, {
+     *   display_id: "progress",
+     *   update: true,
+     * });
+     * ```
+     *
      * @param obj - The object to be displayed
      * @param options - Display options with a default { raw: true }
      * @category Jupyter
      */
-    export function display(obj: unknown, options?: DisplayOptions): void;
+    export function display(
+      obj: unknown,
+      options?: DisplayOptions,
+    ): Promise<void>;
 
     /**
      * Show Markdown in Jupyter frontends with a tagged template function.
@@ -2191,6 +2213,87 @@ declare namespace Deno {
      */
     export function format(obj: unknown): MediaBundle;
 
+    /**
+     * A message sent over a {@linkcode Comm}.
+     *
+     * @category Jupyter */
+    export interface CommMessage {
+      data: Record<string, unknown>;
+      buffers: Uint8Array[];
+    }
+
+    /**
+     * A channel for custom messages between the kernel and a Jupyter
+     * frontend, such as the ones used by interactive widgets.
+     *
+     * @category Jupyter */
+    export interface Comm {
+      /** The unique identifier of the comm. */
+      readonly id: string;
+      /** The name of the target which the comm was opened to. */
+      readonly targetName: string;
+      /** Whether the comm was closed by either side. */
+      readonly closed: boolean;
+      /** Sends a message to the other side of the comm. */
+      send(
+        data?: Record<string, unknown>,
+        extra?: {
+          metadata?: Record<string, unknown>;
+          buffers?: Uint8Array[];
+        },
+      ): Promise<void>;
+      /** Closes the comm, optionally sending a final message. */
+      close(
+        data?: Record<string, unknown>,
+        extra?: {
+          metadata?: Record<string, unknown>;
+          buffers?: Uint8Array[];
+        },
+      ): Promise<void>;
+      /** Adds a listener for the messages sent by the frontend. */
+      onMessage(listener: (message: CommMessage) => void | Promise<void>): void;
+      /** Adds a listener for when the frontend closes the comm. */
+      onClose(listener: (message: CommMessage) => void | Promise<void>): void;
+    }
+
+    /**
+     * Opens a comm to a target which is registered in the frontend, such as
+     * `"jupyter.widget"` for widgets.
+     *
+     * ```typescript
+     * const comm = await Deno.jupyter.openComm("my_target", { value: 1 });
+     * comm.onMessage(({ data }) => console.log("received", data));
+     * await comm.send({ value: 2 });
+     * ```
+     *
+     * @category Jupyter */
+    export function openComm(
+      targetName: string,
+      data?: Record<string, unknown>,
+      extra?: {
+        metadata?: Record<string, unknown>;
+        buffers?: Uint8Array[];
+      },
+    ): Promise<Comm>;
+
+    /**
+     * Registers a target which the frontend can open comms to. The callback
+     * is called with each comm that's opened along with its first message.
+     *
+     * ```typescript
+     * Deno.jupyter.registerCommTarget("my_target", (comm, { data }) => {
+     *   comm.onMessage(async ({ data }) => {
+     *     await comm.send({ echo: data });
+     *   });
+     * });
+     * ```
+     *
+     * @category Jupyter */
+    export function registerCommTarget(
+      targetName: string,
+      callback: (comm: Comm, message: CommMessage) => void | Promise<void>,
+    ): void;
+
     /**
      * Broadcast a message on IO pub channel.
      *
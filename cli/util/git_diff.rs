@@ -0,0 +1,163 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Getting the lines of files which were changed, either since a git ref or
+//! by a unified diff.
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Zero based line indexes, with an exclusive end.
+pub type LineRange = Range<usize>;
+
+/// The lines of each file which were changed, keyed by the canonicalized
+/// file path.
+#[derive(Debug, Default)]
+pub struct ChangedLines(HashMap<PathBuf, Vec<LineRange>>);
+
+impl ChangedLines {
+  /// Gets the lines which differ from the provided git ref in the working
+  /// tree of the git repository containing `cwd`. Untracked files are
+  /// considered to be changed entirely.
+  pub fn from_git(cwd: &Path, git_ref: &str) -> Result<Self, AnyError> {
+    let root = PathBuf::from(
+      run_git(cwd, &["rev-parse", "--show-toplevel"])?.trim_end(),
+    );
+    let root = crate::util::fs::canonicalize_path(&root)?;
+    // the paths are parsed from the output, so don't let the user's git
+    // configuration change how they're printed
+    let diff = run_git(
+      &root,
+      &[
+        "-c",
+        "core.quotePath=false",
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "--unified=0",
+        git_ref,
+        "--",
+      ],
+    )?;
+    let mut changed_lines = Self(parse_diff_hunks(&diff, &root));
+    let untracked = run_git(
+      &root,
+      &[
+        "-c",
+        "core.quotePath=false",
+        "ls-files",
+        "--others",
+        "--exclude-standard",
+      ],
+    )?;
+    for path in untracked.lines().filter(|line| !line.is_empty()) {
+      changed_lines.0.insert(root.join(path), vec![0..usize::MAX]);
+    }
+    Ok(changed_lines)
+  }
+
+  /// Gets the lines which were added or changed by a unified diff, where the
+  /// paths in the diff are relative to `root`.
+  pub fn from_unified_diff(diff: &str, root: &Path) -> Self {
+    Self(parse_diff_hunks(diff, root))
+  }
+
+  pub fn get(&self, path: &Path) -> Option<&[LineRange]> {
+    self.0.get(path).map(|ranges| ranges.as_slice())
+  }
+}
+
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String, AnyError> {
+  let output = Command::new("git")
+    .args(args)
+    .current_dir(cwd)
+    .output()
+    .context("Failed to run git. Is it installed and on the PATH?")?;
+  if !output.status.success() {
+    bail!(
+      "git {} failed: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Parses the post-image line ranges of the hunks in the output of
+/// `git diff --unified=0`.
+fn parse_diff_hunks(
+  diff: &str,
+  root: &Path,
+) -> HashMap<PathBuf, Vec<LineRange>> {
+  let mut result: HashMap<PathBuf, Vec<LineRange>> = HashMap::new();
+  let mut current_file: Option<PathBuf> = None;
+  for line in diff.lines() {
+    if let Some(path) = line.strip_prefix("+++ ") {
+      current_file = path.strip_prefix("b/").map(|path| root.join(path));
+    } else if let Some(hunk) = line.strip_prefix("@@ ") {
+      let Some(file) = &current_file else {
+        continue;
+      };
+      // @@ -start[,count] +start[,count] @@
+      let Some(new_range) = hunk
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))
+      else {
+        continue;
+      };
+      let (start, count) = match new_range.split_once(',') {
+        Some((start, count)) => {
+          (start.parse::<usize>(), count.parse::<usize>())
+        }
+        None => (new_range.parse::<usize>(), Ok(1)),
+      };
+      let (Ok(start), Ok(count)) = (start, count) else {
+        continue;
+      };
+      let range = if count == 0 {
+        // lines were only removed after the line `start`, which is an empty
+        // range between that line and the next one
+        start..start
+      } else {
+        start - 1..start - 1 + count
+      };
+      result.entry(file.clone()).or_default().push(range);
+    }
+  }
+  result
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn parses_diff_hunks() {
+    let diff = concat!(
+      "diff --git a/a.ts b/a.ts\n",
+      "--- a/a.ts\n",
+      "+++ b/a.ts\n",
+      "@@ -1 +1 @@\n",
+      "-const a=1\n",
+      "+const a=2\n",
+      "@@ -5,2 +5,3 @@ function foo() {\n",
+      "@@ -10,2 +11,0 @@\n",
+      "diff --git a/b.ts b/b.ts\n",
+      "deleted file mode 100644\n",
+      "--- a/b.ts\n",
+      "+++ /dev/null\n",
+      "@@ -1 +0,0 @@\n",
+    );
+    let root = PathBuf::from("/repo");
+    let hunks = parse_diff_hunks(diff, &root);
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[&root.join("a.ts")], vec![0..1, 4..7, 11..11]);
+  }
+}
@@ -36,6 +36,7 @@ use deno_runtime::fmt_errors::format_js_error;
 use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::ops::worker_host::CreateWebWorkerCb;
 use deno_runtime::permissions::PermissionsContainer;
+use deno_runtime::web_worker::CreateCoverageCollectorCb;
 use deno_runtime::web_worker::WebWorker;
 use deno_runtime::web_worker::WebWorkerOptions;
 use deno_runtime::worker::MainWorker;
@@ -52,8 +53,9 @@ use crate::args::StorageKeyResolver;
 use crate::emit::Emitter;
 use crate::errors;
 use crate::npm::CliNpmResolver;
-use crate::tools;
 use crate::tools::coverage::CoverageCollector;
+use crate::tools::coverage::PendingWorkerCoverage;
+use crate::tools::coverage::WorkerCoverageCollector;
 use crate::tools::run::hmr::HmrRunner;
 use crate::util::checksum;
 use crate::util::file_watcher::WatcherCommunicator;
@@ -126,6 +128,7 @@ struct SharedWorkerState {
   feature_checker: Arc<FeatureChecker>,
   node_ipc: Option<i64>,
   disable_deprecated_api_warning: bool,
+  pending_worker_coverage: Arc<PendingWorkerCoverage>,
 }
 
 impl SharedWorkerState {
@@ -330,8 +333,11 @@ impl CliMainWorker {
       let session = self.worker.create_inspector_session().await;
 
       let coverage_dir = PathBuf::from(coverage_dir);
-      let mut coverage_collector =
-        tools::coverage::CoverageCollector::new(coverage_dir, session);
+      let mut coverage_collector = CoverageCollector::new_for_main_worker(
+        coverage_dir,
+        session,
+        self.shared.pending_worker_coverage.clone(),
+      );
       self
         .worker
         .js_runtime
@@ -429,6 +435,7 @@ impl CliMainWorkerFactory {
         feature_checker,
         node_ipc,
         disable_deprecated_api_warning,
+        pending_worker_coverage: Default::default(),
       }),
     }
   }
@@ -729,6 +736,20 @@ impl CliMainWorkerFactory {
   }
 }
 
+fn create_coverage_collector_callback(
+  coverage_dir: &str,
+  pending_worker_coverage: Arc<PendingWorkerCoverage>,
+) -> Arc<CreateCoverageCollectorCb> {
+  let coverage_dir = PathBuf::from(coverage_dir);
+  Arc::new(move |session| {
+    Box::new(WorkerCoverageCollector::new(
+      coverage_dir.clone(),
+      session,
+      pending_worker_coverage.clone(),
+    ))
+  })
+}
+
 fn create_web_worker_callback(
   shared: Arc<SharedWorkerState>,
   stdio: deno_runtime::deno_io::Stdio,
@@ -818,6 +839,14 @@ fn create_web_worker_callback(
       stdio: stdio.clone(),
       cache_storage_dir,
       feature_checker,
+      create_coverage_collector_cb: shared.options.coverage_dir.as_ref().map(
+        |coverage_dir| {
+          create_coverage_collector_callback(
+            coverage_dir,
+            shared.pending_worker_coverage.clone(),
+          )
+        },
+      ),
     };
 
     WebWorker::bootstrap_from_options(
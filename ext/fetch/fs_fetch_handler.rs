@@ -7,30 +7,58 @@ use crate::FetchHandler;
 use deno_core::error::type_error;
 use deno_core::futures::FutureExt;
 use deno_core::futures::TryFutureExt;
+use deno_core::unsync::spawn;
 use deno_core::url::Url;
 use deno_core::CancelFuture;
 use deno_core::OpState;
+use deno_fs::FileSystemRc;
+use deno_fs::OpenOptions;
 use reqwest::StatusCode;
 use std::rc::Rc;
+use tokio::io::AsyncWriteExt;
 use tokio_util::io::ReaderStream;
 
-/// An implementation which tries to read file URLs from the file system via
-/// tokio::fs.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// An implementation which tries to read file URLs from the file system of
+/// the runtime when `deno_fs` is used, so virtual file systems such as the
+/// one of `deno compile` are taken into account, otherwise via tokio::fs.
 #[derive(Clone)]
 pub struct FsFetchHandler;
 
 impl FetchHandler for FsFetchHandler {
   fn fetch_file(
     &self,
-    _state: &mut OpState,
+    state: &mut OpState,
     url: Url,
   ) -> (CancelableResponseFuture, Option<Rc<CancelHandle>>) {
     let cancel_handle = CancelHandle::new_rc();
+    let maybe_fs = state.try_borrow::<FileSystemRc>().cloned();
     let response_fut = async move {
       let path = url.to_file_path()?;
-      let file = tokio::fs::File::open(path).map_err(|_| ()).await?;
-      let stream = ReaderStream::new(file);
-      let body = reqwest::Body::wrap_stream(stream);
+      let body = match maybe_fs {
+        Some(fs) => {
+          let file = fs
+            .open_async(path, OpenOptions::read())
+            .map_err(|_| ())
+            .await?;
+          // the file isn't `Send`, so it's read on this thread and piped to
+          // the body
+          let (mut writer, reader) = tokio::io::duplex(CHUNK_SIZE);
+          spawn(async move {
+            while let Ok(buf) = file.clone().read(CHUNK_SIZE).await {
+              if buf.is_empty() || writer.write_all(&buf).await.is_err() {
+                break;
+              }
+            }
+          });
+          reqwest::Body::wrap_stream(ReaderStream::new(reader))
+        }
+        None => {
+          let file = tokio::fs::File::open(path).map_err(|_| ()).await?;
+          reqwest::Body::wrap_stream(ReaderStream::new(file))
+        }
+      };
       let response = http_v02::Response::builder()
         .status(StatusCode::OK)
         .body(body)
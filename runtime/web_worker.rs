@@ -17,6 +17,7 @@ use deno_core::error::AnyError;
 use deno_core::error::JsError;
 use deno_core::futures::channel::mpsc;
 use deno_core::futures::future::poll_fn;
+use deno_core::futures::future::LocalBoxFuture;
 use deno_core::futures::stream::StreamExt;
 use deno_core::futures::task::AtomicWaker;
 use deno_core::located_script_name;
@@ -30,6 +31,7 @@ use deno_core::Extension;
 use deno_core::FeatureChecker;
 use deno_core::GetErrorClassFn;
 use deno_core::JsRuntime;
+use deno_core::LocalInspectorSession;
 use deno_core::ModuleCodeString;
 use deno_core::ModuleId;
 use deno_core::ModuleLoader;
@@ -318,6 +320,17 @@ fn create_handles(
   (internal_handle, external_handle)
 }
 
+/// Collects the code coverage of a web worker through an inspector session,
+/// from before its main module is executed until it finishes.
+pub trait WebWorkerCoverageCollector {
+  fn start_collecting(&mut self) -> LocalBoxFuture<'_, Result<(), AnyError>>;
+  fn stop_collecting(&mut self) -> LocalBoxFuture<'_, Result<(), AnyError>>;
+}
+
+pub type CreateCoverageCollectorCb = dyn Fn(LocalInspectorSession) -> Box<dyn WebWorkerCoverageCollector>
+  + Sync
+  + Send;
+
 /// This struct is an implementation of `Worker` Web API
 ///
 /// Each `WebWorker` is either a child of `MainWorker` or other
@@ -331,6 +344,7 @@ pub struct WebWorker {
   pub main_module: ModuleSpecifier,
   poll_for_messages_fn: Option<v8::Global<v8::Value>>,
   bootstrap_fn_global: Option<v8::Global<v8::Function>>,
+  create_coverage_collector_cb: Option<Arc<CreateCoverageCollectorCb>>,
 }
 
 pub struct WebWorkerOptions {
@@ -356,6 +370,7 @@ pub struct WebWorkerOptions {
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub stdio: Stdio,
   pub feature_checker: Arc<FeatureChecker>,
+  pub create_coverage_collector_cb: Option<Arc<CreateCoverageCollectorCb>>,
 }
 
 impl WebWorker {
@@ -608,6 +623,7 @@ impl WebWorker {
         main_module,
         poll_for_messages_fn: None,
         bootstrap_fn_global: Some(bootstrap_fn_global),
+        create_coverage_collector_cb: options.create_coverage_collector_cb,
       },
       external_handle,
     )
@@ -707,6 +723,43 @@ impl WebWorker {
     }
   }
 
+  async fn maybe_start_coverage_collector(
+    &mut self,
+  ) -> Result<Option<Box<dyn WebWorkerCoverageCollector>>, AnyError> {
+    let Some(create_coverage_collector_cb) =
+      self.create_coverage_collector_cb.clone()
+    else {
+      return Ok(None);
+    };
+    self.js_runtime.maybe_init_inspector();
+    let session = self.js_runtime.inspector().borrow().create_local_session();
+    let mut coverage_collector = create_coverage_collector_cb(session);
+    self
+      .js_runtime
+      .with_event_loop_future(
+        coverage_collector.start_collecting(),
+        PollEventLoopOptions::default(),
+      )
+      .await?;
+    Ok(Some(coverage_collector))
+  }
+
+  async fn stop_coverage_collector(
+    &mut self,
+    mut coverage_collector: Box<dyn WebWorkerCoverageCollector>,
+  ) -> Result<(), AnyError> {
+    // the coverage is taken through the inspector, which can't run on an
+    // isolate whose execution was terminated by `close()` or `terminate()`
+    self.js_runtime.v8_isolate().cancel_terminate_execution();
+    self
+      .js_runtime
+      .with_event_loop_future(
+        coverage_collector.stop_collecting(),
+        PollEventLoopOptions::default(),
+      )
+      .await
+  }
+
   /// Loads, instantiates and executes specified JavaScript module.
   ///
   /// This module will have "import.meta.main" equal to true.
@@ -829,6 +882,18 @@ pub fn run_web_worker(
   let fut = async move {
     let internal_handle = worker.internal_handle.clone();
 
+    let mut maybe_coverage_collector =
+      match worker.maybe_start_coverage_collector().await {
+        Ok(maybe_coverage_collector) => maybe_coverage_collector,
+        Err(e) => {
+          print_worker_error(&e, &name, format_js_error_fn.as_deref());
+          internal_handle
+            .post_event(WorkerControlEvent::TerminalError(e))
+            .expect("Failed to post message to host");
+          return Ok(());
+        }
+      };
+
     // Execute provided source code immediately
     let result = if let Some(source_code) = maybe_source_code.take() {
       let r = worker.execute_script(located_script_name!(), source_code.into());
@@ -849,6 +914,12 @@ pub fn run_web_worker(
     // If sender is closed it means that worker has already been closed from
     // within using "globalThis.close()"
     if internal_handle.is_terminated() {
+      if let Some(coverage_collector) = maybe_coverage_collector.take() {
+        if let Err(e) = worker.stop_coverage_collector(coverage_collector).await
+        {
+          print_worker_error(&e, &name, format_js_error_fn.as_deref());
+        }
+      }
       return Ok(());
     }
 
@@ -863,6 +934,15 @@ pub fn run_web_worker(
       result
     };
 
+    let result = match maybe_coverage_collector.take() {
+      Some(coverage_collector) => {
+        let stop_result =
+          worker.stop_coverage_collector(coverage_collector).await;
+        result.and(stop_result)
+      }
+      None => result,
+    };
+
     if let Err(e) = result {
       print_worker_error(&e, &name, format_js_error_fn.as_deref());
       internal_handle